@@ -0,0 +1,1053 @@
+use crate::core::{
+    cigar::Cigar,
+    common::{supported, Version, CURRENT_VERSION},
+    counter::{tables as counter, Counter},
+    dater::Dater,
+    indexer::Indexer,
+    matter::Matter,
+    pather::Pather,
+    prefixer::Prefixer,
+    saider::Saider,
+    seqner::Seqner,
+    siger::Siger,
+    verfer::Verfer,
+};
+use crate::error::{err, Error, Result};
+
+/// A decoded attachment group. Each variant corresponds to a group code in `counter::Codex` and
+/// holds the typed primitives that follow the counter in the stream.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Group {
+    ControllerIdxSigs(Vec<Siger>),
+    WitnessIdxSigs(Vec<Siger>),
+    NonTransReceiptCouples(Vec<(Prefixer, Cigar)>),
+    TransReceiptQuadruples(Vec<(Prefixer, Seqner, Saider, Siger)>),
+    FirstSeenReplayCouples(Vec<(Seqner, Dater)>),
+    TransIdxSigGroups(Vec<(Prefixer, Seqner, Saider, Vec<Siger>)>),
+    SealSourceCouples(Vec<(Seqner, Saider)>),
+    TransLastIdxSigGroups(Vec<(Prefixer, Vec<Siger>)>),
+    SealSourceTriples(Vec<(Prefixer, Seqner, Saider)>),
+    // each path is followed by exactly one TransIdxSigGroups, ControllerIdxSigs or
+    // NonTransReceiptCouples group
+    SadPathSig(Vec<(Pather, Group)>),
+    SadPathSigGroup(Pather, Vec<(Pather, Group)>),
+    PathedMaterialQuadlets(Pather, Vec<Group>),
+}
+
+impl Group {
+    pub fn qb64(&self) -> Result<String> {
+        Ok(String::from_utf8(self.qb64b()?)?)
+    }
+
+    pub fn qb64b(&self) -> Result<Vec<u8>> {
+        self.encode(Domain::Text)
+    }
+
+    pub fn qb2(&self) -> Result<Vec<u8>> {
+        self.encode(Domain::Binary)
+    }
+
+    fn encode(&self, domain: Domain) -> Result<Vec<u8>> {
+        let mut out = vec![];
+        match self {
+            Group::ControllerIdxSigs(sigers) => {
+                out.append(&mut domain.counter(counter::Codex::ControllerIdxSigs, sigers.len())?);
+                for siger in sigers {
+                    out.append(&mut domain.indexer(siger)?);
+                }
+            }
+            Group::WitnessIdxSigs(sigers) => {
+                out.append(&mut domain.counter(counter::Codex::WitnessIdxSigs, sigers.len())?);
+                for siger in sigers {
+                    out.append(&mut domain.indexer(siger)?);
+                }
+            }
+            Group::NonTransReceiptCouples(couples) => {
+                out.append(
+                    &mut domain.counter(counter::Codex::NonTransReceiptCouples, couples.len())?,
+                );
+                for (prefixer, cigar) in couples {
+                    out.append(&mut domain.matter(prefixer)?);
+                    out.append(&mut domain.matter(cigar)?);
+                }
+            }
+            Group::TransReceiptQuadruples(quadruples) => {
+                out.append(
+                    &mut domain
+                        .counter(counter::Codex::TransReceiptQuadruples, quadruples.len())?,
+                );
+                for (prefixer, seqner, saider, siger) in quadruples {
+                    out.append(&mut domain.matter(prefixer)?);
+                    out.append(&mut domain.matter(seqner)?);
+                    out.append(&mut domain.matter(saider)?);
+                    out.append(&mut domain.indexer(siger)?);
+                }
+            }
+            Group::FirstSeenReplayCouples(couples) => {
+                out.append(
+                    &mut domain.counter(counter::Codex::FirstSeenReplayCouples, couples.len())?,
+                );
+                for (seqner, dater) in couples {
+                    out.append(&mut domain.matter(seqner)?);
+                    out.append(&mut domain.matter(dater)?);
+                }
+            }
+            Group::TransIdxSigGroups(groups) => {
+                out.append(&mut domain.counter(counter::Codex::TransIdxSigGroups, groups.len())?);
+                for (prefixer, seqner, saider, sigers) in groups {
+                    out.append(&mut domain.matter(prefixer)?);
+                    out.append(&mut domain.matter(seqner)?);
+                    out.append(&mut domain.matter(saider)?);
+                    out.append(&mut Group::ControllerIdxSigs(sigers.clone()).encode(domain)?);
+                }
+            }
+            Group::SealSourceCouples(couples) => {
+                out.append(&mut domain.counter(counter::Codex::SealSourceCouples, couples.len())?);
+                for (seqner, saider) in couples {
+                    out.append(&mut domain.matter(seqner)?);
+                    out.append(&mut domain.matter(saider)?);
+                }
+            }
+            Group::TransLastIdxSigGroups(groups) => {
+                out.append(
+                    &mut domain.counter(counter::Codex::TransLastIdxSigGroups, groups.len())?,
+                );
+                for (prefixer, sigers) in groups {
+                    out.append(&mut domain.matter(prefixer)?);
+                    out.append(&mut Group::ControllerIdxSigs(sigers.clone()).encode(domain)?);
+                }
+            }
+            Group::SealSourceTriples(triples) => {
+                out.append(&mut domain.counter(counter::Codex::SealSourceTriples, triples.len())?);
+                for (prefixer, seqner, saider) in triples {
+                    out.append(&mut domain.matter(prefixer)?);
+                    out.append(&mut domain.matter(seqner)?);
+                    out.append(&mut domain.matter(saider)?);
+                }
+            }
+            Group::SadPathSig(sigs) => {
+                out.append(&mut domain.counter(counter::Codex::SadPathSig, sigs.len())?);
+                for (pather, group) in sigs {
+                    out.append(&mut domain.matter(pather)?);
+                    out.append(&mut group.encode(domain)?);
+                }
+            }
+            Group::SadPathSigGroup(root, sigs) => {
+                out.append(&mut domain.counter(counter::Codex::SadPathSigGroup, 1)?);
+                out.append(&mut domain.matter(root)?);
+                out.append(&mut Group::SadPathSig(sigs.clone()).encode(domain)?);
+            }
+            Group::PathedMaterialQuadlets(pather, groups) => {
+                let mut inner = domain.matter(pather)?;
+                for group in groups {
+                    inner.append(&mut group.encode(domain)?);
+                }
+                out.append(&mut domain.counter(
+                    counter::Codex::PathedMaterialQuadlets,
+                    domain.quadlets(inner.len()),
+                )?);
+                out.append(&mut inner);
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+/// An ordered run of attachment groups, serialized as a single `AttachedMaterialQuadlets`
+/// group so a receiver knows where the attachments end. Runs too long to be counted by
+/// `AttachedMaterialQuadlets` are framed with `BigAttachedMaterialQuadlets` instead.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Attachments {
+    groups: Vec<Group>,
+}
+
+impl Attachments {
+    pub fn new(groups: &[Group]) -> Self {
+        Attachments { groups: groups.to_vec() }
+    }
+
+    pub fn new_with_qb64(qb64: &str) -> Result<Self> {
+        Self::new_with_qb64b(qb64.as_bytes())
+    }
+
+    pub fn new_with_qb64b(qb64b: &[u8]) -> Result<Self> {
+        Ok(Attachments { groups: Extractor::new(qb64b, Domain::Text, CURRENT_VERSION).groups()? })
+    }
+
+    pub fn new_with_qb2(qb2: &[u8]) -> Result<Self> {
+        Ok(Attachments { groups: Extractor::new(qb2, Domain::Binary, CURRENT_VERSION).groups()? })
+    }
+
+    pub fn groups(&self) -> Vec<Group> {
+        self.groups.clone()
+    }
+
+    pub fn push(&mut self, group: Group) {
+        self.groups.push(group);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    pub fn qb64(&self) -> Result<String> {
+        Ok(String::from_utf8(self.qb64b()?)?)
+    }
+
+    pub fn qb64b(&self) -> Result<Vec<u8>> {
+        self.encode(Domain::Text)
+    }
+
+    pub fn qb2(&self) -> Result<Vec<u8>> {
+        self.encode(Domain::Binary)
+    }
+
+    fn encode(&self, domain: Domain) -> Result<Vec<u8>> {
+        let mut inner = vec![];
+        for group in &self.groups {
+            inner.append(&mut group.encode(domain)?);
+        }
+
+        let quadlets = domain.quadlets(inner.len());
+        let code = if quadlets < 64_usize.pow(2) {
+            counter::Codex::AttachedMaterialQuadlets
+        } else {
+            counter::Codex::BigAttachedMaterialQuadlets
+        };
+
+        let mut out = domain.counter(code, quadlets)?;
+        out.append(&mut inner);
+        Ok(out)
+    }
+}
+
+/// The encoding of a run of attachments, determined by the first tritet of its counter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Domain {
+    Text,
+    Binary,
+}
+
+impl Domain {
+    // the smallest counter in either domain, used to avoid slicing into a partial code
+    fn minimum_counter_size(&self) -> usize {
+        match self {
+            Domain::Text => 4,
+            Domain::Binary => 3,
+        }
+    }
+
+    fn size(&self, qb64_size: usize) -> usize {
+        match self {
+            Domain::Text => qb64_size,
+            Domain::Binary => qb64_size * 3 / 4,
+        }
+    }
+
+    // the number of quadlets (or triplets, in binary) in `size` bytes of material
+    fn quadlets(&self, size: usize) -> usize {
+        match self {
+            Domain::Text => size / 4,
+            Domain::Binary => size / 3,
+        }
+    }
+
+    fn counter(&self, code: &str, count: usize) -> Result<Vec<u8>> {
+        let count = u32::try_from(count)
+            .map_err(|_| Error::InvalidVarIndex(format!("count too large: count = {count}")))?;
+        let counter = Counter::new_with_code_and_count(code, count)?;
+        match self {
+            Domain::Text => counter.qb64b(),
+            Domain::Binary => counter.qb2(),
+        }
+    }
+
+    fn matter(&self, matter: &impl Matter) -> Result<Vec<u8>> {
+        match self {
+            Domain::Text => matter.qb64b(),
+            Domain::Binary => matter.qb2(),
+        }
+    }
+
+    fn indexer(&self, indexer: &impl Indexer) -> Result<Vec<u8>> {
+        match self {
+            Domain::Text => indexer.qb64b(),
+            Domain::Binary => indexer.qb2(),
+        }
+    }
+}
+
+// the longest prefix of bytes that is valid utf-8. text domain primitives must not choke on
+// binary material that may follow them in a stream.
+fn text(bytes: &[u8]) -> &[u8] {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => bytes,
+        Err(e) => &bytes[..e.valid_up_to()],
+    }
+}
+
+// the span was complete, so running short inside it is malformed material
+fn truncated(e: anyhow::Error) -> anyhow::Error {
+    if let Some(Error::Shortage(s)) = e.downcast_ref::<Error>() {
+        Error::Parsing(format!("truncated group: {s}")).into()
+    } else {
+        e
+    }
+}
+
+/// Walks a byte slice, extracting primitives in a single domain and tracking how much has been
+/// consumed. Counters are read from the code table of the active genus version, which a
+/// `KERIACDCGenusVersion` counter in the stream switches.
+pub(crate) struct Extractor<'a> {
+    ims: &'a [u8],
+    offset: usize,
+    domain: Domain,
+    gvrsn: Version,
+    framed: bool,
+}
+
+impl<'a> Extractor<'a> {
+    pub(crate) fn new(ims: &'a [u8], domain: Domain, gvrsn: &Version) -> Self {
+        Extractor { ims, offset: 0, domain, gvrsn: gvrsn.clone(), framed: false }
+    }
+
+    pub(crate) fn gvrsn(&self) -> Version {
+        self.gvrsn.clone()
+    }
+
+    pub(crate) fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether an AttachedMaterialQuadlets frame has been unwrapped.
+    pub(crate) fn framed(&self) -> bool {
+        self.framed
+    }
+
+    pub(crate) fn remaining(&self) -> &'a [u8] {
+        &self.ims[self.offset..]
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.offset >= self.ims.len()
+    }
+
+    fn advance(&mut self, qb64_size: usize) {
+        self.offset += self.domain.size(qb64_size);
+    }
+
+    fn ensure(&self, size: usize) -> Result<()> {
+        let available = self.ims.len() - self.offset;
+        if available < size {
+            return err!(Error::Shortage(format!(
+                "need {} more bytes to extract attachment",
+                size - available
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn counter(&mut self) -> Result<Counter> {
+        self.ensure(self.domain.minimum_counter_size())?;
+        let rest = self.remaining();
+        let counter = match self.domain {
+            Domain::Text => Counter::new_with_gvrsn(
+                None,
+                None,
+                None,
+                Some(text(rest)),
+                None,
+                None,
+                &self.gvrsn,
+            )?,
+            Domain::Binary => {
+                Counter::new_with_gvrsn(None, None, None, None, None, Some(rest), &self.gvrsn)?
+            }
+        };
+        self.advance(counter.full_size()?);
+        Ok(counter)
+    }
+
+    fn matter<T: Matter>(
+        &mut self,
+        qb64b: fn(&[u8]) -> Result<T>,
+        qb2: fn(&[u8]) -> Result<T>,
+    ) -> Result<T> {
+        self.ensure(1)?;
+        let rest = self.remaining();
+        let matter = match self.domain {
+            Domain::Text => qb64b(text(rest))?,
+            Domain::Binary => qb2(rest)?,
+        };
+        self.advance(matter.full_size()?);
+        Ok(matter)
+    }
+
+    fn siger(&mut self) -> Result<Siger> {
+        self.ensure(1)?;
+        let rest = self.remaining();
+        let siger = match self.domain {
+            Domain::Text => Siger::new_with_qb64b(text(rest), None)?,
+            Domain::Binary => Siger::new_with_qb2(rest, None)?,
+        };
+        self.advance(siger.full_size()?);
+        Ok(siger)
+    }
+
+    fn prefixer(&mut self) -> Result<Prefixer> {
+        self.matter(Prefixer::new_with_qb64b, Prefixer::new_with_qb2)
+    }
+
+    fn seqner(&mut self) -> Result<Seqner> {
+        self.matter(Seqner::new_with_qb64b, Seqner::new_with_qb2)
+    }
+
+    fn saider(&mut self) -> Result<Saider> {
+        self.matter(Saider::new_with_qb64b, Saider::new_with_qb2)
+    }
+
+    fn dater(&mut self) -> Result<Dater> {
+        self.matter(Dater::new_with_qb64b, Dater::new_with_qb2)
+    }
+
+    fn pather(&mut self) -> Result<Pather> {
+        self.matter(Pather::new_with_qb64b, Pather::new_with_qb2)
+    }
+
+    fn cigar(&mut self, verfer: &Verfer) -> Result<Cigar> {
+        let cigar =
+            self.matter(|b| Cigar::new_with_qb64b(b, None), |b| Cigar::new_with_qb2(b, None))?;
+        Cigar::new_with_raw(&cigar.raw(), Some(verfer), Some(&cigar.code()))
+    }
+
+    // the 1.0 code of the group a counter introduces. 2.0 groups that share a layout with a 1.0
+    // group are mapped onto it, big variants included.
+    fn code(&self, counter: &Counter) -> Result<String> {
+        if self.gvrsn.major < 2 {
+            return Ok(counter.code());
+        }
+
+        Ok(match counter.code().as_str() {
+            counter::Codex_2_0::AttachmentGroup => counter::Codex::AttachedMaterialQuadlets,
+            counter::Codex_2_0::BigAttachmentGroup => counter::Codex::BigAttachedMaterialQuadlets,
+            counter::Codex_2_0::ControllerIdxSigs | counter::Codex_2_0::BigControllerIdxSigs => {
+                counter::Codex::ControllerIdxSigs
+            }
+            counter::Codex_2_0::WitnessIdxSigs | counter::Codex_2_0::BigWitnessIdxSigs => {
+                counter::Codex::WitnessIdxSigs
+            }
+            counter::Codex_2_0::NonTransReceiptCouples
+            | counter::Codex_2_0::BigNonTransReceiptCouples => {
+                counter::Codex::NonTransReceiptCouples
+            }
+            counter::Codex_2_0::TransReceiptQuadruples
+            | counter::Codex_2_0::BigTransReceiptQuadruples => {
+                counter::Codex::TransReceiptQuadruples
+            }
+            counter::Codex_2_0::FirstSeenReplayCouples
+            | counter::Codex_2_0::BigFirstSeenReplayCouples => {
+                counter::Codex::FirstSeenReplayCouples
+            }
+            counter::Codex_2_0::TransIdxSigGroups | counter::Codex_2_0::BigTransIdxSigGroups => {
+                counter::Codex::TransIdxSigGroups
+            }
+            counter::Codex_2_0::TransLastIdxSigGroups
+            | counter::Codex_2_0::BigTransLastIdxSigGroups => counter::Codex::TransLastIdxSigGroups,
+            counter::Codex_2_0::SealSourceCouples | counter::Codex_2_0::BigSealSourceCouples => {
+                counter::Codex::SealSourceCouples
+            }
+            counter::Codex_2_0::SealSourceTriples | counter::Codex_2_0::BigSealSourceTriples => {
+                counter::Codex::SealSourceTriples
+            }
+            counter::Codex_2_0::KERIACDCGenusVersion => counter::Codex::KERIACDCGenusVersion,
+            _ => {
+                return err!(Error::UnexpectedCountCode(format!(
+                    "unsupported attachment group: code = {code}",
+                    code = counter.code()
+                )))
+            }
+        }
+        .to_string())
+    }
+
+    // the items of a group. 1.0 counts items, 2.0 counts the quadlets (or triplets, in binary)
+    // the items span.
+    fn repeat<T>(
+        &mut self,
+        count: u32,
+        mut item: impl FnMut(&mut Extractor<'a>) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        if self.gvrsn.major < 2 {
+            let mut items = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                items.push(item(self)?);
+            }
+            return Ok(items);
+        }
+
+        let span = self.quadlets(count)?;
+        let mut extractor = Extractor::new(span, self.domain, &self.gvrsn);
+        let mut items = vec![];
+        while !extractor.is_empty() {
+            items.push(item(&mut extractor).map_err(truncated)?);
+        }
+        Ok(items)
+    }
+
+    // a nested ControllerIdxSigs group, as found in TransIdxSigGroups and TransLastIdxSigGroups
+    fn controller_idx_sigs(&mut self) -> Result<Vec<Siger>> {
+        let counter = self.counter()?;
+        if self.code(&counter)? != counter::Codex::ControllerIdxSigs {
+            return err!(Error::UnexpectedCountCode(format!(
+                "expected controller indexed signatures, got {code}",
+                code = counter.code()
+            )));
+        }
+
+        self.repeat(counter.count(), Self::siger)
+    }
+
+    // the span of a group whose count is in quadlets (or triplets, in binary)
+    fn quadlets(&mut self, count: u32) -> Result<&'a [u8]> {
+        let size = self.domain.size(count as usize * 4);
+        self.ensure(size)?;
+        let span = &self.ims[self.offset..self.offset + size];
+        self.offset += size;
+        Ok(span)
+    }
+
+    fn sad_path_sigs(&mut self, count: u32) -> Result<Vec<(Pather, Group)>> {
+        let mut sigs = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let pather = self.pather()?;
+            let counter = self.counter()?;
+            let group = match self.code(&counter)?.as_str() {
+                counter::Codex::TransIdxSigGroups
+                | counter::Codex::ControllerIdxSigs
+                | counter::Codex::NonTransReceiptCouples => self.group(&counter)?,
+                _ => {
+                    return err!(Error::UnexpectedCountCode(format!(
+                        "unexpected signature group in sad path signature: code = {code}",
+                        code = counter.code()
+                    )))
+                }
+            };
+            sigs.push((pather, group));
+        }
+        Ok(sigs)
+    }
+
+    /// Extracts the body of the group introduced by `counter`.
+    pub(crate) fn group(&mut self, counter: &Counter) -> Result<Group> {
+        let count = counter.count();
+
+        Ok(match self.code(counter)?.as_str() {
+            counter::Codex::ControllerIdxSigs => {
+                Group::ControllerIdxSigs(self.repeat(count, Self::siger)?)
+            }
+            counter::Codex::WitnessIdxSigs => {
+                Group::WitnessIdxSigs(self.repeat(count, Self::siger)?)
+            }
+            counter::Codex::NonTransReceiptCouples => {
+                Group::NonTransReceiptCouples(self.repeat(count, |e| {
+                    let prefixer = e.prefixer()?;
+                    let verfer = Verfer::new_with_qb64b(&prefixer.qb64b()?)?;
+                    let cigar = e.cigar(&verfer)?;
+                    Ok((prefixer, cigar))
+                })?)
+            }
+            counter::Codex::TransReceiptQuadruples => Group::TransReceiptQuadruples(
+                self.repeat(count, |e| Ok((e.prefixer()?, e.seqner()?, e.saider()?, e.siger()?)))?,
+            ),
+            counter::Codex::FirstSeenReplayCouples => Group::FirstSeenReplayCouples(
+                self.repeat(count, |e| Ok((e.seqner()?, e.dater()?)))?,
+            ),
+            counter::Codex::TransIdxSigGroups => {
+                Group::TransIdxSigGroups(self.repeat(count, |e| {
+                    Ok((e.prefixer()?, e.seqner()?, e.saider()?, e.controller_idx_sigs()?))
+                })?)
+            }
+            counter::Codex::SealSourceCouples => {
+                Group::SealSourceCouples(self.repeat(count, |e| Ok((e.seqner()?, e.saider()?)))?)
+            }
+            counter::Codex::TransLastIdxSigGroups => Group::TransLastIdxSigGroups(
+                self.repeat(count, |e| Ok((e.prefixer()?, e.controller_idx_sigs()?)))?,
+            ),
+            counter::Codex::SealSourceTriples => Group::SealSourceTriples(
+                self.repeat(count, |e| Ok((e.prefixer()?, e.seqner()?, e.saider()?)))?,
+            ),
+            counter::Codex::SadPathSig => Group::SadPathSig(self.sad_path_sigs(count)?),
+            counter::Codex::SadPathSigGroup => {
+                let root = self.pather()?;
+                let mut sigs = vec![];
+                for _ in 0..count {
+                    let counter = self.counter()?;
+                    if counter.code() != counter::Codex::SadPathSig {
+                        return err!(Error::UnexpectedCountCode(format!(
+                            "expected sad path signature, got {code}",
+                            code = counter.code()
+                        )));
+                    }
+                    sigs.append(&mut self.sad_path_sigs(counter.count())?);
+                }
+                Group::SadPathSigGroup(root, sigs)
+            }
+            counter::Codex::PathedMaterialQuadlets => {
+                let span = self.quadlets(count)?;
+                let mut extractor = Extractor::new(span, self.domain, &self.gvrsn);
+                let pather = extractor.pather().map_err(truncated)?;
+                let groups = extractor.groups().map_err(truncated)?;
+                Group::PathedMaterialQuadlets(pather, groups)
+            }
+            _ => {
+                return err!(Error::UnexpectedCountCode(format!(
+                    "unsupported attachment group: code = {code}",
+                    code = counter.code()
+                )))
+            }
+        })
+    }
+
+    /// Extracts the next counter and its group. AttachedMaterialQuadlets framing is unwrapped,
+    /// yielding every group inside it, and protocol stack and genus version counters yield
+    /// nothing. A genus version counter switches the code table used from then on.
+    pub(crate) fn next_groups(&mut self) -> Result<Vec<Group>> {
+        let counter = self.counter()?;
+        Ok(match self.code(&counter)?.as_str() {
+            counter::Codex::AttachedMaterialQuadlets
+            | counter::Codex::BigAttachedMaterialQuadlets => {
+                let span = self.quadlets(counter.count())?;
+                self.framed = true;
+                Extractor::new(span, self.domain, &self.gvrsn).groups()?
+            }
+            counter::Codex::KERIACDCGenusVersion => {
+                let gvrsn = counter.genus_version()?;
+                if !supported(&gvrsn) {
+                    return err!(Error::Value(format!(
+                        "unsupported genus version: version = {}.{}",
+                        gvrsn.major, gvrsn.minor
+                    )));
+                }
+                self.gvrsn = gvrsn;
+                vec![]
+            }
+            counter::Codex::KERIProtocolStack => vec![],
+            _ => vec![self.group(&counter)?],
+        })
+    }
+
+    /// Extracts groups until the slice is exhausted.
+    pub(crate) fn groups(&mut self) -> Result<Vec<Group>> {
+        let mut groups = vec![];
+        while !self.is_empty() {
+            groups.append(&mut self.next_groups()?);
+        }
+        Ok(groups)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Attachments, Domain, Extractor, Group};
+    use crate::core::{
+        common::{Version, CURRENT_VERSION},
+        counter::{tables as counter, Counter},
+        dater::Dater,
+        indexer::Indexer,
+        matter::Matter,
+        pather::Pather,
+        prefixer::Prefixer,
+        saider::Saider,
+        seqner::Seqner,
+        siger::Siger,
+        signer::Signer,
+    };
+    use crate::error::Error;
+    use base64::{engine::general_purpose as b64_engine, Engine};
+
+    fn signers() -> Vec<Signer> {
+        (0..3u8).map(|i| Signer::new_with_raw(&[i; 32], Some(true), None).unwrap()).collect()
+    }
+
+    // sigers extracted from a stream carry no verfer
+    fn unverfered(siger: &Siger) -> Siger {
+        Siger::new_with_qb64(&siger.qb64().unwrap(), None).unwrap()
+    }
+
+    #[test]
+    fn controller_idx_sigs() {
+        let ser = b"abcdefg";
+        let sigers: Vec<_> = signers()
+            .iter()
+            .enumerate()
+            .map(|(i, s)| unverfered(&s.sign_indexed(ser, false, i as u32, None).unwrap()))
+            .collect();
+
+        let mut qb64 = Counter::new_with_code_and_count(counter::Codex::ControllerIdxSigs, 3)
+            .unwrap()
+            .qb64()
+            .unwrap();
+        let mut qb2 = Counter::new_with_code_and_count(counter::Codex::ControllerIdxSigs, 3)
+            .unwrap()
+            .qb2()
+            .unwrap();
+        for siger in &sigers {
+            qb64 += &siger.qb64().unwrap();
+            qb2.append(&mut siger.qb2().unwrap());
+        }
+
+        let mut extractor = Extractor::new(qb64.as_bytes(), Domain::Text, CURRENT_VERSION);
+        assert_eq!(extractor.groups().unwrap(), vec![Group::ControllerIdxSigs(sigers.clone())]);
+        assert_eq!(extractor.offset(), qb64.len());
+
+        let mut extractor = Extractor::new(&qb2, Domain::Binary, CURRENT_VERSION);
+        assert_eq!(extractor.groups().unwrap(), vec![Group::ControllerIdxSigs(sigers)]);
+        assert_eq!(extractor.offset(), qb2.len());
+    }
+
+    #[test]
+    fn version_counters() {
+        let siger = unverfered(&signers()[0].sign_indexed(b"abcdefg", false, 0, None).unwrap());
+        let genus = Counter::new_with_genus_version(&Version { major: 1, minor: 0 }).unwrap();
+        let stack = Counter::new_with_code_and_count(counter::Codex::KERIProtocolStack, 0).unwrap();
+
+        let qb64 = genus.qb64().unwrap()
+            + &stack.qb64().unwrap()
+            + &Counter::new_with_code_and_count(counter::Codex::ControllerIdxSigs, 1)
+                .unwrap()
+                .qb64()
+                .unwrap()
+            + &siger.qb64().unwrap();
+
+        let groups =
+            Extractor::new(qb64.as_bytes(), Domain::Text, CURRENT_VERSION).groups().unwrap();
+        assert_eq!(groups, vec![Group::ControllerIdxSigs(vec![siger])]);
+    }
+
+    // a 2.0 counter, whose count is the size of the material that follows in quadlets
+    fn counter_2_0(code: &str, material: &str) -> String {
+        let gvrsn = Version { major: 2, minor: 0 };
+        let count = material.len() as u32 / 4;
+        Counter::new_with_gvrsn(Some(count), None, Some(code), None, None, None, &gvrsn)
+            .unwrap()
+            .qb64()
+            .unwrap()
+            + material
+    }
+
+    #[test]
+    fn version_2_groups() {
+        let ser = b"abcdefg";
+        let signers = signers();
+        let sigers: Vec<_> = signers
+            .iter()
+            .enumerate()
+            .map(|(i, s)| unverfered(&s.sign_indexed(ser, false, i as u32, None).unwrap()))
+            .collect();
+        let sigs: String = sigers.iter().map(|s| s.qb64().unwrap()).collect();
+        let prefixer = Prefixer::new_with_qb64(&signers[0].verfer().qb64().unwrap()).unwrap();
+        let seqner = Seqner::new_with_sn(1).unwrap();
+        let said = "EBAjyPZ8Ed4XXl5cVZhqAy7SuaGivQp0WqQKVXvg7oqd";
+
+        let genus = Counter::new_with_genus_version(&Version { major: 2, minor: 0 }).unwrap();
+        let trans = prefixer.qb64().unwrap()
+            + &seqner.qb64().unwrap()
+            + said
+            + &counter_2_0(counter::Codex_2_0::ControllerIdxSigs, &sigs);
+        let qb64 = genus.qb64().unwrap()
+            + &counter_2_0(counter::Codex_2_0::ControllerIdxSigs, &sigs)
+            + &counter_2_0(counter::Codex_2_0::BigWitnessIdxSigs, &sigs)
+            + &counter_2_0(
+                counter::Codex_2_0::AttachmentGroup,
+                &counter_2_0(counter::Codex_2_0::TransIdxSigGroups, &trans),
+            );
+
+        let mut extractor = Extractor::new(qb64.as_bytes(), Domain::Text, CURRENT_VERSION);
+        let groups = extractor.groups().unwrap();
+        assert_eq!(extractor.gvrsn(), Version { major: 2, minor: 0 });
+        assert_eq!(
+            groups,
+            vec![
+                Group::ControllerIdxSigs(sigers.clone()),
+                Group::WitnessIdxSigs(sigers.clone()),
+                Group::TransIdxSigGroups(vec![(
+                    prefixer,
+                    seqner,
+                    Saider::new_with_qb64(said).unwrap(),
+                    sigers
+                )]),
+            ]
+        );
+
+        let qb2 = b64_engine::URL_SAFE.decode(&qb64).unwrap();
+        let mut extractor = Extractor::new(&qb2, Domain::Binary, CURRENT_VERSION);
+        assert_eq!(extractor.groups().unwrap(), groups);
+        assert_eq!(extractor.offset(), qb2.len());
+    }
+
+    #[test]
+    fn version_2_unhappy_paths() {
+        let gvrsn = Version { major: 2, minor: 0 };
+        let siger = signers()[0].sign_indexed(b"abcdefg", false, 0, None).unwrap();
+
+        // generic groups are not attachments
+        let qb64 = counter_2_0(counter::Codex_2_0::GenericGroup, "");
+        let e = Extractor::new(qb64.as_bytes(), Domain::Text, &gvrsn).groups().unwrap_err();
+        assert!(matches!(e.downcast_ref::<Error>(), Some(Error::UnexpectedCountCode(_))));
+
+        // 1.0 only codes
+        let e = Extractor::new(b"--AAAAAA", Domain::Text, &gvrsn).groups().unwrap_err();
+        assert!(matches!(e.downcast_ref::<Error>(), Some(Error::UnknownHardage(_))));
+
+        // a group whose span ends inside an item
+        let qb64 = counter_2_0(counter::Codex_2_0::ControllerIdxSigs, &siger.qb64().unwrap()[..44])
+            + &siger.qb64().unwrap()[44..];
+        let e = Extractor::new(qb64.as_bytes(), Domain::Text, &gvrsn).groups().unwrap_err();
+        assert!(matches!(e.downcast_ref::<Error>(), Some(Error::Parsing(_))));
+
+        // a span longer than the stream
+        let qb64 = counter_2_0(counter::Codex_2_0::ControllerIdxSigs, &siger.qb64().unwrap());
+        let e = Extractor::new(&qb64.as_bytes()[..40], Domain::Text, &gvrsn).groups().unwrap_err();
+        assert!(matches!(e.downcast_ref::<Error>(), Some(Error::Shortage(_))));
+
+        let genus = Counter::new_with_genus_version(&Version { major: 3, minor: 0 }).unwrap();
+        let qb64 = genus.qb64().unwrap();
+        assert!(Extractor::new(qb64.as_bytes(), Domain::Text, &gvrsn).groups().is_err());
+    }
+
+    #[test]
+    fn non_trans_receipt_couples() {
+        let ser = b"abcdefg";
+        let signer = Signer::new_with_raw(&[7u8; 32], Some(false), None).unwrap();
+        let cigar = signer.sign_unindexed(ser).unwrap();
+        let prefixer = Prefixer::new_with_qb64(&signer.verfer().qb64().unwrap()).unwrap();
+
+        let qb64 = Counter::new_with_code_and_count(counter::Codex::NonTransReceiptCouples, 1)
+            .unwrap()
+            .qb64()
+            .unwrap()
+            + &prefixer.qb64().unwrap()
+            + &cigar.qb64().unwrap();
+
+        let groups =
+            Extractor::new(qb64.as_bytes(), Domain::Text, CURRENT_VERSION).groups().unwrap();
+        match &groups[..] {
+            [Group::NonTransReceiptCouples(couples)] => {
+                assert_eq!(couples.len(), 1);
+                assert_eq!(couples[0].0, prefixer);
+                assert_eq!(couples[0].1.raw(), cigar.raw());
+                assert!(couples[0].1.verfer().verify(&couples[0].1.raw(), ser).unwrap());
+            }
+            _ => panic!("unexpected groups {groups:?}"),
+        }
+    }
+
+    #[test]
+    fn trans_idx_sig_groups_in_attached_material() {
+        let ser = b"abcdefg";
+        let signers = signers();
+        let siger = unverfered(&signers[0].sign_indexed(ser, false, 0, None).unwrap());
+        let prefixer = Prefixer::new_with_qb64(&signers[0].verfer().qb64().unwrap()).unwrap();
+        let seqner = Seqner::new_with_sn(1).unwrap();
+        let said = "EBAjyPZ8Ed4XXl5cVZhqAy7SuaGivQp0WqQKVXvg7oqd";
+
+        let inner = Counter::new_with_code_and_count(counter::Codex::TransIdxSigGroups, 1)
+            .unwrap()
+            .qb64()
+            .unwrap()
+            + &prefixer.qb64().unwrap()
+            + &seqner.qb64().unwrap()
+            + said
+            + &Counter::new_with_code_and_count(counter::Codex::ControllerIdxSigs, 1)
+                .unwrap()
+                .qb64()
+                .unwrap()
+            + &siger.qb64().unwrap();
+        let qb64 = Counter::new_with_code_and_count(
+            counter::Codex::AttachedMaterialQuadlets,
+            inner.len() as u32 / 4,
+        )
+        .unwrap()
+        .qb64()
+        .unwrap()
+            + &inner;
+
+        let groups =
+            Extractor::new(qb64.as_bytes(), Domain::Text, CURRENT_VERSION).groups().unwrap();
+        match &groups[..] {
+            [Group::TransIdxSigGroups(groups)] => {
+                assert_eq!(groups[0].0, prefixer);
+                assert_eq!(groups[0].1, seqner);
+                assert_eq!(groups[0].2.qb64().unwrap(), said);
+                assert_eq!(groups[0].3, vec![siger]);
+            }
+            _ => panic!("unexpected groups {groups:?}"),
+        }
+    }
+
+    #[test]
+    fn pathed_material_quadlets() {
+        let siger = unverfered(&signers()[0].sign_indexed(b"abcdefg", false, 0, None).unwrap());
+        let pather = Pather::new_with_path(&dat!(["e", "acdc"])).unwrap();
+
+        let inner = pather.qb64().unwrap()
+            + &Counter::new_with_code_and_count(counter::Codex::ControllerIdxSigs, 1)
+                .unwrap()
+                .qb64()
+                .unwrap()
+            + &siger.qb64().unwrap();
+        let qb64 = Counter::new_with_code_and_count(
+            counter::Codex::PathedMaterialQuadlets,
+            inner.len() as u32 / 4,
+        )
+        .unwrap()
+        .qb64()
+        .unwrap()
+            + &inner;
+
+        let groups =
+            Extractor::new(qb64.as_bytes(), Domain::Text, CURRENT_VERSION).groups().unwrap();
+        assert_eq!(
+            groups,
+            vec![Group::PathedMaterialQuadlets(
+                pather,
+                vec![Group::ControllerIdxSigs(vec![siger])]
+            )]
+        );
+    }
+
+    #[test]
+    fn attachments_round_trip() {
+        let ser = b"abcdefg";
+        let signers = signers();
+        let sigers: Vec<_> = signers
+            .iter()
+            .enumerate()
+            .map(|(i, s)| unverfered(&s.sign_indexed(ser, false, i as u32, None).unwrap()))
+            .collect();
+        let signer = Signer::new_with_raw(&[7u8; 32], Some(false), None).unwrap();
+        let cigar = signer.sign_unindexed(ser).unwrap();
+        let nontrans = Prefixer::new_with_qb64(&signer.verfer().qb64().unwrap()).unwrap();
+        let prefixer = Prefixer::new_with_qb64(&signers[0].verfer().qb64().unwrap()).unwrap();
+        let seqner = Seqner::new_with_sn(1).unwrap();
+        let saider = Saider::new_with_qb64("EBAjyPZ8Ed4XXl5cVZhqAy7SuaGivQp0WqQKVXvg7oqd").unwrap();
+        let dater = Dater::new_with_dts("2020-08-22T17:50:09.988921+00:00", None).unwrap();
+        let pather = Pather::new_with_path(&dat!(["a", "b"])).unwrap();
+
+        let mut attachments = Attachments::new(&[
+            Group::ControllerIdxSigs(sigers.clone()),
+            Group::WitnessIdxSigs(sigers[..1].to_vec()),
+            Group::NonTransReceiptCouples(vec![(nontrans.clone(), cigar.clone())]),
+            Group::TransReceiptQuadruples(vec![(
+                prefixer.clone(),
+                seqner.clone(),
+                saider.clone(),
+                sigers[0].clone(),
+            )]),
+            Group::FirstSeenReplayCouples(vec![(seqner.clone(), dater)]),
+            Group::TransIdxSigGroups(vec![(
+                prefixer.clone(),
+                seqner.clone(),
+                saider.clone(),
+                sigers.clone(),
+            )]),
+            Group::SealSourceCouples(vec![(seqner.clone(), saider.clone())]),
+            Group::TransLastIdxSigGroups(vec![(prefixer.clone(), sigers[1..].to_vec())]),
+            Group::SealSourceTriples(vec![(prefixer, seqner, saider)]),
+        ]);
+        attachments.push(Group::SadPathSig(vec![(
+            pather.clone(),
+            Group::ControllerIdxSigs(sigers.clone()),
+        )]));
+        attachments.push(Group::SadPathSigGroup(
+            pather.clone(),
+            vec![(pather.clone(), Group::NonTransReceiptCouples(vec![(nontrans, cigar)]))],
+        ));
+        attachments.push(Group::PathedMaterialQuadlets(
+            pather,
+            vec![Group::ControllerIdxSigs(sigers.clone())],
+        ));
+
+        let qb64 = attachments.qb64().unwrap();
+        assert!(qb64.starts_with(counter::Codex::AttachedMaterialQuadlets));
+        assert_eq!(Attachments::new_with_qb64(&qb64).unwrap(), attachments);
+
+        let qb2 = attachments.qb2().unwrap();
+        assert_eq!(qb2, b64_engine::URL_SAFE.decode(&qb64).unwrap());
+        assert_eq!(Attachments::new_with_qb2(&qb2).unwrap(), attachments);
+
+        // groups serialize exactly as they would be assembled by hand
+        let group = Group::ControllerIdxSigs(sigers.clone());
+        let mut expected = Counter::new_with_code_and_count(counter::Codex::ControllerIdxSigs, 3)
+            .unwrap()
+            .qb64()
+            .unwrap();
+        for siger in &sigers {
+            expected += &siger.qb64().unwrap();
+        }
+        assert_eq!(group.qb64().unwrap(), expected);
+        assert_eq!(Attachments::new(&[group]).qb64().unwrap(), format!("-VBD{expected}"),);
+
+        assert!(Attachments::default().is_empty());
+        assert_eq!(Attachments::default().qb64().unwrap(), "-VAA");
+    }
+
+    #[test]
+    fn big_attachments() {
+        let siger = unverfered(&signers()[0].sign_indexed(b"abcdefg", false, 0, None).unwrap());
+        let attachments = Attachments::new(&[Group::ControllerIdxSigs(vec![siger; 200])]);
+
+        // 200 signatures of 22 quadlets each, plus the group counter
+        let qb64 = attachments.qb64().unwrap();
+        assert_eq!(&qb64[..8], "-0VAABEx");
+        assert_eq!(qb64.len(), 8 + 4 + 200 * 88);
+        assert_eq!(Attachments::new_with_qb64(&qb64).unwrap(), attachments);
+
+        let qb2 = attachments.qb2().unwrap();
+        assert_eq!(Attachments::new_with_qb2(&qb2).unwrap(), attachments);
+    }
+
+    #[test]
+    fn shortage() {
+        let siger = signers()[0].sign_indexed(b"abcdefg", false, 0, None).unwrap();
+        let qb64 = Counter::new_with_code_and_count(counter::Codex::ControllerIdxSigs, 2)
+            .unwrap()
+            .qb64()
+            .unwrap()
+            + &siger.qb64().unwrap();
+
+        for end in [2, qb64.len() - 1, qb64.len()] {
+            let e = Extractor::new(&qb64.as_bytes()[..end], Domain::Text, CURRENT_VERSION)
+                .groups()
+                .unwrap_err();
+            assert!(matches!(e.downcast_ref::<Error>(), Some(Error::Shortage(_))));
+        }
+    }
+
+    #[test]
+    fn unhappy_paths() {
+        let qb64 = Counter::new_with_code_and_count(counter::Codex::SadPathSig, 1)
+            .unwrap()
+            .qb64()
+            .unwrap()
+            + &Pather::new_with_path(&dat!(["a"])).unwrap().qb64().unwrap()
+            + &Counter::new_with_code_and_count(counter::Codex::WitnessIdxSigs, 0)
+                .unwrap()
+                .qb64()
+                .unwrap();
+        assert!(Extractor::new(qb64.as_bytes(), Domain::Text, CURRENT_VERSION).groups().is_err());
+
+        let qb64 = Counter::new_with_code_and_count(counter::Codex::TransLastIdxSigGroups, 1)
+            .unwrap()
+            .qb64()
+            .unwrap()
+            + "BEy_EvE8OUMqj0AgCJ3wOCOrIVHVtwubYAysPyaAv9VI"
+            + &Counter::new_with_code_and_count(counter::Codex::WitnessIdxSigs, 0)
+                .unwrap()
+                .qb64()
+                .unwrap();
+        assert!(Extractor::new(qb64.as_bytes(), Domain::Text, CURRENT_VERSION).groups().is_err());
+    }
+}
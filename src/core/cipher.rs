@@ -0,0 +1,131 @@
+use crate::core::matter::{tables as matter, Matter};
+use crate::error::{err, Error, Result};
+
+/// An X25519 sealed box containing a qb64 encoded secret, produced by `Encrypter` and opened by
+/// `Decrypter`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cipher {
+    raw: Vec<u8>,
+    code: String,
+    size: u32,
+}
+
+impl Default for Cipher {
+    fn default() -> Self {
+        Cipher { raw: vec![], code: matter::Codex::X25519_Cipher_Salt.to_string(), size: 0 }
+    }
+}
+
+fn validate_code(code: &str) -> Result<()> {
+    const CODES: &[&str] = &[matter::Codex::X25519_Cipher_Salt, matter::Codex::X25519_Cipher_Seed];
+
+    if !CODES.contains(&code) {
+        return err!(Error::UnexpectedCode(code.to_string()));
+    }
+
+    Ok(())
+}
+
+// the cipher code whose raw size fits a sealed secret of this size
+fn derive_code(raw: &[u8]) -> Result<&'static str> {
+    for code in [matter::Codex::X25519_Cipher_Salt, matter::Codex::X25519_Cipher_Seed] {
+        if raw.len() == matter::raw_size(code)? as usize {
+            return Ok(code);
+        }
+    }
+
+    err!(Error::Value(format!("unsupported cipher raw size = {}", raw.len())))
+}
+
+impl Cipher {
+    pub fn new(
+        code: Option<&str>,
+        raw: Option<&[u8]>,
+        qb64b: Option<&[u8]>,
+        qb64: Option<&str>,
+        qb2: Option<&[u8]>,
+    ) -> Result<Self> {
+        let code = match (code, raw) {
+            (None, Some(raw)) => Some(derive_code(raw)?),
+            _ => code,
+        };
+
+        let cipher: Self = Matter::new(code, raw, qb64b, qb64, qb2)?;
+        validate_code(&cipher.code())?;
+        Ok(cipher)
+    }
+
+    pub fn new_with_raw(raw: &[u8], code: Option<&str>) -> Result<Self> {
+        Self::new(code, Some(raw), None, None, None)
+    }
+
+    pub fn new_with_qb64b(qb64b: &[u8]) -> Result<Self> {
+        Self::new(None, None, Some(qb64b), None, None)
+    }
+
+    pub fn new_with_qb64(qb64: &str) -> Result<Self> {
+        Self::new(None, None, None, Some(qb64), None)
+    }
+
+    pub fn new_with_qb2(qb2: &[u8]) -> Result<Self> {
+        Self::new(None, None, None, None, Some(qb2))
+    }
+}
+
+impl Matter for Cipher {
+    fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    fn raw(&self) -> Vec<u8> {
+        self.raw.clone()
+    }
+
+    fn size(&self) -> u32 {
+        self.size
+    }
+
+    fn set_code(&mut self, code: &str) {
+        self.code = code.to_string();
+    }
+
+    fn set_raw(&mut self, raw: &[u8]) {
+        self.raw = raw.to_vec();
+    }
+
+    fn set_size(&mut self, size: u32) {
+        self.size = size;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::{
+        cipher::Cipher,
+        matter::{tables as matter, Matter},
+    };
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(matter::Codex::X25519_Cipher_Salt, 72)]
+    #[case(matter::Codex::X25519_Cipher_Seed, 92)]
+    fn convenience(#[case] code: &str, #[case] size: usize) {
+        let raw = vec![7u8; size];
+
+        let cipher = Cipher::new_with_raw(&raw, None).unwrap();
+        assert_eq!(cipher.code(), code);
+        assert_eq!(cipher.raw(), raw);
+
+        assert_eq!(Cipher::new_with_raw(&raw, Some(code)).unwrap(), cipher);
+        assert_eq!(Cipher::new_with_qb64b(&cipher.qb64b().unwrap()).unwrap(), cipher);
+        assert_eq!(Cipher::new_with_qb64(&cipher.qb64().unwrap()).unwrap(), cipher);
+        assert_eq!(Cipher::new_with_qb2(&cipher.qb2().unwrap()).unwrap(), cipher);
+    }
+
+    #[test]
+    fn unhappy_paths() {
+        assert!(Cipher::new_with_raw(&[0u8; 71], None).is_err());
+        assert!(Cipher::new_with_raw(&[0u8; 32], Some(matter::Codex::Ed25519)).is_err());
+        assert!(Cipher::new_with_qb64("DAWRoSQNmmnDTcVyIFrfkGYJurA4VHvmsS54fmfT6fBv").is_err());
+    }
+}
@@ -1,8 +1,9 @@
-use crate::data::{dat, Value};
+use crate::core::util;
+use crate::data::Value;
 use crate::error::{err, Error, Result};
 
 use lazy_static::lazy_static;
-use regex::Regex;
+use regex::bytes::Regex as BytesRegex;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SizeifyResult {
@@ -35,9 +36,32 @@ pub struct Version {
     pub minor: u8,
 }
 
+/// The kind of frame that starts with a given byte, classified by its first tritet (three most
+/// significant bits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColdCode {
+    AnnotatedB64, // 0o0 annotated or whitespace
+    CountB64,     // 0o1 qb64 count code, '-'
+    OpB64,        // 0o2 qb64 op code, '_'
+    JSON,         // 0o3 '{'
+    MGPK1,        // 0o4 MGPK fixmap
+    CBOR,         // 0o5 CBOR map
+    MGPK2,        // 0o6 MGPK map16 or map32
+    CountOpB2,    // 0o7 qb2 count or op code
+}
+
+impl ColdCode {
+    /// True when the frame is a serialized message rather than CESR material.
+    pub fn message(&self) -> bool {
+        matches!(self, ColdCode::JSON | ColdCode::MGPK1 | ColdCode::CBOR | ColdCode::MGPK2)
+    }
+}
+
 #[allow(non_snake_case)]
 pub mod Serialage {
     pub const JSON: &str = "JSON";
+    pub const MGPK: &str = "MGPK";
+    pub const CBOR: &str = "CBOR";
 }
 
 #[allow(non_snake_case)]
@@ -69,6 +93,36 @@ pub mod Ilkage {
     pub const brv: &str = "brv";
 }
 
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+pub mod Traitage {
+    pub const EO: &str = "EO"; // establishment events only
+    pub const DND: &str = "DND"; // do not delegate
+    pub const NB: &str = "NB"; // registry without backers
+}
+
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+pub mod Rolage {
+    pub const controller: &str = "controller";
+    pub const witness: &str = "witness";
+    pub const registrar: &str = "registrar";
+    pub const watcher: &str = "watcher";
+    pub const judge: &str = "judge";
+    pub const juror: &str = "juror";
+    pub const peer: &str = "peer";
+    pub const mailbox: &str = "mailbox";
+    pub const agent: &str = "agent";
+}
+
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+pub mod Schemage {
+    pub const http: &str = "http";
+    pub const https: &str = "https";
+    pub const tcp: &str = "tcp";
+}
+
 #[allow(non_snake_case)]
 #[allow(non_upper_case_globals)]
 pub mod Tierage {
@@ -92,62 +146,188 @@ pub mod Ids {
     pub const b: &str = "b";
     pub const a: &str = "a";
     pub const s: &str = "s";
+    pub const p: &str = "p";
     pub const f: &str = "f";
     pub const v: &str = "v";
     pub const kt: &str = "kt";
     pub const nt: &str = "nt";
+    pub const bt: &str = "bt";
+    pub const br: &str = "br";
+    pub const ba: &str = "ba";
     pub const di: &str = "di";
+    pub const rd: &str = "rd";
+    pub const c: &str = "c";
+    pub const ii: &str = "ii";
+    pub const ri: &str = "ri";
+    pub const ra: &str = "ra";
+    pub const dt: &str = "dt";
+    pub const e: &str = "e";
+    pub const r: &str = "r";
+    pub const u: &str = "u";
+    pub const A: &str = "A";
+    pub const rp: &str = "rp";
+    pub const q: &str = "q";
+    pub const vn: &str = "vn";
+    pub const et: &str = "et";
+    pub const ee: &str = "ee";
+    pub const rr: &str = "rr";
 }
 
 const REVER_STRING: &str = "(?P<ident>[A-Z]{4})(?P<major>[0-9a-f])(?P<minor>[0-9a-f])(?P<kind>[A-Z]{4})(?P<size>[0-9a-f]{6})_";
+const REVER_2_STRING: &str = "(?P<ident2>[A-Z]{4})(?P<major2>[0-9A-Za-z_-])(?P<minor2>[0-9A-Za-z_-]{2})(?P<gmajor2>[0-9A-Za-z_-])(?P<gminor2>[0-9A-Za-z_-]{2})(?P<kind2>[A-Z]{4})(?P<size2>[0-9A-Za-z_-]{4})\\.";
 const IDENTS: &[&str] = &[Identage::ACDC, Identage::KERI];
-const SERIALS: &[&str] = &[Serialage::JSON];
+const SERIALS: &[&str] = &[Serialage::JSON, Serialage::MGPK, Serialage::CBOR];
 
 pub(crate) const DUMMY: u8 = b'#';
 
 pub const CURRENT_VERSION: &Version = &Version { major: 1, minor: 0 };
+pub const VRSN_2_0: &Version = &Version { major: 2, minor: 0 };
+const VERSIONS: &[&Version] = &[CURRENT_VERSION, VRSN_2_0];
 
 const MAXIMUM_START_SIZE: usize = 12;
 pub(crate) const VERSION_FULL_SIZE: usize = 17;
-pub(crate) const MINIMUM_SNIFF_SIZE: usize = MAXIMUM_START_SIZE + VERSION_FULL_SIZE;
+pub(crate) const VERSION_2_FULL_SIZE: usize = 19;
+const MAXIMUM_VERSION_FULL_SIZE: usize =
+    if VERSION_2_FULL_SIZE > VERSION_FULL_SIZE { VERSION_2_FULL_SIZE } else { VERSION_FULL_SIZE };
+pub(crate) const MINIMUM_SNIFF_SIZE: usize = MAXIMUM_START_SIZE + MAXIMUM_VERSION_FULL_SIZE;
+
+// a 2.0 version is one base64 character for major and two for minor
+pub(crate) fn version_to_b64(version: &Version) -> Result<String> {
+    if version.major > 63 {
+        return err!(Error::Conversion(format!(
+            "invalid major version = {major}",
+            major = version.major
+        )));
+    }
 
-pub fn deversify(vs: &str) -> Result<DeversifyResult> {
+    Ok(format!(
+        "{major}{minor}",
+        major = util::u32_to_b64(version.major as u32, 1)?,
+        minor = util::u32_to_b64(version.minor as u32, 2)?
+    ))
+}
+
+pub(crate) fn b64_to_version(b64: &str) -> Result<Version> {
+    if b64.len() != 3 {
+        return err!(Error::Conversion(format!("invalid base64 version = {b64}")));
+    }
+
+    let major = util::b64_to_u32(&b64[..1])?;
+    let minor = util::b64_to_u32(&b64[1..])?;
+    if minor > u8::MAX as u32 {
+        return err!(Error::Conversion(format!("unsupported minor version = {minor}")));
+    }
+
+    Ok(Version { major: major as u8, minor: minor as u8 })
+}
+
+pub(crate) fn supported(version: &Version) -> bool {
+    VERSIONS.contains(&version)
+}
+
+struct Rematch {
+    start: usize,
+    end: usize,
+    result: DeversifyResult,
+    gvrsn: Option<Version>, // genus version, in 2.0 version strings only
+}
+
+// finds and parses the first version string of either format in raw, none when there is none
+fn rematch(raw: &[u8]) -> Result<Option<Rematch>> {
     lazy_static! {
-        static ref REVER: Regex = Regex::new(REVER_STRING).unwrap();
+        static ref REVER: BytesRegex =
+            BytesRegex::new(&format!("{REVER_STRING}|{REVER_2_STRING}")).unwrap();
     };
 
-    if REVER.is_match(vs) {
-        let ident = REVER.replace_all(vs, "$ident").to_string();
-        let major = u8::from_str_radix(&REVER.replace_all(vs, "$major"), 16)?;
-        let minor = u8::from_str_radix(&REVER.replace_all(vs, "$minor"), 16)?;
-        let kind = REVER.replace_all(vs, "$kind").to_string();
-        let size = u32::from_str_radix(&REVER.replace_all(vs, "$size"), 16)?;
+    let caps = match REVER.captures(raw) {
+        Some(caps) => caps,
+        None => return Ok(None),
+    };
 
-        if !IDENTS.contains(&ident.as_str()) {
-            return err!(Error::Validation(format!("invalid ident {ident}")));
-        }
+    // the pattern only matches ascii so these conversions cannot fail
+    let field = |name: &str| -> Result<String> {
+        Ok(String::from_utf8(caps.name(name).map(|m| m.as_bytes().to_vec()).unwrap_or_default())?)
+    };
 
-        if !SERIALS.contains(&kind.as_str()) {
-            return err!(Error::Validation(format!("invalid serialization kind {kind}")));
+    let whole = caps.get(0).unwrap();
+    let (result, gvrsn) = if caps.name("ident").is_some() {
+        let result = DeversifyResult {
+            ident: field("ident")?,
+            kind: field("kind")?,
+            version: Version {
+                major: u8::from_str_radix(&field("major")?, 16)?,
+                minor: u8::from_str_radix(&field("minor")?, 16)?,
+            },
+            size: u32::from_str_radix(&field("size")?, 16)?,
+        };
+        (result, None)
+    } else {
+        let version = b64_to_version(&format!("{}{}", field("major2")?, field("minor2")?))?;
+        if version.major < 2 {
+            return err!(Error::Validation(format!(
+                "invalid major version = {major} for version 2 string",
+                major = version.major
+            )));
         }
 
-        return Ok(DeversifyResult { ident, kind, version: Version { major, minor }, size });
+        let result = DeversifyResult {
+            ident: field("ident2")?,
+            kind: field("kind2")?,
+            version,
+            size: util::b64_to_u32(&field("size2")?)?,
+        };
+        let gvrsn = b64_to_version(&format!("{}{}", field("gmajor2")?, field("gminor2")?))?;
+        (result, Some(gvrsn))
+    };
+
+    if !IDENTS.contains(&result.ident.as_str()) {
+        return err!(Error::Validation(format!("invalid ident {ident}", ident = result.ident)));
+    }
+
+    if !SERIALS.contains(&result.kind.as_str()) {
+        return err!(Error::Validation(format!(
+            "invalid serialization kind {kind}",
+            kind = result.kind
+        )));
     }
 
-    err!(Error::Validation(format!("invalid version string {vs}")))
+    Ok(Some(Rematch { start: whole.start(), end: whole.end(), result, gvrsn }))
 }
 
-pub fn sizeify(ked: &Value, kind: Option<&str>) -> Result<SizeifyResult> {
-    lazy_static! {
-        static ref REVER: Regex = Regex::new(REVER_STRING).unwrap();
-    };
+// the version string `vs` parsed, a validation error when it is malformed
+fn vs_rematch(vs: &str) -> Result<Rematch> {
+    match rematch(vs.as_bytes())? {
+        Some(rematch) => Ok(rematch),
+        None => err!(Error::Validation(format!("invalid version string {vs}"))),
+    }
+}
 
+// the first version string in `raw` parsed
+fn raw_rematch(raw: &[u8]) -> Result<Rematch> {
+    match rematch(raw)? {
+        Some(rematch) => Ok(rematch),
+        None => err!(Error::Value(format!("invalid version string in raw = {raw:?}"))),
+    }
+}
+
+/// Parses a version string in either the 1.0 (`KERI10JSON000000_`) or the 2.0
+/// (`KERICAACAAJSONAAAA.`) format.
+pub fn deversify(vs: &str) -> Result<DeversifyResult> {
+    Ok(vs_rematch(vs)?.result)
+}
+
+/// The genus version carried by a 2.0 version string, none for a 1.0 version string.
+pub fn genus_version(vs: &str) -> Result<Option<Version>> {
+    Ok(vs_rematch(vs)?.gvrsn)
+}
+
+pub fn sizeify(ked: &Value, kind: Option<&str>) -> Result<SizeifyResult> {
     if !ked.to_map()?.contains_key("v") {
         return err!(Error::Value("missing or empty version string".to_string()));
     }
 
-    let result = deversify(&ked["v"].to_string()?)?;
-    if result.version != *CURRENT_VERSION {
+    let Rematch { result, gvrsn, .. } = vs_rematch(&ked["v"].to_string()?)?;
+    if !supported(&result.version) {
         return err!(Error::Value(format!(
             "unsupported version {}.{}",
             result.version.major, result.version.minor
@@ -163,12 +343,7 @@ pub fn sizeify(ked: &Value, kind: Option<&str>) -> Result<SizeifyResult> {
     let raw = &dumps(ked, Some(&kind))?;
     let size = raw.len();
 
-    let start = match REVER.shortest_match(&String::from_utf8(raw.clone())?) {
-        Some(m) => m - VERSION_FULL_SIZE,
-        // unreachable - deversify has been called which ensures this will match
-        None => return err!(Error::Value(format!("invalid version string in raw = {raw:?}"))),
-    };
-
+    let Rematch { start, end, .. } = raw_rematch(raw)?;
     if start > MAXIMUM_START_SIZE {
         return err!(Error::Value(format!(
             "invalid version string in raw = {raw:?} start = {start}"
@@ -176,8 +351,19 @@ pub fn sizeify(ked: &Value, kind: Option<&str>) -> Result<SizeifyResult> {
     }
 
     let fore = raw[..start].to_vec();
-    let mut back = raw[start + VERSION_FULL_SIZE..].to_vec();
-    let vs = versify(Some(&result.ident), Some(&result.version), Some(&kind), Some(size as u32))?;
+    let mut back = raw[end..].to_vec();
+    let vs = match gvrsn {
+        Some(gvrsn) => versify_with_gvrsn(
+            Some(&result.ident),
+            Some(&result.version),
+            Some(&kind),
+            Some(size as u32),
+            Some(&gvrsn),
+        )?,
+        None => {
+            versify(Some(&result.ident), Some(&result.version), Some(&kind), Some(size as u32))?
+        }
+    };
 
     let mut raw = fore;
     raw.append(&mut vs.as_bytes().to_vec());
@@ -194,23 +380,31 @@ pub fn sizeify(ked: &Value, kind: Option<&str>) -> Result<SizeifyResult> {
     Ok(SizeifyResult { raw, ident: result.ident, kind, ked, version: result.version })
 }
 
+/// Builds a version string. Versions 2.0 and later use the base64 format with a genus
+/// version of 2.0, see `versify_with_gvrsn`.
 pub fn versify(
     ident: Option<&str>,
     version: Option<&Version>,
     kind: Option<&str>,
     size: Option<u32>,
 ) -> Result<String> {
-    let ident = ident.unwrap_or(Identage::KERI);
     let version = version.unwrap_or(CURRENT_VERSION);
+    if version.major >= 2 {
+        return versify_with_gvrsn(ident, Some(version), kind, size, None);
+    }
+
+    let ident = ident.unwrap_or(Identage::KERI);
     let kind = kind.unwrap_or(Serialage::JSON);
     let size = size.unwrap_or(0);
 
-    if !IDENTS.contains(&ident) {
-        return err!(Error::Validation(format!("invalid ident {ident}")));
-    }
+    validate_ident_and_kind(ident, kind)?;
 
-    if !SERIALS.contains(&kind) {
-        return err!(Error::Validation(format!("invalid serialization kind {kind}")));
+    if version.major > 15 || version.minor > 15 || size > 0xffffff {
+        return err!(Error::Validation(format!(
+            "invalid version {major}.{minor} or size {size}",
+            major = version.major,
+            minor = version.minor
+        )));
     }
 
     Ok(format!(
@@ -220,42 +414,133 @@ pub fn versify(
     ))
 }
 
-pub(crate) fn loads(raw: &[u8], size: Option<u32>, kind: Option<&str>) -> Result<Value> {
+/// Builds a 2.0 version string, which carries the genus version `gvrsn` of the CESR code
+/// tables used by the message. `version` defaults to 2.0, as does `gvrsn`.
+pub fn versify_with_gvrsn(
+    ident: Option<&str>,
+    version: Option<&Version>,
+    kind: Option<&str>,
+    size: Option<u32>,
+    gvrsn: Option<&Version>,
+) -> Result<String> {
+    let ident = ident.unwrap_or(Identage::KERI);
+    let version = version.unwrap_or(VRSN_2_0);
     let kind = kind.unwrap_or(Serialage::JSON);
+    let size = size.unwrap_or(0);
+    let gvrsn = gvrsn.unwrap_or(VRSN_2_0);
 
-    if let Some(size) = size {
-        match kind {
-            Serialage::JSON => {
-                let v: serde_json::Value =
-                    serde_json::from_str(&String::from_utf8(raw[..(size as usize)].to_vec())?)?;
-                Ok(Value::from(&v))
-            }
-            _ => err!(Error::Validation(format!("invalid serialization kind {kind}"))),
+    validate_ident_and_kind(ident, kind)?;
+
+    if version.major < 2 {
+        return err!(Error::Validation(format!(
+            "invalid version {major}.{minor} for version 2 string",
+            major = version.major,
+            minor = version.minor
+        )));
+    }
+
+    if size > 64_u32.pow(4) - 1 {
+        return err!(Error::Validation(format!("invalid size {size} for version 2 string")));
+    }
+
+    Ok(format!(
+        "{ident}{pvrsn}{gvrsn}{kind}{size}.",
+        pvrsn = version_to_b64(version)?,
+        gvrsn = version_to_b64(gvrsn)?,
+        size = util::u32_to_b64(size, 4)?
+    ))
+}
+
+fn validate_ident_and_kind(ident: &str, kind: &str) -> Result<()> {
+    if !IDENTS.contains(&ident) {
+        return err!(Error::Validation(format!("invalid ident {ident}")));
+    }
+
+    if !SERIALS.contains(&kind) {
+        return err!(Error::Validation(format!("invalid serialization kind {kind}")));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn loads(raw: &[u8], size: Option<u32>, kind: Option<&str>) -> Result<Value> {
+    let kind = kind.unwrap_or(Serialage::JSON);
+    let raw = if let Some(size) = size {
+        if raw.len() < size as usize {
+            return err!(Error::Shortage(format!(
+                "need {size} bytes to load, got {len}",
+                len = raw.len()
+            )));
         }
+        &raw[..(size as usize)]
     } else {
-        match kind {
-            Serialage::JSON => {
-                let v: serde_json::Value = serde_json::from_str(&String::from_utf8(raw.to_vec())?)?;
-                Ok(Value::from(&v))
-            }
-            _ => err!(Error::Validation(format!("invalid serialization kind {kind}"))),
-        }
-    }
+        raw
+    };
+
+    let v: serde_json::Value = match kind {
+        Serialage::JSON => serde_json::from_str(&String::from_utf8(raw.to_vec())?)?,
+        Serialage::MGPK => rmp_serde::from_slice(raw)?,
+        Serialage::CBOR => ciborium::from_reader(raw)?,
+        _ => return err!(Error::Validation(format!("invalid serialization kind {kind}"))),
+    };
+
+    Ok(Value::from(&v))
 }
 
 pub(crate) fn dumps(ked: &Value, kind: Option<&str>) -> Result<Vec<u8>> {
     let kind = kind.unwrap_or(Serialage::JSON);
     match kind {
         Serialage::JSON => Ok(ked.to_json()?.as_bytes().to_vec()),
+        Serialage::MGPK | Serialage::CBOR => {
+            let v: serde_json::Value = serde_json::from_str(&ked.to_json()?)?;
+            if kind == Serialage::MGPK {
+                Ok(rmp_serde::to_vec(&v)?)
+            } else {
+                let mut raw = vec![];
+                ciborium::into_writer(&v, &mut raw)?;
+                Ok(raw)
+            }
+        }
         _ => err!(Error::Value(format!("invalid serialization kind = {kind}"))),
     }
 }
 
-pub fn sniff(raw: &[u8]) -> Result<SniffResult> {
-    lazy_static! {
-        static ref REVER: Regex = Regex::new(REVER_STRING).unwrap();
-    };
+pub fn cold_start(byte: u8) -> ColdCode {
+    match byte >> 5 {
+        0o0 => ColdCode::AnnotatedB64,
+        0o1 => ColdCode::CountB64,
+        0o2 => ColdCode::OpB64,
+        0o3 => ColdCode::JSON,
+        0o4 => ColdCode::MGPK1,
+        0o5 => ColdCode::CBOR,
+        0o6 => ColdCode::MGPK2,
+        _ => ColdCode::CountOpB2,
+    }
+}
+
+/// Returns the offset of the first byte in `ims` that starts a message with a valid version
+/// string, or that may start one once more bytes arrive. Bytes before the offset can be
+/// discarded to recover from garbage in a stream.
+pub fn resync(ims: &[u8]) -> Option<usize> {
+    (0..ims.len()).find(|&i| {
+        match cold_start(ims[i]) {
+            // the json tritet also covers lowercase letters
+            ColdCode::JSON if ims[i] != b'{' => return false,
+            cold if !cold.message() => return false,
+            _ => (),
+        }
+
+        let rest = &ims[i..];
+        if rest.len() < MINIMUM_SNIFF_SIZE {
+            // too short to tell, keep it
+            true
+        } else {
+            sniff(&rest[..MINIMUM_SNIFF_SIZE]).is_ok()
+        }
+    })
+}
 
+pub fn sniff(raw: &[u8]) -> Result<SniffResult> {
     if raw.len() < MINIMUM_SNIFF_SIZE {
         return err!(Error::Value(format!(
             "need more bytes than {bytes} to sniff",
@@ -263,38 +548,25 @@ pub fn sniff(raw: &[u8]) -> Result<SniffResult> {
         )));
     }
 
-    let raw = &String::from_utf8(raw.to_vec())?;
-    let start = match REVER.shortest_match(raw) {
-        Some(m) => m - VERSION_FULL_SIZE,
-        None => return err!(Error::Value(format!("invalid version string in raw = {raw:?}"))),
-    };
-
+    let Rematch { start, result, .. } = raw_rematch(raw)?;
     if start > MAXIMUM_START_SIZE {
         return err!(Error::Value(format!(
             "invalid version string in raw = {raw:?} start = {start}"
         )));
     }
 
-    let vs = &raw[start..(start + VERSION_FULL_SIZE)];
-
-    let ident = REVER.replace_all(vs, "$ident").to_string();
-    let major = u8::from_str_radix(&REVER.replace_all(vs, "$major"), 16)?;
-    let minor = u8::from_str_radix(&REVER.replace_all(vs, "$minor"), 16)?;
-    let kind = REVER.replace_all(vs, "$kind").to_string();
-    let size = u32::from_str_radix(&REVER.replace_all(vs, "$size"), 16)?;
-    let version = Version { major, minor };
-
-    if !SERIALS.contains(&kind.as_str()) {
-        return err!(Error::Validation(format!("invalid serialization kind {kind}")));
-    }
-
-    Ok(SniffResult { ident, kind, version, size })
+    Ok(SniffResult {
+        ident: result.ident,
+        kind: result.kind,
+        version: result.version,
+        size: result.size,
+    })
 }
 
 #[cfg(test)]
 mod test {
     use crate::core::common;
-    use crate::data::dat;
+    use crate::error::Error;
     use rstest::rstest;
 
     #[test]
@@ -303,6 +575,56 @@ mod test {
         assert!(common::loads(raw, None, None).is_ok());
     }
 
+    #[rstest]
+    #[case(common::Serialage::MGPK, &[0x82, 0xa1, b'v', 0xb1], &[0xa1, b'd', 0xa0])]
+    #[case(common::Serialage::CBOR, &[0xa2, 0x61, b'v', 0x71], &[0x61, b'd', 0x60])]
+    fn sizeify_binary(#[case] kind: &str, #[case] fore: &[u8], #[case] back: &[u8]) {
+        let ked = dat!({"v":"KERI10JSON000000_","d":""});
+        let result = common::sizeify(&ked, Some(kind)).unwrap();
+
+        let vs = format!("KERI10{kind}000018_");
+        let mut raw = fore.to_vec();
+        raw.extend_from_slice(vs.as_bytes());
+        raw.extend_from_slice(back);
+        assert_eq!(result.raw, raw);
+        assert_eq!(result.kind, kind);
+        assert_eq!(result.ked["v"], dat!(&vs));
+
+        let sniffed = common::sniff(&[raw.as_slice(), &[0; 8]].concat()).unwrap();
+        assert_eq!(sniffed.kind, kind);
+        assert_eq!(sniffed.size, 24);
+        assert_eq!(common::loads(&raw, Some(24), Some(kind)).unwrap(), result.ked);
+        assert_eq!(common::dumps(&result.ked, Some(kind)).unwrap(), raw);
+        assert_eq!(common::resync(&[b"-AAB", raw.as_slice()].concat()), Some(4));
+    }
+
+    #[rstest]
+    #[case(b'\n', common::ColdCode::AnnotatedB64)]
+    #[case(b'-', common::ColdCode::CountB64)]
+    #[case(b'_', common::ColdCode::OpB64)]
+    #[case(b'{', common::ColdCode::JSON)]
+    #[case(0x87, common::ColdCode::MGPK1)]
+    #[case(0xa7, common::ColdCode::CBOR)]
+    #[case(0xde, common::ColdCode::MGPK2)]
+    #[case(0xf8, common::ColdCode::CountOpB2)]
+    #[case(0xfc, common::ColdCode::CountOpB2)]
+    fn cold_start(#[case] byte: u8, #[case] cold: common::ColdCode) {
+        assert_eq!(common::cold_start(byte), cold);
+    }
+
+    #[test]
+    fn resync() {
+        let msg = dat!({"v":"KERI10JSON000000_","d":""}).to_json().unwrap();
+        let mut ims = b"-garbage{\"x\":\"not a version string, just noise\"}".to_vec();
+        let offset = ims.len();
+        ims.extend_from_slice(msg.as_bytes());
+
+        assert_eq!(common::resync(&ims), Some(offset));
+        assert_eq!(common::resync(&ims[offset..]), Some(0));
+        assert_eq!(common::resync(b"-AAB{\"v\""), Some(4));
+        assert_eq!(common::resync(b"-AABxyz"), None);
+    }
+
     #[test]
     fn sniff_unhappy_paths() {
         assert!(common::sniff(&[]).is_err()); // minimum 29 octets
@@ -326,27 +648,123 @@ mod test {
         let raw = &dat!({}).to_json().unwrap().as_bytes().to_vec();
         assert!(common::loads(raw, None, Some("CESR")).is_err());
         assert!(common::loads(raw, Some(1024), Some("CESR")).is_err());
+        assert!(common::loads(raw, Some(1024), None).is_err());
+        assert!(common::loads(raw, None, Some(common::Serialage::CBOR)).is_err());
+        assert!(common::loads(&[0xc1], None, Some(common::Serialage::MGPK)).is_err());
     }
 
     #[test]
     fn sizeify_unhappy_paths() {
         assert!(common::sizeify(&dat!({}), None).is_err());
+        assert!(common::sizeify(&dat!({"v":"KERIDAACAAJSONAAAA."}), None).is_err());
         assert!(common::sizeify(&dat!({"v":"KERIffJSON000000_"}), None).is_err());
         assert!(common::sizeify(&dat!({"v":"KERI10JSON000000_"}), Some("CESR")).is_err());
         assert!(common::sizeify(&dat!({"i":"filler entry","v":"KERI10JSON000000_"}), None).is_err());
     }
 
+    #[rstest]
+    #[case(common::CURRENT_VERSION, common::Serialage::JSON, 0, "KERI10JSON000000_")]
+    #[case(common::VRSN_2_0, common::Serialage::JSON, 0, "KERICAACAAJSONAAAA.")]
+    #[case(common::VRSN_2_0, common::Serialage::CBOR, 65, "KERICAACAACBORAABB.")]
+    fn versify_deversify(
+        #[case] version: &common::Version,
+        #[case] kind: &str,
+        #[case] size: u32,
+        #[case] vs: &str,
+    ) {
+        assert_eq!(common::versify(None, Some(version), Some(kind), Some(size)).unwrap(), vs);
+
+        let result = common::deversify(vs).unwrap();
+        assert_eq!(result.ident, common::Identage::KERI);
+        assert_eq!(result.version, *version);
+        assert_eq!(result.kind, kind);
+        assert_eq!(result.size, size);
+        if version.major > 1 {
+            assert_eq!(common::genus_version(vs).unwrap().as_ref(), Some(common::VRSN_2_0));
+        } else {
+            assert!(common::genus_version(vs).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn versify_with_gvrsn() {
+        let version = common::Version { major: 2, minor: 1 };
+        let gvrsn = common::Version { major: 2, minor: 2 };
+        let vs = common::versify_with_gvrsn(
+            None,
+            Some(&version),
+            Some(common::Serialage::MGPK),
+            Some(4096),
+            Some(&gvrsn),
+        )
+        .unwrap();
+        assert_eq!(vs, "KERICABCACMGPKABAA.");
+        assert_eq!(common::deversify(&vs).unwrap().version, version);
+        assert_eq!(common::genus_version(&vs).unwrap(), Some(gvrsn));
+        assert_eq!(
+            common::versify_with_gvrsn(None, None, None, None, None).unwrap(),
+            "KERICAACAAJSONAAAA."
+        );
+
+        assert!(common::versify_with_gvrsn(None, Some(common::CURRENT_VERSION), None, None, None)
+            .is_err());
+        assert!(common::versify_with_gvrsn(None, None, None, Some(1 << 24), None).is_err());
+        let version = common::Version { major: 64, minor: 0 };
+        assert!(common::versify_with_gvrsn(None, Some(&version), None, None, None).is_err());
+        assert!(common::versify_with_gvrsn(Some("CESR"), None, None, None, None).is_err());
+    }
+
+    #[rstest]
+    fn sizeify_version_2(
+        #[values(common::Serialage::JSON, common::Serialage::MGPK, common::Serialage::CBOR)]
+        kind: &str,
+    ) {
+        let ked = dat!({"v":"KERICAACACJSONAAAA.","d":"","a":["filler"]});
+        let result = common::sizeify(&ked, Some(kind)).unwrap();
+        assert_eq!(result.version, *common::VRSN_2_0);
+
+        let vs = result.ked["v"].to_string().unwrap();
+        let deversified = common::deversify(&vs).unwrap();
+        assert_eq!(deversified.kind, kind);
+        assert_eq!(deversified.size as usize, result.raw.len());
+        assert_eq!(
+            common::genus_version(&vs).unwrap(),
+            Some(common::Version { major: 2, minor: 2 })
+        );
+
+        let sniffed = common::sniff(&result.raw).unwrap();
+        assert_eq!(sniffed.version, *common::VRSN_2_0);
+        assert_eq!(sniffed.size as usize, result.raw.len());
+        assert_eq!(common::loads(&result.raw, Some(sniffed.size), Some(kind)).unwrap(), result.ked);
+    }
+
     #[test]
     fn versify_unhappy_paths() {
         assert!(common::versify(Some("CESR"), None, None, None).is_err());
         assert!(common::versify(None, None, Some("CESR"), None).is_err());
+        let version = common::Version { major: 1, minor: 16 };
+        assert!(common::versify(None, Some(&version), None, None).is_err());
+        assert!(common::versify(None, None, None, Some(1 << 24)).is_err());
+        assert!(common::versify(None, Some(common::VRSN_2_0), None, Some(1 << 24)).is_err());
+        let version = common::Version { major: 64, minor: 0 };
+        assert!(common::versify(None, Some(&version), None, None).is_err());
     }
 
     #[rstest]
     fn deversify_unhappy_paths(
-        #[values("CESR10JSON000000_", "KERI10CESR000000_", "KERIXXJSON000000_")] vs: &str,
+        #[values(
+            "CESR10JSON000000_",
+            "KERI10CESR000000_",
+            "KERIXXJSON000000_",
+            "KERICAACAACESRAAAA.",
+            "KERIBAACAAJSONAAAA.",
+            "KERICAACAAJSONAAAA_",
+            "not a version string"
+        )]
+        vs: &str,
     ) {
-        assert!(common::deversify(vs).is_err());
+        let e = common::deversify(vs).unwrap_err();
+        assert!(matches!(e.downcast_ref::<Error>(), Some(Error::Validation(_))));
     }
 
     #[test]
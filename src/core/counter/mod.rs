@@ -1,12 +1,16 @@
 pub mod tables;
 
-use crate::core::util;
+use crate::core::{
+    common::{b64_to_version, version_to_b64, Version, CURRENT_VERSION},
+    util,
+};
 use crate::error::{err, Error, Result};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Counter {
     pub(crate) code: String,
     pub(crate) count: u32,
+    pub(crate) gvrsn: Version,
 }
 
 impl Counter {
@@ -17,6 +21,21 @@ impl Counter {
         qb64b: Option<&[u8]>,
         qb64: Option<&str>,
         qb2: Option<&[u8]>,
+    ) -> Result<Self> {
+        Self::new_with_gvrsn(count, count_b64, code, qb64b, qb64, qb2, CURRENT_VERSION)
+    }
+
+    /// Builds a counter against the code table of the CESR genus version `gvrsn`, as announced
+    /// by the genus-version counter of a stream. `new` and the other constructors use the 1.0
+    /// table.
+    pub fn new_with_gvrsn(
+        count: Option<u32>,
+        count_b64: Option<&str>,
+        code: Option<&str>,
+        qb64b: Option<&[u8]>,
+        qb64: Option<&str>,
+        qb2: Option<&[u8]>,
+        gvrsn: &Version,
     ) -> Result<Self> {
         if let Some(code) = code {
             let count = if let Some(count) = count {
@@ -27,13 +46,14 @@ impl Counter {
                 1
             };
 
-            Self::new_with_code_and_count(code, count)
+            Self::with_code_and_count(code, count, gvrsn)
         } else if let Some(qb64b) = qb64b {
-            Self::new_with_qb64b(qb64b)
+            let qb64 = String::from_utf8(qb64b.to_vec())?;
+            Self::with_qb64(&qb64, gvrsn)
         } else if let Some(qb64) = qb64 {
-            Self::new_with_qb64(qb64)
+            Self::with_qb64(qb64, gvrsn)
         } else if let Some(qb2) = qb2 {
-            Self::new_with_qb2(qb2)
+            Self::with_qb2(qb2, gvrsn)
         } else {
             err!(Error::Validation("need either code and count, qb64b, qb64 or qb2".to_string()))
         }
@@ -47,8 +67,13 @@ impl Counter {
         self.count
     }
 
+    /// The CESR version whose code table this counter was built against.
+    pub fn gvrsn(&self) -> Version {
+        self.gvrsn.clone()
+    }
+
     pub fn count_as_b64(&self, length: usize) -> Result<String> {
-        let length = if length == 0 { tables::sizage(&self.code())?.ss as usize } else { length };
+        let length = if length == 0 { self.sizage(&self.code())?.ss as usize } else { length };
         util::u32_to_b64(self.count(), length)
     }
 
@@ -106,12 +131,47 @@ impl Counter {
         Counter::sem_ver_parts_to_b64(parts)
     }
 
+    /// Builds a genus-version counter announcing the CESR version of the stream that follows.
+    pub fn new_with_genus_version(version: &Version) -> Result<Self> {
+        let count = util::b64_to_u32(&version_to_b64(version)?)?;
+        Self::new_with_code_and_count(tables::Codex::KERIACDCGenusVersion, count)
+    }
+
+    /// The CESR version announced by a genus-version counter.
+    pub fn genus_version(&self) -> Result<Version> {
+        if self.code() != tables::Codex::KERIACDCGenusVersion {
+            return err!(Error::UnexpectedCountCode(format!(
+                "not a genus version counter: code = {code}",
+                code = self.code()
+            )));
+        }
+
+        b64_to_version(&self.count_as_b64(0)?)
+    }
+
     pub fn new_with_code_and_count(code: &str, count: u32) -> Result<Self> {
+        Self::with_code_and_count(code, count, CURRENT_VERSION)
+    }
+
+    pub fn new_with_qb64(qb64: &str) -> Result<Self> {
+        Self::with_qb64(qb64, CURRENT_VERSION)
+    }
+
+    pub fn new_with_qb64b(qb64b: &[u8]) -> Result<Self> {
+        let qb64 = String::from_utf8(qb64b.to_vec())?;
+        Self::with_qb64(&qb64, CURRENT_VERSION)
+    }
+
+    pub fn new_with_qb2(qb2: &[u8]) -> Result<Self> {
+        Self::with_qb2(qb2, CURRENT_VERSION)
+    }
+
+    fn with_code_and_count(code: &str, count: u32, gvrsn: &Version) -> Result<Self> {
         if code.is_empty() {
             return err!(Error::EmptyMaterial("empty code".to_string()));
         }
 
-        let szg = tables::sizage(code)?;
+        let szg = Self::sizage_for(code, gvrsn)?;
         let cs = szg.hs + szg.ss;
         if szg.fs != cs || cs % 4 != 0 {
             // unreachable
@@ -128,27 +188,56 @@ impl Counter {
             )));
         }
 
-        Ok(Counter { code: code.to_string(), count })
+        Ok(Counter { code: code.to_string(), count, gvrsn: gvrsn.clone() })
     }
 
-    pub fn new_with_qb64(qb64: &str) -> Result<Self> {
-        let mut counter: Counter = Default::default();
+    fn with_qb64(qb64: &str, gvrsn: &Version) -> Result<Self> {
+        let mut counter = Self::empty(gvrsn)?;
         counter.exfil(qb64)?;
         Ok(counter)
     }
 
-    pub fn new_with_qb64b(qb64b: &[u8]) -> Result<Self> {
-        let qb64 = String::from_utf8(qb64b.to_vec())?;
-
-        let mut counter: Counter = Default::default();
-        counter.exfil(&qb64)?;
+    fn with_qb2(qb2: &[u8], gvrsn: &Version) -> Result<Self> {
+        let mut counter = Self::empty(gvrsn)?;
+        counter.bexfil(qb2)?;
         Ok(counter)
     }
 
-    pub fn new_with_qb2(qb2: &[u8]) -> Result<Self> {
-        let mut counter: Counter = Default::default();
-        counter.bexfil(qb2)?;
-        Ok(counter)
+    fn empty(gvrsn: &Version) -> Result<Self> {
+        // fail early on versions we have no table for
+        Self::sizage_for(tables::Codex::KERIACDCGenusVersion, gvrsn)?;
+        Ok(Counter { gvrsn: gvrsn.clone(), ..Default::default() })
+    }
+
+    fn sizage_for(code: &str, gvrsn: &Version) -> Result<tables::Sizage> {
+        match gvrsn.major {
+            1 => tables::sizage(code),
+            2 => tables::sizage_2_0(code),
+            _ => err!(Error::Value(format!(
+                "unsupported counter table: version = {}.{}",
+                gvrsn.major, gvrsn.minor
+            ))),
+        }
+    }
+
+    fn sizage(&self, code: &str) -> Result<tables::Sizage> {
+        Self::sizage_for(code, &self.gvrsn)
+    }
+
+    fn hardage(&self, s: &str) -> Result<u32> {
+        if self.gvrsn.major == 2 {
+            tables::hardage_2_0(s)
+        } else {
+            tables::hardage(s)
+        }
+    }
+
+    fn bardage(&self, b: &[u8]) -> Result<u32> {
+        if self.gvrsn.major == 2 {
+            tables::bardage_2_0(b)
+        } else {
+            tables::bardage(b)
+        }
     }
 
     fn sem_ver_parts_to_b64(parts: &[u8]) -> Result<String> {
@@ -176,7 +265,7 @@ impl Counter {
         let code = &self.code();
         let count = self.count();
 
-        let szg = tables::sizage(code)?;
+        let szg = self.sizage(code)?;
         let cs = szg.hs + szg.ss;
 
         if szg.fs != cs || cs % 4 != 0 {
@@ -220,7 +309,7 @@ impl Counter {
         // we validated there will be a char here, above.
         let first = &qb64[..2];
 
-        let hs = tables::hardage(first)? as usize;
+        let hs = self.hardage(first)? as usize;
         if qb64.len() < hs {
             return err!(Error::Shortage(format!(
                 "insufficient material for hard part of code: qb64 size = {}, hs = {hs}",
@@ -230,7 +319,7 @@ impl Counter {
 
         // bounds already checked
         let hard = &qb64[..hs];
-        let szg = tables::sizage(hard)?;
+        let szg = self.sizage(hard)?;
         let cs = szg.hs + szg.ss;
 
         if qb64.len() < cs as usize {
@@ -270,7 +359,7 @@ impl Counter {
             }
         }
 
-        let hs = tables::bardage(&first)?;
+        let hs = self.bardage(&first)?;
         let bhs = ((hs + 1) * 3) / 4;
         if qb2.len() < bhs as usize {
             return err!(Error::Shortage(format!(
@@ -280,7 +369,7 @@ impl Counter {
         }
 
         let hard = util::code_b2_to_b64(qb2, hs as usize)?;
-        let szg = tables::sizage(&hard)?;
+        let szg = self.sizage(&hard)?;
         let cs = szg.hs + szg.ss;
         let bcs = ((cs + 1) * 3) / 4;
         if qb2.len() < bcs as usize {
@@ -304,19 +393,22 @@ impl Counter {
     }
 
     pub fn full_size(&self) -> Result<usize> {
-        Ok(tables::sizage(&self.code())?.fs as usize)
+        Ok(self.sizage(&self.code())?.fs as usize)
     }
 }
 
 impl Default for Counter {
     fn default() -> Self {
-        Counter { code: "".to_string(), count: 0 }
+        Counter { code: "".to_string(), count: 0, gvrsn: CURRENT_VERSION.clone() }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::core::counter::{tables as counter, Counter};
+    use crate::core::{
+        common::Version,
+        counter::{tables as counter, Counter},
+    };
     use base64::{engine::general_purpose as b64_engine, Engine};
     use rstest::rstest;
 
@@ -378,6 +470,7 @@ mod test {
 
     #[rstest]
     #[case(0, "AAA", 0, "AAA", counter::Codex::KERIProtocolStack)]
+    #[case(8192, "CAA", 8192, "CAA", counter::Codex::KERIACDCGenusVersion)]
     fn versioned_creation(
         #[case] verint: u32,
         #[case] version: &str,
@@ -500,9 +593,13 @@ mod test {
         );
         assert!(Counter::sem_ver_str_to_b64("1.2.3.4").is_err());
         assert!(Counter::sem_ver_str_to_b64("bad.semantic.version").is_err());
-        assert!((Counter { code: counter::Codex::ControllerIdxSigs.to_string(), count: 64 * 64 })
-            .qb64()
-            .is_err());
+        assert!((Counter {
+            code: counter::Codex::ControllerIdxSigs.to_string(),
+            count: 64 * 64,
+            ..Default::default()
+        })
+        .qb64()
+        .is_err());
 
         assert!(Counter::new(None, None, None, None, Some(""), None).is_err());
         assert!(Counter::new(None, None, None, None, Some("--"), None).is_err());
@@ -525,10 +622,84 @@ mod test {
         assert!(Counter::new(None, None, None, None, None, Some(&[0xfb, 0xe0])).is_err());
     }
 
+    #[rstest]
+    #[case(2, 0, "-_AAACAA")]
+    #[case(1, 0, "-_AAABAA")]
+    #[case(2, 65, "-_AAACBB")]
+    fn genus_version(#[case] major: u8, #[case] minor: u8, #[case] qsc: &str) {
+        let version = Version { major, minor };
+        let counter = Counter::new_with_genus_version(&version).unwrap();
+        assert_eq!(counter.qb64().unwrap(), qsc);
+        assert_eq!(Counter::new_with_qb64(qsc).unwrap().genus_version().unwrap(), version);
+
+        let qb2 = counter.qb2().unwrap();
+        assert_eq!(Counter::new_with_qb2(&qb2).unwrap().genus_version().unwrap(), version);
+    }
+
+    #[test]
+    fn genus_version_unhappy_paths() {
+        let counter = Counter::new_with_code_and_count(counter::Codex::KERIProtocolStack, 0);
+        assert!(counter.unwrap().genus_version().is_err());
+        assert!(Counter::new_with_genus_version(&Version { major: 64, minor: 0 }).is_err());
+    }
+
+    #[rstest]
+    #[case("-KAW", 22, counter::Codex_2_0::ControllerIdxSigs)]
+    #[case("-0KAAAAW", 22, counter::Codex_2_0::BigControllerIdxSigs)]
+    #[case("-0CAAQAA", 65536, counter::Codex_2_0::BigAttachmentGroup)]
+    #[case("-_AAACAA", 8192, counter::Codex_2_0::KERIACDCGenusVersion)]
+    fn version_2_creation(#[case] qsc: &str, #[case] count: u32, #[case] code: &str) {
+        let gvrsn = Version { major: 2, minor: 0 };
+        let qscb2 = b64_engine::URL_SAFE.decode(qsc).unwrap();
+
+        let counter =
+            Counter::new_with_gvrsn(Some(count), None, Some(code), None, None, None, &gvrsn)
+                .unwrap();
+        assert_eq!(counter.qb64().unwrap(), qsc);
+        assert_eq!(counter.qb2().unwrap(), qscb2);
+        assert_eq!(counter.gvrsn(), gvrsn);
+
+        for counter in [
+            Counter::new_with_gvrsn(None, None, None, None, Some(qsc), None, &gvrsn).unwrap(),
+            Counter::new_with_gvrsn(None, None, None, Some(qsc.as_bytes()), None, None, &gvrsn)
+                .unwrap(),
+            Counter::new_with_gvrsn(None, None, None, None, None, Some(&qscb2), &gvrsn).unwrap(),
+        ] {
+            assert_eq!(counter.code(), code);
+            assert_eq!(counter.count(), count);
+            assert_eq!(counter.full_size().unwrap(), qsc.len());
+        }
+    }
+
+    #[test]
+    fn version_2_unhappy_paths() {
+        let gvrsn = Version { major: 2, minor: 0 };
+
+        // big variants only exist in 2.0, protocol stacks only in 1.0
+        assert!(Counter::new_with_qb64("-0KAAAAW").is_err());
+        assert!(Counter::new_with_gvrsn(None, None, None, None, Some("--AAAAAA"), None, &gvrsn)
+            .is_err());
+        assert!(Counter::new_with_code_and_count(counter::Codex_2_0::BigWitnessIdxSigs, 1).is_err());
+        let code = counter::Codex_2_0::ControllerIdxSigs;
+        assert!(Counter::new_with_gvrsn(Some(4096), None, Some(code), None, None, None, &gvrsn)
+            .is_err());
+
+        let gvrsn = Version { major: 3, minor: 0 };
+        assert!(
+            Counter::new_with_gvrsn(None, None, None, None, Some("-AAB"), None, &gvrsn).is_err()
+        );
+        assert!(Counter::new_with_gvrsn(None, None, None, None, None, Some(&[0xf8, 0, 1]), &gvrsn)
+            .is_err());
+        let code = counter::Codex::ControllerIdxSigs;
+        assert!(
+            Counter::new_with_gvrsn(Some(1), None, Some(code), None, None, None, &gvrsn).is_err()
+        );
+    }
+
     #[rstest]
     #[case(counter::Codex::ControllerIdxSigs, 1)]
     fn qb64b(#[case] code: &str, #[case] count: u32) {
-        let c = Counter { code: code.to_string(), count };
+        let c = Counter { code: code.to_string(), count, ..Default::default() };
         let qb64b = c.qb64b().unwrap();
         assert!(Counter::new(None, None, None, Some(&qb64b), None, None).is_ok());
     }
@@ -537,6 +708,7 @@ mod test {
     #[case("-AAB", counter::Codex::ControllerIdxSigs, 4)]
     #[case("-0VAAAQA", counter::Codex::BigAttachedMaterialQuadlets, 8)]
     #[case("--AAAAAA", counter::Codex::KERIProtocolStack, 8)]
+    #[case("-_AAACAA", counter::Codex::KERIACDCGenusVersion, 8)]
     fn qb_size(#[case] qsc: &str, #[case] code: &str, #[case] full_size: usize) {
         let counter = Counter::new(None, None, None, None, Some(qsc), None).unwrap();
         assert_eq!(counter.code(), code); // Just a self-check of the input data
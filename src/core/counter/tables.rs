@@ -25,6 +25,7 @@ pub(crate) fn sizage(s: &str) -> Result<Sizage> {
         "-V" => Sizage { hs: 2, ss: 2, fs: 4, ls: 0 },
         "-0V" => Sizage { hs: 3, ss: 5, fs: 8, ls: 0 },
         "--AAA" => Sizage { hs: 5, ss: 3, fs: 8, ls: 0 },
+        "-_AAA" => Sizage { hs: 5, ss: 3, fs: 8, ls: 0 },
         _ => return err!(Error::UnknownSizage(s.to_string())),
     })
 }
@@ -35,6 +36,7 @@ pub(crate) fn hardage(s: &str) -> Result<u32> {
         | "-V" => Ok(2),
         "-0" => Ok(3),
         "--" => Ok(5),
+        "-_" => Ok(5),
         _ => err!(Error::UnknownHardage(s.to_string())),
     }
 }
@@ -45,6 +47,38 @@ pub(crate) fn bardage(b: &[u8]) -> Result<u32> {
         | b">\x08" | b">\x09" | b">\x0a" | b">\x0b" | b">\x15" => Ok(2),
         b">4" => Ok(3),
         b">>" => Ok(5),
+        b">?" => Ok(5),
+        _ => err!(Error::UnknownBardage(format!("{b:?}"))),
+    }
+}
+
+pub(crate) fn sizage_2_0(s: &str) -> Result<Sizage> {
+    Ok(match s {
+        "-A" | "-B" | "-C" | "-D" | "-E" | "-F" | "-G" | "-H" | "-I" | "-J" | "-K" | "-L"
+        | "-M" | "-N" | "-O" | "-P" | "-Q" | "-R" | "-S" | "-T" | "-U" | "-V" | "-W" | "-X"
+        | "-Y" | "-Z" | "-a" | "-b" => Sizage { hs: 2, ss: 2, fs: 4, ls: 0 },
+        "-0A" | "-0B" | "-0C" | "-0D" | "-0E" | "-0F" | "-0G" | "-0H" | "-0I" | "-0J" | "-0K"
+        | "-0L" | "-0M" | "-0N" | "-0O" | "-0P" | "-0Q" | "-0R" | "-0S" | "-0T" | "-0U" | "-0V"
+        | "-0W" | "-0X" | "-0Y" | "-0Z" | "-0a" | "-0b" => Sizage { hs: 3, ss: 5, fs: 8, ls: 0 },
+        "-_AAA" => Sizage { hs: 5, ss: 3, fs: 8, ls: 0 },
+        _ => return err!(Error::UnknownSizage(s.to_string())),
+    })
+}
+
+pub(crate) fn hardage_2_0(s: &str) -> Result<u32> {
+    match s.as_bytes() {
+        [b'-', b'A'..=b'Z' | b'a'..=b'b'] => Ok(2),
+        b"-0" => Ok(3),
+        b"-_" => Ok(5),
+        _ => err!(Error::UnknownHardage(s.to_string())),
+    }
+}
+
+pub(crate) fn bardage_2_0(b: &[u8]) -> Result<u32> {
+    match b {
+        [62, 0..=27] => Ok(2),
+        [62, 52] => Ok(3),
+        [62, 63] => Ok(5),
         _ => err!(Error::UnknownBardage(format!("{b:?}"))),
     }
 }
@@ -67,6 +101,71 @@ pub mod Codex {
     pub const AttachedMaterialQuadlets: &str = "-V"; // Composed Grouped Attached Material Quadlet (4 char each)
     pub const BigAttachedMaterialQuadlets: &str = "-0V"; // Composed Grouped Attached Material Quadlet (4 char each)
     pub const KERIProtocolStack: &str = "--AAA"; // KERI ACDC Protocol Stack CESR Version
+    pub const KERIACDCGenusVersion: &str = "-_AAA"; // KERI ACDC Genus and CESR 2.0 Version
+}
+
+/// The CESR 2.0 counter codex. Every group has a big variant, and group counts are in quadlets
+/// (or triplets, in binary) rather than items.
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+pub mod Codex_2_0 {
+    pub const GenericGroup: &str = "-A"; // Generic Group (Universal with Override).
+    pub const BigGenericGroup: &str = "-0A"; // Big Generic Group (Universal with Override).
+    pub const BodyWithAttachmentGroup: &str = "-B"; // Message Body plus Attachments Group (Universal with Override).
+    pub const BigBodyWithAttachmentGroup: &str = "-0B"; // Big Message Body plus Attachments Group (Universal with Override).
+    pub const AttachmentGroup: &str = "-C"; // Message Attachments Only Group (Universal with Override).
+    pub const BigAttachmentGroup: &str = "-0C"; // Big Attachments Only Group (Universal with Override).
+    pub const DatagramSegmentGroup: &str = "-D"; // Datagram Segment Group (Universal).
+    pub const BigDatagramSegmentGroup: &str = "-0D"; // Big Datagram Segment Group (Universal).
+    pub const ESSRWrapperGroup: &str = "-E"; // ESSR Wrapper Group (Universal).
+    pub const BigESSRWrapperGroup: &str = "-0E"; // Big ESSR Wrapper Group (Universal).
+    pub const FixBodyGroup: &str = "-F"; // Fixed Field Message Body Group (Universal).
+    pub const BigFixBodyGroup: &str = "-0F"; // Big Fixed Field Message Body Group (Universal).
+    pub const MapBodyGroup: &str = "-G"; // Field Map Message Body Group (Universal).
+    pub const BigMapBodyGroup: &str = "-0G"; // Big Field Map Message Body Group (Universal).
+    pub const NonNativeBodyGroup: &str = "-H"; // Message body Non-native enclosed with Texter
+    pub const BigNonNativeBodyGroup: &str = "-0H"; // Big Message body Non-native enclosed with Texter
+    pub const GenericMapGroup: &str = "-I"; // Generic Field Map Group (Universal).
+    pub const BigGenericMapGroup: &str = "-0I"; // Big Generic Field Map Group (Universal).
+    pub const GenericListGroup: &str = "-J"; // Generic List Group (Universal).
+    pub const BigGenericListGroup: &str = "-0J"; // Big Generic List Group (Universal).
+    pub const ControllerIdxSigs: &str = "-K"; // Controller Indexed Signature(s) of qb64.
+    pub const BigControllerIdxSigs: &str = "-0K"; // Big Controller Indexed Signature(s) of qb64.
+    pub const WitnessIdxSigs: &str = "-L"; // Witness Indexed Signature(s) of qb64.
+    pub const BigWitnessIdxSigs: &str = "-0L"; // Big Witness Indexed Signature(s) of qb64.
+    pub const NonTransReceiptCouples: &str = "-M"; // NonTrans Receipt Couple(s), pre+cig.
+    pub const BigNonTransReceiptCouples: &str = "-0M"; // Big NonTrans Receipt Couple(s), pre+cig.
+    pub const TransReceiptQuadruples: &str = "-N"; // Trans Receipt Quadruple(s), pre+snu+dig+sig.
+    pub const BigTransReceiptQuadruples: &str = "-0N"; // Big Trans Receipt Quadruple(s), pre+snu+dig+sig.
+    pub const FirstSeenReplayCouples: &str = "-O"; // First Seen Replay Couple(s), fnu+dts.
+    pub const BigFirstSeenReplayCouples: &str = "-0O"; // Big First Seen Replay Couple(s), fnu+dts.
+    pub const TransIdxSigGroups: &str = "-P"; // Trans Indexed Signature Group(s), pre+snu+dig+ControllerIdxSigs of qb64.
+    pub const BigTransIdxSigGroups: &str = "-0P"; // Big Trans Indexed Signature Group(s), pre+snu+dig+ControllerIdxSigs of qb64.
+    pub const TransLastIdxSigGroups: &str = "-Q"; // Trans Last Est Evt Indexed Signature Group(s), pre+ControllerIdxSigs of qb64.
+    pub const BigTransLastIdxSigGroups: &str = "-0Q"; // Big Trans Last Est Evt Indexed Signature Group(s), pre+ControllerIdxSigs of qb64.
+    pub const SealSourceCouples: &str = "-R"; // Seal Source Couple(s), snu+dig of source sealing or sealed event.
+    pub const BigSealSourceCouples: &str = "-0R"; // Big Seal Source Couple(s), snu+dig of source sealing or sealed event.
+    pub const SealSourceTriples: &str = "-S"; // Seal Source Triple(s), pre+snu+dig of source sealing or sealed event.
+    pub const BigSealSourceTriples: &str = "-0S"; // Big Seal Source Triple(s), pre+snu+dig of source sealing or sealed event.
+    pub const PathedMaterialCouples: &str = "-T"; // Pathed Material Couple(s) of path+group.
+    pub const BigPathedMaterialCouples: &str = "-0T"; // Big Pathed Material Couple(s) of path+group.
+    pub const SadPathSigGroups: &str = "-U"; // SAD Path Group(s) sadpath+TransIdxSigGroup(s) of SAID.
+    pub const BigSadPathSigGroups: &str = "-0U"; // Big SAD Path Group(s) sadpath+TransIdxSigGroup(s) of SAID.
+    pub const RootSadPathSigGroups: &str = "-V"; // Root Path SAD Path Group(s), rootpath+SadPathGroup(s).
+    pub const BigRootSadPathSigGroups: &str = "-0V"; // Big Root Path SAD Path Group(s), rootpath+SadPathGroup(s).
+    pub const DigestSealSingles: &str = "-W"; // Digest Seal Single(s), dig of sealed data.
+    pub const BigDigestSealSingles: &str = "-0W"; // Big Digest Seal Single(s), dig of sealed data.
+    pub const MerkleRootSealSingles: &str = "-X"; // Merkle Tree Root Digest Seal Single(s), dig of sealed data.
+    pub const BigMerkleRootSealSingles: &str = "-0X"; // Big Merkle Tree Root Digest Seal Single(s), dig of sealed data.
+    pub const BackerRegistrarSealCouples: &str = "-Y"; // Backer Registrar Seal Couple(s), brid+dig of sealed data.
+    pub const BigBackerRegistrarSealCouples: &str = "-0Y"; // Big Backer Registrar Seal Couple(s), brid+dig of sealed data.
+    pub const SealSourceLastSingles: &str = "-Z"; // Seal Source Couple(s), pre of last source sealing event.
+    pub const BigSealSourceLastSingles: &str = "-0Z"; // Big Seal Source Couple(s), pre of last source sealing event.
+    pub const ESSRPayloadGroup: &str = "-a"; // ESSR Payload Group.
+    pub const BigESSRPayloadGroup: &str = "-0a"; // Big ESSR Payload Group.
+    pub const BlindedStateQuadruples: &str = "-b"; // Blinded State Quadruple(s), dig+uuid+said+state.
+    pub const BigBlindedStateQuadruples: &str = "-0b"; // Big Blinded State Quadruple(s), dig+uuid+said+state.
+    pub const KERIACDCGenusVersion: &str = "-_AAA"; // KERI ACDC Genus and CESR 2.0 Version
 }
 
 #[cfg(test)]
@@ -90,6 +189,7 @@ mod test {
     #[case("-V", 2)]
     #[case("-0", 3)]
     #[case("--", 5)]
+    #[case("-_", 5)]
     fn hardage(#[case] code: &str, #[case] hdg: u32) {
         assert_eq!(matter::hardage(code).unwrap(), hdg);
     }
@@ -110,6 +210,7 @@ mod test {
     #[case(&[62, 21], 2)]
     #[case(&[62, 52], 3)]
     #[case(&[62, 62], 5)]
+    #[case(&[62, 63], 5)]
     fn bardage(#[case] bard: &[u8], #[case] bdg: u32) {
         assert_eq!(matter::bardage(bard).unwrap(), bdg);
     }
@@ -130,6 +231,7 @@ mod test {
     #[case("-V", 2, 2, 4, 0)]
     #[case("-0V", 3, 5, 8, 0)]
     #[case("--AAA", 5, 3, 8, 0)]
+    #[case("-_AAA", 5, 3, 8, 0)]
     fn sizage(
         #[case] code: &str,
         #[case] hs: u32,
@@ -160,13 +262,88 @@ mod test {
     #[case(matter::Codex::AttachedMaterialQuadlets, "-V")]
     #[case(matter::Codex::BigAttachedMaterialQuadlets, "-0V")]
     #[case(matter::Codex::KERIProtocolStack, "--AAA")]
+    #[case(matter::Codex::KERIACDCGenusVersion, "-_AAA")]
     fn codex(#[case] code: &str, #[case] value: &str) {
         assert_eq!(code, value);
     }
 
+    #[rstest]
+    #[case("-A", 2)]
+    #[case("-K", 2)]
+    #[case("-Z", 2)]
+    #[case("-a", 2)]
+    #[case("-b", 2)]
+    #[case("-0", 3)]
+    #[case("-_", 5)]
+    fn hardage_2_0(#[case] code: &str, #[case] hdg: u32) {
+        assert_eq!(matter::hardage_2_0(code).unwrap(), hdg);
+    }
+
+    #[rstest]
+    #[case(&[62, 0], 2)]
+    #[case(&[62, 10], 2)]
+    #[case(&[62, 25], 2)]
+    #[case(&[62, 27], 2)]
+    #[case(&[62, 52], 3)]
+    #[case(&[62, 63], 5)]
+    fn bardage_2_0(#[case] bard: &[u8], #[case] bdg: u32) {
+        assert_eq!(matter::bardage_2_0(bard).unwrap(), bdg);
+    }
+
+    #[rstest]
+    #[case("-A", 2, 2, 4, 0)]
+    #[case("-K", 2, 2, 4, 0)]
+    #[case("-b", 2, 2, 4, 0)]
+    #[case("-0A", 3, 5, 8, 0)]
+    #[case("-0K", 3, 5, 8, 0)]
+    #[case("-0b", 3, 5, 8, 0)]
+    #[case("-_AAA", 5, 3, 8, 0)]
+    fn sizage_2_0(
+        #[case] code: &str,
+        #[case] hs: u32,
+        #[case] ss: u32,
+        #[case] fs: u32,
+        #[case] ls: u32,
+    ) {
+        let s = matter::sizage_2_0(code).unwrap();
+        assert_eq!(s.hs, hs);
+        assert_eq!(s.ss, ss);
+        assert_eq!(s.fs, fs);
+        assert_eq!(s.ls, ls);
+    }
+
+    #[rstest]
+    #[case(matter::Codex_2_0::GenericGroup, "-A")]
+    #[case(matter::Codex_2_0::BigGenericGroup, "-0A")]
+    #[case(matter::Codex_2_0::BodyWithAttachmentGroup, "-B")]
+    #[case(matter::Codex_2_0::AttachmentGroup, "-C")]
+    #[case(matter::Codex_2_0::BigAttachmentGroup, "-0C")]
+    #[case(matter::Codex_2_0::ControllerIdxSigs, "-K")]
+    #[case(matter::Codex_2_0::BigControllerIdxSigs, "-0K")]
+    #[case(matter::Codex_2_0::WitnessIdxSigs, "-L")]
+    #[case(matter::Codex_2_0::NonTransReceiptCouples, "-M")]
+    #[case(matter::Codex_2_0::TransReceiptQuadruples, "-N")]
+    #[case(matter::Codex_2_0::FirstSeenReplayCouples, "-O")]
+    #[case(matter::Codex_2_0::TransIdxSigGroups, "-P")]
+    #[case(matter::Codex_2_0::TransLastIdxSigGroups, "-Q")]
+    #[case(matter::Codex_2_0::SealSourceCouples, "-R")]
+    #[case(matter::Codex_2_0::SealSourceTriples, "-S")]
+    #[case(matter::Codex_2_0::BlindedStateQuadruples, "-b")]
+    #[case(matter::Codex_2_0::BigBlindedStateQuadruples, "-0b")]
+    #[case(matter::Codex_2_0::KERIACDCGenusVersion, "-_AAA")]
+    fn codex_2_0(#[case] code: &str, #[case] value: &str) {
+        assert_eq!(code, value);
+    }
+
     #[test]
     fn unhappy_paths() {
         assert!(matter::sizage("CESR").is_err());
         assert!(matter::bardage(&[63, 0]).is_err());
+        assert!(matter::sizage("-0K").is_err());
+        assert!(matter::sizage_2_0("--AAA").is_err());
+        assert!(matter::sizage_2_0("-c").is_err());
+        assert!(matter::hardage_2_0("--").is_err());
+        assert!(matter::bardage_2_0(&[62, 28]).is_err());
+        assert!(matter::bardage_2_0(&[62, 62]).is_err());
     }
 }
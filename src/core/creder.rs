@@ -1,9 +1,11 @@
 use crate::{
     common::{Identage, Ids, Serialage, Version, CURRENT_VERSION},
-    core::matter::tables as matter,
+    core::diger::Diger,
+    core::matter::{tables as matter, Matter},
     core::sadder::Sadder,
     core::saider::Saider,
-    data::{dat, Value},
+    core::salter::Salter,
+    data::Value,
     error::{err, Error, Result},
 };
 
@@ -19,6 +21,65 @@ pub struct Creder {
     saider: Saider,
 }
 
+// the sections of a credential that may be replaced by their saids
+const SECTIONS: &[&str] = &[Ids::a, Ids::e, Ids::r];
+
+// the said of a blinded attribute block, or the said that stands in for an undisclosed one
+fn block_said(block: &Value) -> Result<String> {
+    let said = match block {
+        Value::Object(map) => match map.get(Ids::d) {
+            Some(said) => said.to_string()?,
+            None => return err!(Error::Value("missing said in attribute block".to_string())),
+        },
+        _ => block.to_string()?,
+    };
+    Saider::new_with_qb64(&said)?;
+
+    Ok(said)
+}
+
+// the `section` under `label` with its said computed. Only the edge and rule sections have
+// blocks with saids of their own nested in them, the edges and rules and their groups, so the
+// values of an attribute section or a blinded attribute block are left as they are
+pub(crate) fn saidify_section(
+    label: &str,
+    section: &Value,
+    code: &str,
+    kind: &str,
+) -> Result<Value> {
+    let (_, section) = if [Ids::e, Ids::r].contains(&label) {
+        Saider::saidify_nested(section, Some(code), Some(kind), None)?
+    } else {
+        Saider::saidify(section, Some(code), Some(kind), None, None)?
+    };
+
+    Ok(section)
+}
+
+// the most compact form of the credential `ked`, with each of the attribute, edge and rule
+// sections replaced by its said and the attribute aggregate by the aggregate said
+pub(crate) fn compacted(ked: &Value) -> Result<Value> {
+    let mut ked = ked.clone();
+    for label in SECTIONS {
+        let map = ked.to_map()?;
+        if let Some(Value::Object(section)) = map.get(*label) {
+            match section.get(Ids::d) {
+                Some(said) => ked[*label] = said.clone(),
+                None => return err!(Error::Value(format!("missing said in section = {label}"))),
+            }
+        }
+    }
+
+    if let Some(Value::Array(aggregate)) = ked.to_map()?.get(Ids::A) {
+        match aggregate.first() {
+            Some(agid) => ked[Ids::A] = agid.clone(),
+            None => return err!(Error::Value("empty attribute aggregate".to_string())),
+        }
+    }
+
+    Ok(ked)
+}
+
 fn validate_ident(ident: &str) -> Result<()> {
     if ident != Identage::ACDC {
         return err!(Error::Value("creder must be an ACDC".to_string()));
@@ -85,6 +146,170 @@ impl Creder {
             Ok(dat!({}))
         }
     }
+
+    /// The most compact form of the credential, with each of the attribute, edge and rule
+    /// sections replaced by its said. The top level said is computed over the most compact
+    /// form, so every form of the credential has the same said.
+    pub fn compact(&self) -> Result<Self> {
+        Self::new_with_ked(&compacted(&self.ked())?, Some(&self.code()), Some(&self.kind()))
+    }
+
+    /// Whether the said of each section, and of every edge, rule and blinded attribute block,
+    /// matches its content, and the top level said matches the most compact form of the credential.
+    pub fn verify(&self) -> Result<bool> {
+        let ked = self.ked();
+        let code = self.code();
+        let kind = self.kind();
+        let map = ked.to_map()?;
+        let mut expected = ked.clone();
+        for label in SECTIONS {
+            if let Some(section @ Value::Object(_)) = map.get(*label) {
+                expected[*label] = saidify_section(label, section, &code, &kind)?;
+            }
+        }
+        if let Some(Value::Array(aggregate)) = map.get(Ids::A) {
+            let mut blocks = aggregate.clone();
+            for block in blocks.iter_mut().skip(1) {
+                if let Value::Object(_) = block {
+                    *block = saidify_section(Ids::A, block, &code, &kind)?;
+                }
+            }
+            expected[Ids::A] = Value::Array(blocks);
+        }
+        let (saider, _) =
+            Saider::saidify(&compacted(&expected)?, Some(&code), Some(&kind), None, None)?;
+        expected[Ids::d] = dat!(&saider.qb64()?);
+        if ked != expected {
+            return Ok(false);
+        }
+
+        match ked.to_map()?.get(Ids::A) {
+            Some(Value::Array(aggregate)) if !aggregate.is_empty() => {
+                let agid = aggregate[0].to_string()?;
+                Ok(agid == Self::aggregate(&aggregate[1..], Some(&self.code()))?)
+            }
+            Some(Value::Array(_)) => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    /// Blinds each of the `attributes` in a block of its own, `{d, u, label: value}`, with a
+    /// salty nonce `u`, and returns the selectively disclosable attribute aggregate: the
+    /// aggregate said followed by the blocks. Nonces are derived from `salter` by block index
+    /// when given, and random otherwise.
+    pub fn blind(
+        attributes: &Value,
+        salter: Option<&Salter>,
+        code: Option<&str>,
+        kind: Option<&str>,
+    ) -> Result<Value> {
+        let code = code.unwrap_or(matter::Codex::Blake3_256);
+        let mut blocks = vec![];
+        for (index, (label, value)) in attributes.to_map()?.into_iter().enumerate() {
+            if [Ids::d, Ids::u].contains(&label.as_str()) {
+                return err!(Error::Value(format!("reserved attribute label = {label}")));
+            }
+
+            let nonce = match salter {
+                Some(salter) => {
+                    let raw = salter.stretch(Some(16), Some(&index.to_string()), None, None)?;
+                    Salter::new_with_raw(&raw, None, None)?.qb64()?
+                }
+                None => Salter::new_with_defaults(None)?.qb64()?,
+            };
+
+            let mut block = dat!({ "d": "", "u": &nonce });
+            block[label.as_str()] = value;
+            let (_, block) = Saider::saidify(&block, Some(code), kind, None, None)?;
+            blocks.push(block);
+        }
+
+        let mut aggregate = vec![dat!(&Self::aggregate(&blocks, Some(code))?)];
+        aggregate.append(&mut blocks);
+        Ok(Value::Array(aggregate))
+    }
+
+    /// The aggregate said of `blocks`, the digest of the concatenation of their saids in
+    /// order. Each block is either a blinded attribute block or the said of one.
+    pub fn aggregate(blocks: &[Value], code: Option<&str>) -> Result<String> {
+        let code = code.unwrap_or(matter::Codex::Blake3_256);
+        if blocks.is_empty() {
+            return err!(Error::Value("empty attribute aggregate".to_string()));
+        }
+
+        let saids = blocks.iter().map(block_said).collect::<Result<Vec<_>>>()?;
+        Diger::new_with_ser(saids.concat().as_bytes(), Some(code))?.qb64()
+    }
+
+    /// The aggregate said of a selectively disclosable credential, if it is one.
+    pub fn agid(&self) -> Result<Option<String>> {
+        match self.ked().to_map()?.get(Ids::A) {
+            Some(Value::Array(aggregate)) => match aggregate.first() {
+                Some(agid) => Ok(Some(agid.to_string()?)),
+                None => err!(Error::Value("empty attribute aggregate".to_string())),
+            },
+            Some(agid) => Ok(Some(agid.to_string()?)),
+            None => Ok(None),
+        }
+    }
+
+    /// A disclosure of the attributes with `labels` from the full aggregate of a selectively
+    /// disclosable credential, in which every other block is replaced by its said. Every block has
+    /// a said `d` and a nonce `u`, so those labels are reserved and rejected.
+    pub fn disclose(&self, labels: &[&str]) -> Result<Value> {
+        if let Some(label) = labels.iter().find(|label| [Ids::d, Ids::u].contains(*label)) {
+            return err!(Error::Value(format!("reserved attribute label = {label}")));
+        }
+
+        let aggregate = match self.ked().to_map()?.get(Ids::A) {
+            Some(Value::Array(aggregate)) if !aggregate.is_empty() => aggregate.clone(),
+            _ => return err!(Error::Value("missing attribute aggregate blocks".to_string())),
+        };
+
+        let mut found = vec![];
+        let mut disclosure = vec![aggregate[0].clone()];
+        for block in &aggregate[1..] {
+            let map = block.to_map()?;
+            match labels.iter().find(|label| map.contains_key(**label)) {
+                Some(label) => {
+                    found.push(*label);
+                    disclosure.push(block.clone());
+                }
+                None => disclosure.push(dat!(&block_said(block)?)),
+            }
+        }
+
+        if let Some(label) = labels.iter().find(|label| !found.contains(label)) {
+            return err!(Error::Value(format!("missing attribute = {label}")));
+        }
+
+        Ok(Value::Array(disclosure))
+    }
+
+    /// Whether `disclosure` belongs to the aggregate of this credential: each disclosed block
+    /// must match its said and the saids must aggregate to that of the credential.
+    pub fn disclosed(&self, disclosure: &Value) -> Result<bool> {
+        let agid = match self.agid()? {
+            Some(agid) => agid,
+            None => return err!(Error::Value("missing attribute aggregate".to_string())),
+        };
+
+        let disclosure = disclosure.to_vec()?;
+        if disclosure.len() < 2 || disclosure[0].to_string()? != agid {
+            return Ok(false);
+        }
+
+        for block in &disclosure[1..] {
+            if let Value::Object(_) = block {
+                let saider = Saider::new_with_qb64(&block_said(block)?)?;
+                if !saider.verify(block, Some(true), None, Some(&self.kind()), None, None)? {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(agid == Self::aggregate(&disclosure[1..], Some(&self.code()))?)
+    }
 }
 
 impl Default for Creder {
@@ -171,11 +396,12 @@ impl Sadder for Creder {
 #[cfg(test)]
 mod test {
     use crate::{
-        common::{versify, Identage, Serialage, CURRENT_VERSION},
-        Saider,
+        common::{versify, Identage, Ids, Serialage, Tierage, CURRENT_VERSION},
+        data::Value,
+        Matter, Saider, Salter,
     };
 
-    use super::{Creder, Sadder};
+    use super::{compacted, Creder, Sadder};
 
     #[test]
     fn sanity() {
@@ -293,4 +519,134 @@ mod test {
         assert_eq!(creder.size(), acdc_message.len() as u32);
         assert_eq!(creder.version(), *CURRENT_VERSION);
     }
+
+    #[test]
+    fn selective_disclosure() {
+        let attributes = dat!({
+            "LEI": "254900OPPU84GM83MG36",
+            "name": "Acme",
+            "dt": "2023-04-30T00:34:11.853572+00:00"
+        });
+        let salter = Salter::new_with_raw(b"0123456789abcdef", None, Some(Tierage::min)).unwrap();
+
+        let aggregate = Creder::blind(&attributes, Some(&salter), None, None).unwrap();
+        let blocks = aggregate.to_vec().unwrap();
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(blocks[0].to_string().unwrap(), Creder::aggregate(&blocks[1..], None).unwrap());
+        assert_eq!(blocks[1].to_map().unwrap().keys().collect::<Vec<_>>(), vec!["d", "u", "LEI"]);
+        let (said, _) = Saider::saidify(&blocks[2], None, None, None, None).unwrap();
+        assert_eq!(blocks[2][Ids::d].to_string().unwrap(), said.qb64().unwrap());
+
+        // nonces are derived from the salter, and differ between blocks
+        assert_eq!(Creder::blind(&attributes, Some(&salter), None, None).unwrap(), aggregate);
+        assert_ne!(blocks[1][Ids::u], blocks[2][Ids::u]);
+        let random = Creder::blind(&attributes, None, None, None).unwrap();
+        assert_ne!(random[0], aggregate[0]);
+
+        // the aggregate commits to the saids only, whether or not the blocks are disclosed
+        let saids = blocks[1..].iter().map(|block| block[Ids::d].clone()).collect::<Vec<_>>();
+        assert_eq!(Creder::aggregate(&saids, None).unwrap(), blocks[0].to_string().unwrap());
+        assert_ne!(
+            Creder::aggregate(&[saids[1].clone(), saids[0].clone(), saids[2].clone()], None)
+                .unwrap(),
+            blocks[0].to_string().unwrap()
+        );
+
+        let mut ked = dat!({
+            "v": "ACDC10JSON000000_",
+            "d": "",
+            "i": "ENayINhHQnx6525EpcTmkvo6ZixiJyiskwkVNbMPohYa",
+            "s": "EE5uDJTq5cc6AEdqbyMpvARUjsK_chNdInf3xyRoCBcT",
+        });
+        ked[Ids::A] = aggregate.clone();
+        let (saider, _) =
+            Saider::saidify(&compacted(&ked).unwrap(), None, None, None, None).unwrap();
+        ked[Ids::d] = dat!(&saider.qb64().unwrap());
+        let creder = Creder::new_with_ked(&ked, None, None).unwrap();
+        assert!(creder.verify().unwrap());
+
+        // saidified over the full form rather than the compact form
+        let (_, full) = Saider::saidify(&ked, None, None, None, None).unwrap();
+        assert!(!Creder::new_with_ked(&full, None, None).unwrap().verify().unwrap());
+        assert_eq!(creder.agid().unwrap().unwrap(), blocks[0].to_string().unwrap());
+
+        let compact = creder.compact().unwrap();
+        assert_eq!(compact.ked()[Ids::A], blocks[0]);
+        assert_eq!(compact.agid().unwrap(), creder.agid().unwrap());
+        assert!(compact.verify().unwrap());
+
+        let disclosure = creder.disclose(&["name"]).unwrap();
+        assert_eq!(
+            disclosure,
+            Value::Array(vec![
+                blocks[0].clone(),
+                blocks[1][Ids::d].clone(),
+                blocks[2].clone(),
+                blocks[3][Ids::d].clone()
+            ])
+        );
+        assert!(creder.disclosed(&disclosure).unwrap());
+        assert!(compact.disclosed(&disclosure).unwrap());
+        assert!(compact.disclosed(&creder.disclose(&["LEI", "dt"]).unwrap()).unwrap());
+        assert!(compact.disclosed(&aggregate).unwrap());
+
+        // a tampered, reordered or foreign disclosure does not belong to the aggregate
+        let mut tampered = disclosure.clone();
+        tampered[2]["name"] = dat!("Evil");
+        assert!(!compact.disclosed(&tampered).unwrap());
+        let reordered = Value::Array(vec![
+            blocks[0].clone(),
+            blocks[2].clone(),
+            blocks[1][Ids::d].clone(),
+            blocks[3][Ids::d].clone(),
+        ]);
+        assert!(!compact.disclosed(&reordered).unwrap());
+        assert!(!compact.disclosed(&random).unwrap());
+        assert!(!compact.disclosed(&Value::Array(vec![blocks[0].clone()])).unwrap());
+
+        // a tampered aggregate breaks the credential
+        let mut ked = creder.ked();
+        ked[Ids::A][0] = random[0].clone();
+        let (_, ked) = Saider::saidify(&ked, None, None, None, None).unwrap();
+        assert!(!Creder::new_with_ked(&ked, None, None).unwrap().verify().unwrap());
+    }
+
+    #[test]
+    fn selective_disclosure_unhappy_paths() {
+        assert!(Creder::blind(&dat!({ "u": "nonce" }), None, None, None).is_err());
+        assert!(Creder::blind(&dat!(["LEI"]), None, None, None).is_err());
+        assert!(Creder::blind(&dat!({}), None, None, None).is_err());
+        assert!(Creder::aggregate(&[dat!("not a said")], None).is_err());
+        assert!(Creder::aggregate(&[dat!({ "u": "nonce" })], None).is_err());
+
+        let (_, ked) = Saider::saidify(
+            &dat!({
+                "v": "ACDC10JSON000000_",
+                "d": "",
+                "i": "ENayINhHQnx6525EpcTmkvo6ZixiJyiskwkVNbMPohYa",
+                "s": "EE5uDJTq5cc6AEdqbyMpvARUjsK_chNdInf3xyRoCBcT",
+                "a": { "d": "", "dt": "2023-04-30T00:34:11.853572+00:00" }
+            }),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let creder = Creder::new_with_ked(&ked, None, None).unwrap();
+        assert_eq!(creder.agid().unwrap(), None);
+        assert!(creder.disclose(&["dt"]).is_err());
+        assert!(creder.disclosed(&dat!([])).is_err());
+
+        let aggregate =
+            Creder::blind(&dat!({ "LEI": "254900OPPU84GM83MG36" }), None, None, None).unwrap();
+        let mut ked = creder.ked();
+        ked[Ids::A] = aggregate;
+        let (_, ked) = Saider::saidify(&ked, None, None, None, None).unwrap();
+        let creder = Creder::new_with_ked(&ked, None, None).unwrap();
+        assert!(creder.disclose(&["name"]).is_err());
+        assert!(creder.disclose(&["d"]).is_err());
+        assert!(creder.disclose(&["LEI", "u"]).is_err());
+        assert!(creder.compact().unwrap().disclose(&["LEI"]).is_err());
+    }
 }
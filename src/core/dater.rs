@@ -92,6 +92,15 @@ impl Dater {
     pub fn dtsb(&self) -> Result<Vec<u8>> {
         Ok(self.dts()?.as_bytes().to_vec())
     }
+
+    /// The datetime, for comparing datetimes that may be in different offsets.
+    pub fn datetime(&self) -> Result<chrono::DateTime<chrono::FixedOffset>> {
+        let dts = self.dts()?;
+        match chrono::DateTime::parse_from_rfc3339(&dts) {
+            Ok(datetime) => Ok(datetime),
+            Err(_) => err!(Error::Conversion(format!("invalid datetime = {dts}"))),
+        }
+    }
 }
 
 impl Matter for Dater {
@@ -146,6 +155,18 @@ mod test {
         assert!(Dater::new(None, None, None, None, Some(&qb64), None,).is_ok());
     }
 
+    #[test]
+    fn datetime() {
+        let earlier = Dater::new_with_dts("2020-08-22T17:50:09.988921-01:00", None).unwrap();
+        let later = Dater::new_with_dts("2020-08-22T17:50:10.000000-01:00", None).unwrap();
+        let same = Dater::new_with_dts("2020-08-22T18:50:09.988921+00:00", None).unwrap();
+        assert!(earlier.datetime().unwrap() < later.datetime().unwrap());
+        assert_eq!(earlier.datetime().unwrap(), same.datetime().unwrap());
+
+        let dater = Dater::new_with_dts("2020-08-22T17:50:09.988921+0x:00", None).unwrap();
+        assert!(dater.datetime().is_err());
+    }
+
     #[rstest]
     fn new_default(
         #[values(
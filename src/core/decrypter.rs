@@ -0,0 +1,189 @@
+use zeroize::ZeroizeOnDrop;
+
+use crate::core::{
+    cipher::Cipher,
+    matter::{tables as matter, Matter},
+    salter::Salter,
+    signer::Signer,
+};
+use crate::crypto::seal;
+use crate::error::{err, Error, Result};
+
+#[derive(Debug, Clone, PartialEq, ZeroizeOnDrop)]
+pub struct Decrypter {
+    raw: Vec<u8>,
+    #[zeroize(skip)]
+    code: String,
+    #[zeroize(skip)]
+    size: u32,
+}
+
+impl Default for Decrypter {
+    fn default() -> Self {
+        Decrypter { raw: vec![], code: matter::Codex::X25519_Private.to_string(), size: 0 }
+    }
+}
+
+fn validate_code(code: &str) -> Result<()> {
+    if code != matter::Codex::X25519_Private {
+        return err!(Error::UnexpectedCode(code.to_string()));
+    }
+
+    Ok(())
+}
+
+impl Decrypter {
+    pub fn new(
+        signer: Option<&Signer>,
+        code: Option<&str>,
+        raw: Option<&[u8]>,
+        qb64b: Option<&[u8]>,
+        qb64: Option<&str>,
+        qb2: Option<&[u8]>,
+    ) -> Result<Self> {
+        let code = code.unwrap_or(matter::Codex::X25519_Private);
+
+        let decrypter: Self = if let Some(signer) = signer {
+            if signer.code() != matter::Codex::Ed25519_Seed {
+                return err!(Error::UnexpectedCode(signer.code()));
+            }
+
+            let raw = seal::private_key_from_ed25519(&signer.raw())?;
+            Matter::new(Some(code), Some(&raw), None, None, None)?
+        } else {
+            Matter::new(Some(code), raw, qb64b, qb64, qb2)?
+        };
+        validate_code(&decrypter.code())?;
+
+        Ok(decrypter)
+    }
+
+    pub fn new_with_signer(signer: &Signer) -> Result<Self> {
+        Self::new(Some(signer), None, None, None, None, None)
+    }
+
+    pub fn new_with_raw(raw: &[u8], code: Option<&str>) -> Result<Self> {
+        Self::new(None, code, Some(raw), None, None, None)
+    }
+
+    pub fn new_with_qb64b(qb64b: &[u8]) -> Result<Self> {
+        Self::new(None, None, None, Some(qb64b), None, None)
+    }
+
+    pub fn new_with_qb64(qb64: &str) -> Result<Self> {
+        Self::new(None, None, None, None, Some(qb64), None)
+    }
+
+    pub fn new_with_qb2(qb2: &[u8]) -> Result<Self> {
+        Self::new(None, None, None, None, None, Some(qb2))
+    }
+
+    /// Opens a cipher, returning the sealed plaintext.
+    pub fn decrypt(&self, cipher: &Cipher) -> Result<Vec<u8>> {
+        validate_code(&self.code())?;
+        seal::open(&self.raw(), &cipher.raw())
+    }
+
+    pub fn decrypt_signer(&self, cipher: &Cipher, transferable: Option<bool>) -> Result<Signer> {
+        if cipher.code() != matter::Codex::X25519_Cipher_Seed {
+            return err!(Error::UnexpectedCode(cipher.code()));
+        }
+
+        Signer::new_with_qb64b(&self.decrypt(cipher)?, transferable)
+    }
+
+    pub fn decrypt_salter(&self, cipher: &Cipher, tier: Option<&str>) -> Result<Salter> {
+        if cipher.code() != matter::Codex::X25519_Cipher_Salt {
+            return err!(Error::UnexpectedCode(cipher.code()));
+        }
+
+        Salter::new_with_qb64b(&self.decrypt(cipher)?, tier)
+    }
+}
+
+impl Matter for Decrypter {
+    fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    fn raw(&self) -> Vec<u8> {
+        self.raw.clone()
+    }
+
+    fn size(&self) -> u32 {
+        self.size
+    }
+
+    fn set_code(&mut self, code: &str) {
+        self.code = code.to_string();
+    }
+
+    fn set_raw(&mut self, raw: &[u8]) {
+        self.raw = raw.to_vec();
+    }
+
+    fn set_size(&mut self, size: u32) {
+        self.size = size;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::{
+        decrypter::Decrypter,
+        encrypter::Encrypter,
+        matter::{tables as matter, Matter},
+        salter::Salter,
+        signer::Signer,
+    };
+
+    #[test]
+    fn convenience() {
+        let signer = Signer::new_with_defaults(None, None).unwrap();
+        let decrypter = Decrypter::new_with_signer(&signer).unwrap();
+
+        assert_eq!(decrypter.code(), matter::Codex::X25519_Private);
+        assert_eq!(Decrypter::new_with_raw(&decrypter.raw(), None).unwrap(), decrypter);
+        assert_eq!(Decrypter::new_with_qb64b(&decrypter.qb64b().unwrap()).unwrap(), decrypter);
+        assert_eq!(Decrypter::new_with_qb64(&decrypter.qb64().unwrap()).unwrap(), decrypter);
+        assert_eq!(Decrypter::new_with_qb2(&decrypter.qb2().unwrap()).unwrap(), decrypter);
+    }
+
+    #[test]
+    fn decrypt() {
+        let owner = Signer::new_with_defaults(Some(false), None).unwrap();
+        let encrypter = Encrypter::new_with_verfer(&owner.verfer()).unwrap();
+        let decrypter = Decrypter::new_with_signer(&owner).unwrap();
+
+        let signer = Signer::new_with_defaults(None, None).unwrap();
+        let cipher = encrypter.encrypt_matter(&signer).unwrap();
+        assert_eq!(decrypter.decrypt(&cipher).unwrap(), signer.qb64b().unwrap());
+
+        let decrypted = decrypter.decrypt_signer(&cipher, None).unwrap();
+        assert_eq!(decrypted, signer);
+        let decrypted = decrypter.decrypt_signer(&cipher, Some(false)).unwrap();
+        assert_eq!(decrypted.verfer().code(), matter::Codex::Ed25519N);
+
+        let salter = Salter::new_with_defaults(None).unwrap();
+        let cipher = encrypter.encrypt_matter(&salter).unwrap();
+        assert_eq!(decrypter.decrypt_salter(&cipher, None).unwrap(), salter);
+    }
+
+    #[test]
+    fn unhappy_paths() {
+        let owner = Signer::new_with_defaults(None, None).unwrap();
+        let encrypter = Encrypter::new_with_verfer(&owner.verfer()).unwrap();
+        let cipher = encrypter.encrypt_matter(&owner).unwrap();
+
+        let other = Decrypter::new_with_signer(&Signer::new_with_defaults(None, None).unwrap());
+        assert!(other.unwrap().decrypt(&cipher).is_err());
+
+        let decrypter = Decrypter::new_with_signer(&owner).unwrap();
+        assert!(decrypter.decrypt_salter(&cipher, None).is_err());
+
+        let signer =
+            Signer::new_with_defaults(None, Some(matter::Codex::ECDSA_256r1_Seed)).unwrap();
+        assert!(Decrypter::new_with_signer(&signer).is_err());
+        assert!(Decrypter::new_with_raw(&[0u8; 32], Some(matter::Codex::X25519)).is_err());
+    }
+}
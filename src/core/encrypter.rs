@@ -0,0 +1,227 @@
+use crate::core::{
+    cipher::Cipher,
+    matter::{tables as matter, Matter},
+    signer::Signer,
+    verfer::Verfer,
+};
+use crate::crypto::seal;
+use crate::error::{err, Error, Result};
+
+/// ```rust
+/// use cesride::{Decrypter, Encrypter, Matter, Signer};
+/// use std::error::Error;
+/// // here we seal a signing seed for storage at rest and recover it
+///
+/// fn example() -> Result<(), Box<dyn Error>> {
+///     let owner = Signer::new_with_defaults(None, None)?;
+///     let secret = Signer::new_with_defaults(None, None)?;
+///
+///     let encrypter = Encrypter::new_with_verfer(&owner.verfer())?;
+///     let cipher = encrypter.encrypt_matter(&secret)?;
+///
+///     let decrypter = Decrypter::new_with_signer(&owner)?;
+///     let signer = decrypter.decrypt_signer(&cipher, None)?;
+///
+///     assert_eq!(signer.qb64()?, secret.qb64()?);
+///
+///     Ok(())
+/// }
+///
+/// example().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Encrypter {
+    raw: Vec<u8>,
+    code: String,
+    size: u32,
+}
+
+impl Default for Encrypter {
+    fn default() -> Self {
+        Encrypter { raw: vec![], code: matter::Codex::X25519.to_string(), size: 0 }
+    }
+}
+
+fn validate_code(code: &str) -> Result<()> {
+    if code != matter::Codex::X25519 {
+        return err!(Error::UnexpectedCode(code.to_string()));
+    }
+
+    Ok(())
+}
+
+fn derive_raw(verfer: &Verfer) -> Result<Vec<u8>> {
+    const CODES: &[&str] = &[matter::Codex::Ed25519, matter::Codex::Ed25519N];
+
+    if !CODES.contains(&verfer.code().as_str()) {
+        return err!(Error::UnexpectedCode(verfer.code()));
+    }
+
+    seal::public_key_from_ed25519(&verfer.raw())
+}
+
+impl Encrypter {
+    pub fn new(
+        verfer: Option<&Verfer>,
+        code: Option<&str>,
+        raw: Option<&[u8]>,
+        qb64b: Option<&[u8]>,
+        qb64: Option<&str>,
+        qb2: Option<&[u8]>,
+    ) -> Result<Self> {
+        let code = code.unwrap_or(matter::Codex::X25519);
+
+        let encrypter: Self = if let Some(verfer) = verfer {
+            let raw = derive_raw(verfer)?;
+            Matter::new(Some(code), Some(&raw), None, None, None)?
+        } else {
+            Matter::new(Some(code), raw, qb64b, qb64, qb2)?
+        };
+        validate_code(&encrypter.code())?;
+
+        Ok(encrypter)
+    }
+
+    pub fn new_with_verfer(verfer: &Verfer) -> Result<Self> {
+        Self::new(Some(verfer), None, None, None, None, None)
+    }
+
+    pub fn new_with_raw(raw: &[u8], code: Option<&str>) -> Result<Self> {
+        Self::new(None, code, Some(raw), None, None, None)
+    }
+
+    pub fn new_with_qb64b(qb64b: &[u8]) -> Result<Self> {
+        Self::new(None, None, None, Some(qb64b), None, None)
+    }
+
+    pub fn new_with_qb64(qb64: &str) -> Result<Self> {
+        Self::new(None, None, None, None, Some(qb64), None)
+    }
+
+    pub fn new_with_qb2(qb2: &[u8]) -> Result<Self> {
+        Self::new(None, None, None, None, None, Some(qb2))
+    }
+
+    /// Returns true when `seed` (qb64b) is the signing seed whose verification key this
+    /// encryption key was converted from.
+    pub fn verify_seed(&self, seed: &[u8]) -> Result<bool> {
+        let signer = Signer::new_with_qb64b(seed, None)?;
+        Ok(derive_raw(&signer.verfer())? == self.raw())
+    }
+
+    /// Seals `ser` into a cipher with `code`.
+    pub fn encrypt(&self, ser: &[u8], code: &str) -> Result<Cipher> {
+        validate_code(&self.code())?;
+        let raw = seal::seal(&self.raw(), ser)?;
+        Cipher::new_with_raw(&raw, Some(code))
+    }
+
+    /// Seals the qb64b of a salt or signing seed into the matching cipher code.
+    pub fn encrypt_matter<T: Matter>(&self, prim: &T) -> Result<Cipher> {
+        let code = match prim.code().as_str() {
+            matter::Codex::Salt_128 => matter::Codex::X25519_Cipher_Salt,
+            matter::Codex::Ed25519_Seed => matter::Codex::X25519_Cipher_Seed,
+            _ => return err!(Error::UnexpectedCode(prim.code())),
+        };
+
+        self.encrypt(&prim.qb64b()?, code)
+    }
+}
+
+impl Matter for Encrypter {
+    fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    fn raw(&self) -> Vec<u8> {
+        self.raw.clone()
+    }
+
+    fn size(&self) -> u32 {
+        self.size
+    }
+
+    fn set_code(&mut self, code: &str) {
+        self.code = code.to_string();
+    }
+
+    fn set_raw(&mut self, raw: &[u8]) {
+        self.raw = raw.to_vec();
+    }
+
+    fn set_size(&mut self, size: u32) {
+        self.size = size;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::{
+        encrypter::Encrypter,
+        matter::{tables as matter, Matter},
+        salter::Salter,
+        signer::Signer,
+        verfer::Verfer,
+    };
+
+    #[test]
+    fn convenience() {
+        let signer = Signer::new_with_defaults(None, None).unwrap();
+        let encrypter = Encrypter::new_with_verfer(&signer.verfer()).unwrap();
+
+        assert_eq!(encrypter.code(), matter::Codex::X25519);
+        assert_eq!(encrypter.raw().len(), 32);
+        assert_eq!(Encrypter::new_with_raw(&encrypter.raw(), None).unwrap(), encrypter);
+        assert_eq!(Encrypter::new_with_qb64b(&encrypter.qb64b().unwrap()).unwrap(), encrypter);
+        assert_eq!(Encrypter::new_with_qb64(&encrypter.qb64().unwrap()).unwrap(), encrypter);
+        assert_eq!(Encrypter::new_with_qb2(&encrypter.qb2().unwrap()).unwrap(), encrypter);
+    }
+
+    #[test]
+    fn conversion() {
+        // the same key converts identically whether or not it is transferable
+        let seed = b"\x9f{\xa8\xa7\xa8C9\x96&\xfa\xb1\x99\xeb\xaa \xc4\x1bG\x11\xc4\xaeSAR\xc9\xbd\x04\x9d\x85)~\x93";
+        let transferable = Signer::new_with_raw(seed, Some(true), None).unwrap();
+        let nontransferable = Signer::new_with_raw(seed, Some(false), None).unwrap();
+
+        let encrypter = Encrypter::new_with_verfer(&transferable.verfer()).unwrap();
+        assert_eq!(Encrypter::new_with_verfer(&nontransferable.verfer()).unwrap(), encrypter);
+        assert!(encrypter.verify_seed(&transferable.qb64b().unwrap()).unwrap());
+
+        let other = Signer::new_with_defaults(None, None).unwrap();
+        assert!(!encrypter.verify_seed(&other.qb64b().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn encrypt() {
+        let signer = Signer::new_with_defaults(None, None).unwrap();
+        let encrypter = Encrypter::new_with_verfer(&signer.verfer()).unwrap();
+
+        let salter = Salter::new_with_defaults(None).unwrap();
+        let cipher = encrypter.encrypt_matter(&salter).unwrap();
+        assert_eq!(cipher.code(), matter::Codex::X25519_Cipher_Salt);
+        assert_eq!(cipher.qb64().unwrap().len(), 100);
+
+        let cipher = encrypter.encrypt_matter(&signer).unwrap();
+        assert_eq!(cipher.code(), matter::Codex::X25519_Cipher_Seed);
+        assert_eq!(cipher.qb64().unwrap().len(), 124);
+
+        // sealing is randomized
+        assert_ne!(encrypter.encrypt_matter(&signer).unwrap(), cipher);
+    }
+
+    #[test]
+    fn unhappy_paths() {
+        let signer =
+            Signer::new_with_defaults(None, Some(matter::Codex::ECDSA_256k1_Seed)).unwrap();
+        assert!(Encrypter::new_with_verfer(&signer.verfer()).is_err());
+        assert!(Encrypter::new_with_raw(&[0u8; 32], Some(matter::Codex::Ed25519)).is_err());
+
+        let verfer = Signer::new_with_defaults(None, None).unwrap().verfer();
+        let encrypter = Encrypter::new_with_verfer(&verfer).unwrap();
+        assert!(encrypter.encrypt_matter(&verfer).is_err());
+        assert!(encrypter.encrypt_matter(&signer).is_err());
+        assert!(encrypter.encrypt(b"abc", matter::Codex::X25519_Cipher_Salt).is_err());
+        assert!(Verfer::new_with_qb64b(&encrypter.qb64b().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,1524 @@
+use crate::{
+    core::{
+        attachments::Group,
+        cigar::Cigar,
+        common::{versify, Ids, Ilkage, Serialage, Version, CURRENT_VERSION},
+        dater::Dater,
+        diger::Diger,
+        kever::{verify_said, verify_sigs, verify_trans},
+        matter::{tables as matter, Matter},
+        number::Number,
+        parser::{Body, Message},
+        prefixer::Prefixer,
+        sadder::Sadder,
+        saider::Saider,
+        seal::SealEvent,
+        seqner::Seqner,
+        serder::Serder,
+        siger::Siger,
+        tholder::Tholder,
+        verfer::Verfer,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+/// Returns the witness threshold that ensures agreement among `n` witnesses when at most `f` of
+/// them are faulty. `f` defaults to the largest number of faults `n` can tolerate. With `weak`
+/// (the default) the smallest sufficient threshold is returned, otherwise the largest.
+pub fn ample(n: u128, f: Option<u128>, weak: Option<bool>) -> Result<u128> {
+    let weak = weak.unwrap_or(true);
+
+    if let Some(f) = f {
+        let m1 = (n + f + 2) / 2;
+        let m2 = n.saturating_sub(f);
+
+        if m2 < m1 && n > 0 {
+            return err!(Error::Value(format!("invalid f={f}, too big for n={n}")));
+        }
+
+        if weak {
+            Ok(n.min(m1).min(m2))
+        } else {
+            Ok(n.min(m1.max(m2)))
+        }
+    } else {
+        let f1 = 1.max(n.saturating_sub(1) / 3);
+        let f2 = 1.max(n.saturating_sub(1).div_ceil(3));
+
+        if weak {
+            Ok(n.min((n + f1 + 2) / 2).min((n + f2 + 2) / 2))
+        } else {
+            Ok(n.min(n.saturating_sub(f1).max((n + f1 + 2) / 2)))
+        }
+    }
+}
+
+pub(crate) fn validate_unique(label: &str, items: &[&str]) -> Result<()> {
+    for (i, item) in items.iter().enumerate() {
+        if items[..i].contains(item) {
+            return err!(Error::Value(format!("invalid {label} = {items:?}, has duplicates")));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_keys(keys: &[&str]) -> Result<()> {
+    for key in keys {
+        Verfer::new_with_qb64(key)?;
+    }
+
+    Ok(())
+}
+
+fn validate_digs(digs: &[&str]) -> Result<()> {
+    for dig in digs {
+        Diger::new_with_qb64(dig)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_pres(pres: &[&str]) -> Result<()> {
+    for pre in pres {
+        Prefixer::new_with_qb64(pre)?;
+    }
+
+    Ok(())
+}
+
+// the signing threshold for `count` keys, defaulting to a simple majority of at least `minimum`
+fn threshold(sith: Option<&Value>, count: usize, minimum: i64) -> Result<Tholder> {
+    let sith = if let Some(sith) = sith {
+        sith.clone()
+    } else {
+        dat!(minimum.max((count as i64 + 1) / 2))
+    };
+
+    Tholder::new_with_sith(&sith)
+}
+
+fn validate_tholders(
+    tholder: &Tholder,
+    keys: &[&str],
+    ntholder: &Tholder,
+    ndigs: &[&str],
+) -> Result<()> {
+    if tholder.num()?.is_some_and(|n| n < 1) {
+        return err!(Error::Value(format!(
+            "invalid sith = {n} less than 1",
+            n = tholder.num()?.unwrap_or_default()
+        )));
+    }
+    if tholder.size() as usize > keys.len() {
+        return err!(Error::Value(format!(
+            "invalid sith size = {s} for keys = {keys:?}",
+            s = tholder.size()
+        )));
+    }
+    if ntholder.size() as usize > ndigs.len() {
+        return err!(Error::Value(format!(
+            "invalid nsith size = {s} for ndigs = {ndigs:?}",
+            s = ntholder.size()
+        )));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn toader(toad: Option<u128>, wits: &[&str]) -> Result<Number> {
+    let toader = if let Some(toad) = toad {
+        Number::new_with_num(toad)?
+    } else if wits.is_empty() {
+        Number::new_with_num(0)?
+    } else {
+        Number::new_with_num(ample(wits.len() as u128, None, None)?)?
+    };
+
+    validate_toad(toader.num()?, wits)?;
+    Ok(toader)
+}
+
+/// Validates the witness threshold `toad` for the witnesses `wits`. Without witnesses it must be
+/// zero, otherwise at least one and at most the number of witnesses.
+pub fn validate_toad(toad: u128, wits: &[&str]) -> Result<()> {
+    if (wits.is_empty() && toad != 0)
+        || (!wits.is_empty() && (toad < 1 || toad > wits.len() as u128))
+    {
+        return err!(Error::Value(format!("invalid toad = {toad} for wits = {wits:?}")));
+    }
+
+    Ok(())
+}
+
+/// Returns the witnesses in effect after a rotation removes `cuts` from `wits` and appends
+/// `adds`. None of the lists may have duplicates, every cut must be a witness and no add may
+/// already be a witness or also be cut.
+pub fn rotate_wits(wits: &[&str], cuts: &[&str], adds: &[&str]) -> Result<Vec<String>> {
+    validate_unique("wits", wits)?;
+    validate_unique("cuts", cuts)?;
+    validate_unique("adds", adds)?;
+    validate_pres(adds)?;
+
+    if let Some(cut) = cuts.iter().find(|cut| !wits.contains(cut)) {
+        return err!(Error::Value(format!("invalid cut = {cut}, not in wits = {wits:?}")));
+    }
+    if let Some(add) = adds.iter().find(|add| cuts.contains(add) || wits.contains(add)) {
+        return err!(Error::Value(format!(
+            "invalid add = {add}, already in wits = {wits:?} or cuts = {cuts:?}"
+        )));
+    }
+
+    Ok(wits
+        .iter()
+        .filter(|wit| !cuts.contains(wit))
+        .chain(adds.iter())
+        .map(|wit| wit.to_string())
+        .collect())
+}
+
+/// Whether the indexed witness signatures `wigers` over `ser` satisfy the witness threshold
+/// `toad`. Each signature is verified against the witness at its index in `wits`; invalid
+/// signatures are ignored and duplicate indices count once.
+pub fn witnessed(ser: &[u8], wigers: &[Siger], wits: &[&str], toad: u128) -> Result<bool> {
+    validate_toad(toad, wits)?;
+
+    let verfers = wits.iter().map(|wit| Verfer::new_with_qb64(wit)).collect::<Result<Vec<_>>>()?;
+    let indices = verify_sigs(ser, wigers, &verfers)?;
+    Ok(indices.len() as u128 >= toad)
+}
+
+// numeric thresholds are hex strings unless intive, in which case they are integers
+fn sith_value(tholder: &Tholder, intive: bool) -> Result<Value> {
+    Ok(match tholder.num()? {
+        Some(n) if intive && n < u32::MAX => dat!(n),
+        _ => tholder.sith()?,
+    })
+}
+
+fn toad_value(toader: &Number, intive: bool) -> Result<Value> {
+    Ok(if intive && toader.num()? < u32::MAX as u128 {
+        dat!(toader.num()? as i64)
+    } else {
+        dat!(&toader.numh()?)
+    })
+}
+
+fn values(items: &[&str]) -> Vec<Value> {
+    items.iter().map(|item| dat!(*item)).collect()
+}
+
+/// Builds an inception event, or a delegated inception event when `delpre` is given.
+///
+/// With a single key and no `code` the identifier prefix is the key itself (basic derivation),
+/// otherwise it is a digest of the event (self-addressing derivation).
+#[allow(clippy::too_many_arguments)]
+pub fn incept(
+    keys: &[&str],          // current keys qb64
+    sith: Option<&Value>,   // current signing threshold
+    ndigs: Option<&[&str]>, // next key digests qb64
+    nsith: Option<&Value>,  // next signing threshold
+    toad: Option<u128>,     // witness threshold number
+    wits: Option<&[&str]>,  // witness identifier prefixes qb64
+    cnfg: Option<&[&str]>,  // configuration traits from common::Traitage
+    data: Option<&[Value]>, // seal dicts
+    version: Option<&Version>,
+    kind: Option<&str>,
+    code: Option<&str>,
+    intive: Option<bool>, // sith, nsith and toad are ints, not hex when numeric
+    delpre: Option<&str>, // delegator identifier prefix
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+    let intive = intive.unwrap_or(false);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    let ilk = if delpre.is_none() { Ilkage::icp } else { Ilkage::dip };
+    let sner = Number::new_with_num(0)?;
+
+    let ndigs = ndigs.unwrap_or(&[]);
+    validate_keys(keys)?;
+    validate_digs(ndigs)?;
+
+    let tholder = threshold(sith, keys.len(), 1)?;
+    let ntholder = threshold(nsith, ndigs.len(), 0)?;
+    validate_tholders(&tholder, keys, &ntholder, ndigs)?;
+
+    let wits = wits.unwrap_or(&[]);
+    validate_pres(wits)?;
+    validate_unique("wits", wits)?;
+    let toader = toader(toad, wits)?;
+
+    let cnfg = cnfg.unwrap_or(&[]);
+    let data = data.unwrap_or(&[]);
+
+    let mut ked = dat!({
+        "v": vs,
+        "t": ilk,
+        "d": "",
+        "i": "",
+        "s": &sner.numh()?,
+        "kt": sith_value(&tholder, intive)?,
+        "k": values(keys).as_slice(),
+        "nt": sith_value(&ntholder, intive)?,
+        "n": values(ndigs).as_slice(),
+        "bt": toad_value(&toader, intive)?,
+        "b": values(wits).as_slice(),
+        "c": values(cnfg).as_slice(),
+        "a": data
+    });
+
+    let code = if let Some(delpre) = delpre {
+        Prefixer::new_with_qb64(delpre)?;
+        let label = Ids::di;
+        ked[label] = dat!(delpre);
+        Some(code.unwrap_or(matter::Codex::Blake3_256))
+    } else {
+        code
+    };
+
+    let prefixer = if delpre.is_none() && code.is_none() && keys.len() == 1 {
+        let prefixer = Prefixer::new_with_qb64(keys[0])?;
+        if prefixer.digestive() {
+            return err!(Error::Value(format!(
+                "invalid code, digestive = {c}, must be derived from ked",
+                c = prefixer.code()
+            )));
+        }
+        prefixer
+    } else {
+        let prefixer = Prefixer::new_with_ked(&ked, None, code)?;
+        if delpre.is_some() && !prefixer.digestive() {
+            return err!(Error::Value(format!(
+                "invalid derivation code = {c} for delegation, must be digestive",
+                c = prefixer.code()
+            )));
+        }
+        prefixer
+    };
+
+    let label = Ids::i;
+    ked[label] = dat!(&prefixer.qb64()?);
+    let ked = if prefixer.digestive() {
+        let label = Ids::d;
+        ked[label] = dat!(&prefixer.qb64()?);
+        ked
+    } else {
+        let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
+        ked
+    };
+
+    Serder::new(None, None, None, Some(&ked), None)
+}
+
+/// Builds a delegated inception event. The prefix is always self-addressing.
+#[allow(clippy::too_many_arguments)]
+pub fn delcept(
+    keys: &[&str],
+    delpre: &str,
+    sith: Option<&Value>,
+    ndigs: Option<&[&str]>,
+    nsith: Option<&Value>,
+    toad: Option<u128>,
+    wits: Option<&[&str]>,
+    cnfg: Option<&[&str]>,
+    data: Option<&[Value]>,
+    version: Option<&Version>,
+    kind: Option<&str>,
+    code: Option<&str>,
+    intive: Option<bool>,
+) -> Result<Serder> {
+    incept(
+        keys,
+        sith,
+        ndigs,
+        nsith,
+        toad,
+        wits,
+        cnfg,
+        data,
+        version,
+        kind,
+        code,
+        intive,
+        Some(delpre),
+    )
+}
+
+/// Builds a rotation event, or a delegated rotation event when `ilk` is `drt`.
+///
+/// `wits` are the witnesses in effect before this event; `cuts` are removed from them and
+/// `adds` appended. The default `toad` is computed from the resulting witness list.
+#[allow(clippy::too_many_arguments)]
+pub fn rotate(
+    pre: &str,              // identifier prefix qb64
+    keys: &[&str],          // current keys qb64
+    dig: &str,              // digest of the prior event qb64
+    ilk: Option<&str>,      // rot or drt
+    sn: Option<u128>,       // sequence number
+    sith: Option<&Value>,   // current signing threshold
+    ndigs: Option<&[&str]>, // next key digests qb64
+    nsith: Option<&Value>,  // next signing threshold
+    toad: Option<u128>,     // witness threshold number after cuts and adds
+    wits: Option<&[&str]>,  // prior witness identifier prefixes qb64
+    cuts: Option<&[&str]>,  // witness prefixes to remove
+    adds: Option<&[&str]>,  // witness prefixes to add
+    data: Option<&[Value]>, // seal dicts
+    version: Option<&Version>,
+    kind: Option<&str>,
+    intive: Option<bool>,
+) -> Result<Serder> {
+    let ilk = ilk.unwrap_or(Ilkage::rot);
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+    let intive = intive.unwrap_or(false);
+
+    if ilk != Ilkage::rot && ilk != Ilkage::drt {
+        return err!(Error::Value(format!("invalid ilk = {ilk} for rotation")));
+    }
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    let sner = Number::new_with_num(sn.unwrap_or(1))?;
+    if sner.num()? < 1 {
+        return err!(Error::Value(format!("invalid sn = {sn} for rotation", sn = sner.num()?)));
+    }
+
+    Prefixer::new_with_qb64(pre)?;
+    Diger::new_with_qb64(dig)?;
+
+    let ndigs = ndigs.unwrap_or(&[]);
+    validate_keys(keys)?;
+    validate_digs(ndigs)?;
+
+    let tholder = threshold(sith, keys.len(), 1)?;
+    let ntholder = threshold(nsith, ndigs.len(), 0)?;
+    validate_tholders(&tholder, keys, &ntholder, ndigs)?;
+
+    let cuts = cuts.unwrap_or(&[]);
+    let adds = adds.unwrap_or(&[]);
+    let newits = rotate_wits(wits.unwrap_or(&[]), cuts, adds)?;
+    let toader = toader(toad, &newits.iter().map(|wit| wit.as_str()).collect::<Vec<_>>())?;
+
+    let data = data.unwrap_or(&[]);
+
+    let ked = dat!({
+        "v": vs,
+        "t": ilk,
+        "d": "",
+        "i": pre,
+        "s": &sner.numh()?,
+        "p": dig,
+        "kt": sith_value(&tholder, intive)?,
+        "k": values(keys).as_slice(),
+        "nt": sith_value(&ntholder, intive)?,
+        "n": values(ndigs).as_slice(),
+        "bt": toad_value(&toader, intive)?,
+        "br": values(cuts).as_slice(),
+        "ba": values(adds).as_slice(),
+        "a": data
+    });
+
+    let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
+    Serder::new(None, None, None, Some(&ked), None)
+}
+
+/// Builds a delegated rotation event.
+#[allow(clippy::too_many_arguments)]
+pub fn deltate(
+    pre: &str,
+    keys: &[&str],
+    dig: &str,
+    sn: Option<u128>,
+    sith: Option<&Value>,
+    ndigs: Option<&[&str]>,
+    nsith: Option<&Value>,
+    toad: Option<u128>,
+    wits: Option<&[&str]>,
+    cuts: Option<&[&str]>,
+    adds: Option<&[&str]>,
+    data: Option<&[Value]>,
+    version: Option<&Version>,
+    kind: Option<&str>,
+    intive: Option<bool>,
+) -> Result<Serder> {
+    rotate(
+        pre,
+        keys,
+        dig,
+        Some(Ilkage::drt),
+        sn,
+        sith,
+        ndigs,
+        nsith,
+        toad,
+        wits,
+        cuts,
+        adds,
+        data,
+        version,
+        kind,
+        intive,
+    )
+}
+
+/// Builds an interaction event.
+pub fn interact(
+    pre: &str,              // identifier prefix qb64
+    dig: &str,              // digest of the prior event qb64
+    sn: Option<u128>,       // sequence number
+    data: Option<&[Value]>, // seal dicts
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    let sner = Number::new_with_num(sn.unwrap_or(1))?;
+    if sner.num()? < 1 {
+        return err!(Error::Value(format!("invalid sn = {sn} for interaction", sn = sner.num()?)));
+    }
+
+    Prefixer::new_with_qb64(pre)?;
+    Diger::new_with_qb64(dig)?;
+
+    let data = data.unwrap_or(&[]);
+
+    let ked = dat!({
+        "v": vs,
+        "t": Ilkage::ixn,
+        "d": "",
+        "i": pre,
+        "s": &sner.numh()?,
+        "p": dig,
+        "a": data
+    });
+
+    let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
+    Serder::new(None, None, None, Some(&ked), None)
+}
+
+/// Builds a query (qry) message asking for the resource at `route`, such as `logs`, `ksn` or
+/// `mbx`, to be replied to on `reply_route`.
+pub fn query(
+    route: &str,               // resource queried
+    reply_route: Option<&str>, // route of the reply
+    modifiers: Option<&Value>, // query parameters, such as i for the queried prefix
+    stamp: Option<&str>,       // datetime, ISO 8601, now by default
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    let dater = Dater::new(stamp, None, None, None, None, None)?;
+    let modifiers = modifiers.cloned().unwrap_or(dat!({}));
+    modifiers.to_map()?;
+
+    let ked = dat!({
+        "v": vs,
+        "t": Ilkage::qry,
+        "d": "",
+        "dt": &dater.dts()?,
+        "r": route,
+        "rr": reply_route.unwrap_or(""),
+        "q": modifiers
+    });
+
+    let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
+    Serder::new(None, None, None, Some(&ked), None)
+}
+
+/// Builds a reply (rpy) message carrying `data` on `route`, such as `/ksn`, `/end/role/add` or
+/// `/loc/scheme`.
+pub fn reply(
+    route: &str,          // route of the reply
+    data: Option<&Value>, // attributes
+    stamp: Option<&str>,  // datetime, ISO 8601, now by default
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    let dater = Dater::new(stamp, None, None, None, None, None)?;
+    let data = data.cloned().unwrap_or(dat!({}));
+    data.to_map()?;
+
+    let ked = dat!({
+        "v": vs,
+        "t": Ilkage::rpy,
+        "d": "",
+        "dt": &dater.dts()?,
+        "r": route,
+        "a": data
+    });
+
+    let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
+    Serder::new(None, None, None, Some(&ked), None)
+}
+
+/// Builds the body of a receipt for the event `said` at `sn` in the log of `pre`.
+pub fn receipt(
+    pre: &str,  // identifier prefix qb64 of the receipted event
+    sn: u128,   // sequence number of the receipted event
+    said: &str, // said of the receipted event
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    let sner = Number::new_with_num(sn)?;
+
+    let ked = dat!({
+        "v": vs,
+        "t": Ilkage::rct,
+        "d": said,
+        "i": pre,
+        "s": &sner.numh()?
+    });
+
+    Serder::new_with_ked(&ked, None, None)
+}
+
+// the receipt body for `serder`, in its version and kind
+fn receipt_for(serder: &Serder) -> Result<Serder> {
+    receipt(
+        &serder.pre()?,
+        serder.sn()?,
+        &serder.said()?,
+        Some(&serder.version()),
+        Some(&serder.kind()),
+    )
+}
+
+/// Receipts `serder` with the signatures of non-transferable signers, such as witnesses. Each
+/// cigar must carry the verfer that made it.
+pub fn nontrans_receipt(serder: &Serder, cigars: &[Cigar]) -> Result<Message> {
+    let mut couples = vec![];
+    for cigar in cigars {
+        let prefixer = Prefixer::new_with_qb64b(&cigar.verfer().qb64b()?)?;
+        if prefixer.transferable() {
+            return err!(Error::Validation(format!(
+                "transferable signer in non-transferable receipt: pre = {pre}",
+                pre = prefixer.qb64()?
+            )));
+        }
+        couples.push((prefixer, cigar.clone()));
+    }
+
+    Ok(Message {
+        body: Body::Serder(receipt_for(serder)?),
+        attachments: vec![Group::NonTransReceiptCouples(couples)],
+    })
+}
+
+/// Receipts `serder` with the indexed signatures of a transferable validator, sealed to the
+/// validator's establishment event `said` at `sn`. With `quadruples` each signature is attached
+/// as a TransReceiptQuadruple, otherwise they share one TransIdxSigGroup (the default).
+pub fn trans_receipt(
+    serder: &Serder,
+    pre: &str,  // validator identifier prefix qb64
+    sn: u128,   // sequence number of the validator's establishment event
+    said: &str, // said of the validator's establishment event
+    sigers: &[Siger],
+    quadruples: Option<bool>,
+) -> Result<Message> {
+    let prefixer = Prefixer::new_with_qb64(pre)?;
+    if !prefixer.transferable() {
+        return err!(Error::Validation(format!(
+            "non-transferable validator in transferable receipt: pre = {pre}"
+        )));
+    }
+
+    let seqner = Seqner::new_with_sn(sn)?;
+    let saider = Saider::new_with_qb64(said)?;
+
+    let group = if quadruples.unwrap_or(false) {
+        Group::TransReceiptQuadruples(
+            sigers
+                .iter()
+                .map(|siger| (prefixer.clone(), seqner.clone(), saider.clone(), siger.clone()))
+                .collect(),
+        )
+    } else {
+        Group::TransIdxSigGroups(vec![(prefixer, seqner, saider, sigers.to_vec())])
+    };
+
+    Ok(Message { body: Body::Serder(receipt_for(serder)?), attachments: vec![group] })
+}
+
+/// Verifies the signatures attached to `message`, a receipt of `serder`. Transferable signatures
+/// are checked against the keys of the matching establishment event in `validators`, which must
+/// be the validator's latest establishment event there, and must satisfy its signing threshold.
+pub fn verify_receipt(serder: &Serder, message: &Message, validators: &[Serder]) -> Result<()> {
+    let rct = match &message.body {
+        Body::Serder(rct) => rct,
+        _ => return err!(Error::Validation("receipt is not a key event message".to_string())),
+    };
+
+    let ilk = rct.ked()[Ids::t].to_string()?;
+    if ilk != Ilkage::rct {
+        return err!(Error::Validation(format!("unexpected ilk = {ilk} for receipt")));
+    }
+
+    if rct.pre()? != serder.pre()? || rct.sn()? != serder.sn()? {
+        return err!(Error::Validation(format!(
+            "receipt of another event: pre = {pre}, sn = {sn}",
+            pre = rct.pre()?,
+            sn = rct.sn()?
+        )));
+    }
+
+    let said = rct.ked()[Ids::d].to_string()?;
+    if said != serder.saider().qb64()? {
+        return err!(Error::Validation(format!("receipt of another event: said = {said}")));
+    }
+
+    if message.attachments.is_empty() {
+        return err!(Error::Validation("receipt without signatures".to_string()));
+    }
+
+    // quadruples for the same validator event are evaluated together against its threshold
+    let mut sealed: Vec<(Prefixer, Seqner, Saider, Vec<Siger>)> = vec![];
+    for group in &message.attachments {
+        match group {
+            Group::NonTransReceiptCouples(couples) => {
+                for (prefixer, cigar) in couples {
+                    if prefixer.transferable() {
+                        return err!(Error::Validation(format!(
+                            "transferable signer in non-transferable receipt: pre = {pre}",
+                            pre = prefixer.qb64()?
+                        )));
+                    }
+
+                    let verfer = Verfer::new_with_qb64b(&prefixer.qb64b()?)?;
+                    if !verfer.verify(&cigar.raw(), &serder.raw())? {
+                        return err!(Error::Validation(format!(
+                            "invalid receipt signature: pre = {pre}",
+                            pre = prefixer.qb64()?
+                        )));
+                    }
+                }
+            }
+            Group::TransIdxSigGroups(groups) => sealed.append(&mut groups.clone()),
+            Group::TransReceiptQuadruples(quadruples) => {
+                for (prefixer, seqner, saider, siger) in quadruples {
+                    match sealed
+                        .iter_mut()
+                        .find(|(p, n, d, _)| p == prefixer && n == seqner && d == saider)
+                    {
+                        Some((_, _, _, sigers)) => sigers.push(siger.clone()),
+                        None => sealed.push((
+                            prefixer.clone(),
+                            seqner.clone(),
+                            saider.clone(),
+                            vec![siger.clone()],
+                        )),
+                    }
+                }
+            }
+            _ => {
+                return err!(Error::Validation(format!(
+                    "unexpected attachment in receipt: group = {group:?}"
+                )))
+            }
+        }
+    }
+
+    for (prefixer, seqner, saider, sigers) in &sealed {
+        verify_trans(&serder.raw(), validators, prefixer, seqner, saider, sigers)?;
+    }
+
+    Ok(())
+}
+
+/// Event seals anchoring each of `serders`, for the `a` field of the delegator's approving
+/// interaction or rotation event.
+pub fn anchors(serders: &[Serder]) -> Result<Vec<Value>> {
+    serders.iter().map(|serder| Ok(SealEvent::new_with_serder(serder)?.value())).collect()
+}
+
+/// Whether the `a` field of `anchor` holds `seal`. Entries that are not event seals are
+/// skipped.
+pub fn anchored(anchor: &Serder, seal: &SealEvent) -> Result<bool> {
+    let label = Ids::a;
+    let ked = anchor.ked();
+    if !ked.to_map()?.contains_key(label) {
+        return Ok(false);
+    }
+
+    for value in ked[label].to_vec()? {
+        match SealEvent::new_with_value(&value) {
+            Ok(other) if other == *seal => return Ok(true),
+            _ => continue,
+        }
+    }
+
+    Ok(false)
+}
+
+/// Validates that the delegated event `serder` is approved by the delegator `delpre`. `anchor`
+/// is the delegator's event holding the approving seal and `source` the SealSourceCouples or
+/// SealSourceTriples attached to `serder`, locating `anchor`. The saids of both events must match
+/// their content. The delegator's signatures on `anchor` are not checked here; it must come from
+/// a validated key event log.
+pub fn validate_delegation(
+    serder: &Serder,
+    delpre: &str,
+    anchor: &Serder,
+    source: &Group,
+) -> Result<()> {
+    verify_said(serder)?;
+    verify_said(anchor)?;
+
+    let label = Ids::t;
+    let ilk = serder.ked()[label].to_string()?;
+    match ilk.as_str() {
+        Ilkage::dip => {
+            let label = Ids::di;
+            let di = serder.ked()[label].to_string()?;
+            if di != delpre {
+                return err!(Error::Validation(format!(
+                    "mismatched delegator = {di}, expected {delpre}"
+                )));
+            }
+        }
+        Ilkage::drt => {}
+        _ => return err!(Error::Validation(format!("unexpected ilk = {ilk} for delegation"))),
+    }
+
+    if anchor.pre()? != delpre {
+        return err!(Error::Validation(format!(
+            "anchoring event of another identifier: pre = {pre}",
+            pre = anchor.pre()?
+        )));
+    }
+
+    let (seqner, saider) = match source {
+        Group::SealSourceCouples(couples) if couples.len() == 1 => couples[0].clone(),
+        Group::SealSourceTriples(triples) if triples.len() == 1 => {
+            let (prefixer, seqner, saider) = triples[0].clone();
+            if prefixer.qb64()? != delpre {
+                return err!(Error::Validation(format!(
+                    "source seal of another identifier: pre = {pre}",
+                    pre = prefixer.qb64()?
+                )));
+            }
+            (seqner, saider)
+        }
+        _ => {
+            return err!(Error::Validation(format!(
+                "expected a single source seal, got group = {source:?}"
+            )))
+        }
+    };
+
+    if seqner.sn()? != anchor.sn()? || saider.qb64()? != anchor.said()? {
+        return err!(Error::Validation(format!(
+            "source seal sn = {sn}, said = {said} does not locate the anchoring event",
+            sn = seqner.sn()?,
+            said = saider.qb64()?
+        )));
+    }
+
+    if !anchored(anchor, &SealEvent::new_with_serder(serder)?)? {
+        return err!(Error::Validation(format!(
+            "delegated event said = {said} not anchored by delegator = {delpre}",
+            said = serder.said()?
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        ample, anchored, anchors, delcept, deltate, incept, interact, nontrans_receipt, query,
+        receipt, reply, rotate, rotate_wits, trans_receipt, validate_delegation, validate_toad,
+        verify_receipt, witnessed,
+    };
+    use crate::core::{
+        attachments::{Attachments, Group},
+        cigar::Cigar,
+        common::{Ids, Ilkage},
+        fixtures::{digs, keys, signers, witnesses},
+        matter::{tables as matter, Matter},
+        parser::{Body, Parser},
+        prefixer::Prefixer,
+        sadder::Sadder,
+        saider::Saider,
+        seal::{SealDigest, SealEvent},
+        seqner::Seqner,
+        serder::Serder,
+        siger::Siger,
+        signer::Signer,
+        util::strs,
+    };
+    use rstest::rstest;
+
+    const AIDS: &[&str] = &[
+        "BEy_EvE8OUMqj0AgCJ3wOCOrIVHVtwubYAysPyaAv9VI",
+        "BC9Df6ssUZQFQZJYVUyfudw4WTQsugGcvVD_Z4ChFGE4",
+        "BEejlxZytU7gjUwtgkmNKmBWiFPKSsXjk_uxzoun8dtK",
+    ];
+
+    #[rstest]
+    #[case(0, None, None, 0)]
+    #[case(1, None, None, 1)]
+    #[case(2, None, None, 2)]
+    #[case(3, None, None, 3)]
+    #[case(4, None, None, 3)]
+    #[case(5, None, None, 4)]
+    #[case(6, None, None, 4)]
+    #[case(7, None, None, 5)]
+    #[case(4, Some(1), None, 3)]
+    #[case(7, Some(2), None, 5)]
+    #[case(4, None, Some(false), 3)]
+    #[case(7, Some(2), Some(false), 5)]
+    #[case(10, Some(3), Some(false), 7)]
+    fn ample_thresholds(
+        #[case] n: u128,
+        #[case] f: Option<u128>,
+        #[case] weak: Option<bool>,
+        #[case] m: u128,
+    ) {
+        assert_eq!(ample(n, f, weak).unwrap(), m);
+    }
+
+    #[test]
+    fn ample_unhappy() {
+        assert!(ample(4, Some(2), None).is_err());
+    }
+
+    #[test]
+    fn inception() {
+        let keys = keys(&signers(3, 0));
+        let ndigs = digs(&signers(3, 3));
+        let serder = incept(
+            &strs(&keys),
+            Some(&dat!(2)),
+            Some(&strs(&ndigs)),
+            None,
+            None,
+            Some(&AIDS[1..]),
+            None,
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            Some(true),
+            None,
+        )
+        .unwrap();
+
+        let ked = serder.ked();
+        assert_eq!(ked[Ids::t].to_string().unwrap(), Ilkage::icp);
+        assert_eq!(ked[Ids::i].to_string().unwrap(), serder.pre().unwrap());
+        assert_eq!(ked[Ids::d].to_string().unwrap(), serder.said().unwrap());
+        assert_eq!(ked[Ids::kt].to_i64().unwrap(), 2);
+        assert_eq!(ked[Ids::nt].to_i64().unwrap(), 2);
+        assert_eq!(ked[Ids::bt].to_i64().unwrap(), 2);
+        assert_eq!(serder.sner().unwrap().num().unwrap(), 0);
+        assert_eq!(
+            serder.digers().unwrap().iter().map(|d| d.qb64().unwrap()).collect::<Vec<String>>(),
+            ndigs
+        );
+    }
+
+    #[test]
+    fn delegated_inception() {
+        let keys = keys(&signers(1, 0));
+        let serder = delcept(
+            &strs(&keys),
+            AIDS[0],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let ked = serder.ked();
+        assert_eq!(ked[Ids::t].to_string().unwrap(), Ilkage::dip);
+        assert_eq!(ked[Ids::di].to_string().unwrap(), AIDS[0]);
+        assert_eq!(serder.pre().unwrap(), serder.said().unwrap());
+        assert_eq!(serder.pre().unwrap()[..1].to_string(), "E");
+    }
+
+    #[rstest]
+    #[case(Some(&[] as &[&str]), Some(dat!(2)), None, None, None)]
+    #[case(None, Some(dat!(0)), None, None, None)]
+    #[case(None, Some(dat!(4)), None, None, None)]
+    #[case(None, None, Some(dat!(2)), None, None)]
+    #[case(None, None, None, Some(1), None)]
+    #[case(None, None, None, None, Some(&[AIDS[1], AIDS[1]] as &[&str]))]
+    #[case(None, None, None, None, Some(&["not a prefix"] as &[&str]))]
+    #[case(Some(&["not a key"] as &[&str]), None, None, None, None)]
+    fn inception_unhappy(
+        #[case] keys: Option<&[&str]>,
+        #[case] sith: Option<crate::data::Value>,
+        #[case] nsith: Option<crate::data::Value>,
+        #[case] toad: Option<u128>,
+        #[case] wits: Option<&[&str]>,
+    ) {
+        let default = self::keys(&signers(3, 0));
+        let default = strs(&default);
+        let ndigs = digs(&signers(1, 3));
+        assert!(incept(
+            keys.unwrap_or(&default),
+            sith.as_ref(),
+            Some(&strs(&ndigs)),
+            nsith.as_ref(),
+            toad,
+            wits,
+            None,
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn inception_with_digestive_key() {
+        let dig = &digs(&signers(1, 0))[0];
+        assert!(incept(
+            &[dig],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rotation() {
+        let icp = incept(
+            &[AIDS[0]],
+            None,
+            None,
+            None,
+            None,
+            Some(&AIDS[1..]),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let pre = icp.pre().unwrap();
+        let dig = icp.said().unwrap();
+
+        let keys = keys(&signers(2, 0));
+        let ndigs = digs(&signers(2, 2));
+        let serder = rotate(
+            &pre,
+            &strs(&keys),
+            &dig,
+            None,
+            None,
+            None,
+            Some(&strs(&ndigs)),
+            None,
+            None,
+            Some(&AIDS[1..]),
+            Some(&[AIDS[1]]),
+            Some(&[AIDS[0]]),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let ked = serder.ked();
+        assert_eq!(ked[Ids::t].to_string().unwrap(), Ilkage::rot);
+        assert_eq!(ked[Ids::i].to_string().unwrap(), pre);
+        assert_eq!(ked[Ids::p].to_string().unwrap(), dig);
+        assert_eq!(ked[Ids::kt].to_string().unwrap(), "1");
+        assert_eq!(ked[Ids::bt].to_string().unwrap(), "2");
+        assert_eq!(ked[Ids::br].to_vec().unwrap()[0].to_string().unwrap(), AIDS[1]);
+        assert_eq!(ked[Ids::ba].to_vec().unwrap()[0].to_string().unwrap(), AIDS[0]);
+        assert_eq!(serder.sner().unwrap().num().unwrap(), 1);
+        assert!(serder.est().unwrap());
+
+        let serder = deltate(
+            &pre,
+            &strs(&keys),
+            &dig,
+            Some(2),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(serder.ked()[Ids::t].to_string().unwrap(), Ilkage::drt);
+        assert_eq!(serder.sner().unwrap().num().unwrap(), 2);
+    }
+
+    #[rstest]
+    #[case(Some(Ilkage::ixn), None, None, None, None, None)]
+    #[case(None, Some(0), None, None, None, None)]
+    #[case(None, None, Some(3), None, None, None)]
+    #[case(None, None, None, Some(&[AIDS[0]] as &[&str]), None, None)]
+    #[case(None, None, None, None, Some(&[AIDS[1]] as &[&str]), None)]
+    #[case(None, None, None, Some(&[AIDS[1]] as &[&str]), None, Some(&[AIDS[2]] as &[&str]))]
+    #[case(None, None, None, None, None, Some(&[AIDS[2], AIDS[2]] as &[&str]))]
+    fn rotation_unhappy(
+        #[case] ilk: Option<&str>,
+        #[case] sn: Option<u128>,
+        #[case] toad: Option<u128>,
+        #[case] cuts: Option<&[&str]>,
+        #[case] adds: Option<&[&str]>,
+        #[case] wits: Option<&[&str]>,
+    ) {
+        let dig = &digs(&signers(1, 0))[0];
+        let wits = wits.unwrap_or(&AIDS[1..]);
+        assert!(rotate(
+            AIDS[0],
+            &[AIDS[0]],
+            dig,
+            ilk,
+            sn,
+            None,
+            None,
+            None,
+            toad,
+            Some(wits),
+            cuts,
+            adds,
+            None,
+            None,
+            None,
+            None,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn interaction() {
+        let dig = &digs(&signers(1, 0))[0];
+        let data = [dat!({"i": AIDS[1], "s": "0", "d": dig})];
+        let serder = interact(AIDS[0], dig, Some(3), Some(&data), None, None).unwrap();
+
+        let ked = serder.ked();
+        assert_eq!(ked[Ids::t].to_string().unwrap(), Ilkage::ixn);
+        assert_eq!(ked[Ids::p].to_string().unwrap(), *dig);
+        assert_eq!(ked[Ids::a].to_vec().unwrap(), data);
+        assert_eq!(serder.sner().unwrap().num().unwrap(), 3);
+        assert!(!serder.est().unwrap());
+
+        assert!(interact(AIDS[0], dig, Some(0), None, None, None).is_err());
+        assert!(interact(AIDS[0], AIDS[1], None, None, None, None).is_err());
+    }
+
+    #[test]
+    fn query_and_reply() {
+        let dt = "2023-04-30T00:34:11.853572+00:00";
+
+        let qry = query("logs", Some("log"), Some(&dat!({ "i": AIDS[0] })), Some(dt), None, None)
+            .unwrap();
+        let ked = qry.ked();
+        assert_eq!(
+            ked.to_map().unwrap().keys().cloned().collect::<Vec<_>>(),
+            vec!["v", "t", "d", "dt", "r", "rr", "q"]
+        );
+        assert_eq!(ked[Ids::t].to_string().unwrap(), Ilkage::qry);
+        assert_eq!(ked[Ids::dt].to_string().unwrap(), dt);
+        assert_eq!(ked[Ids::r].to_string().unwrap(), "logs");
+        assert_eq!(ked[Ids::rr].to_string().unwrap(), "log");
+        assert_eq!(ked[Ids::q], dat!({ "i": AIDS[0] }));
+        assert!(Saider::new_with_qb64(&qry.said().unwrap())
+            .unwrap()
+            .verify(&ked, Some(true), Some(true), None, None, None)
+            .unwrap());
+
+        let qry = query("mbx", None, None, None, None, None).unwrap();
+        assert_eq!(qry.ked()[Ids::rr].to_string().unwrap(), "");
+        assert_eq!(qry.ked()[Ids::q], dat!({}));
+        assert_eq!(qry.ked()[Ids::dt].to_string().unwrap().len(), 32);
+
+        let data = dat!({ "cid": AIDS[0], "role": "witness", "eid": AIDS[1] });
+        let rpy = reply("/end/role/add", Some(&data), Some(dt), None, Some("CBOR")).unwrap();
+        let ked = rpy.ked();
+        assert_eq!(
+            ked.to_map().unwrap().keys().cloned().collect::<Vec<_>>(),
+            vec!["v", "t", "d", "dt", "r", "a"]
+        );
+        assert_eq!(ked[Ids::t].to_string().unwrap(), Ilkage::rpy);
+        assert_eq!(ked[Ids::r].to_string().unwrap(), "/end/role/add");
+        assert_eq!(ked[Ids::a], data);
+        assert_eq!(rpy.kind(), "CBOR");
+        assert_eq!(Serder::new_with_raw(&rpy.raw()).unwrap(), rpy);
+
+        // attributes and modifiers are maps, stamps are datetimes
+        assert!(reply("/ksn", Some(&dat!([])), None, None, None).is_err());
+        assert!(reply("/ksn", None, Some("yesterday"), None, None).is_err());
+        assert!(query("ksn", None, Some(&dat!("i")), None, None, None).is_err());
+    }
+
+    // an inception of `n` keys derived from `offset`, and the signers of those keys
+    fn controller(n: u8, offset: u8, sith: u8) -> (Serder, Vec<Signer>) {
+        let signers = signers(n, offset);
+        let keys = keys(&signers);
+        let serder = incept(
+            &strs(&keys),
+            Some(&dat!(sith)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .unwrap();
+        (serder, signers)
+    }
+
+    fn indexed(signers: &[Signer], ser: &[u8]) -> Vec<Siger> {
+        signers
+            .iter()
+            .enumerate()
+            .map(|(i, signer)| signer.sign_indexed(ser, false, i as u32, None).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn receipts() {
+        let (serder, _) = controller(3, 0, 2);
+        let (validator, vsigners) = controller(2, 10, 2);
+        let witnesses = witnesses(2, 20);
+
+        let rct = receipt(&serder.pre().unwrap(), 0, &serder.said().unwrap(), None, None).unwrap();
+        let ked = rct.ked();
+        assert_eq!(ked[Ids::t].to_string().unwrap(), Ilkage::rct);
+        assert_eq!(ked[Ids::d].to_string().unwrap(), serder.said().unwrap());
+        assert_eq!(ked[Ids::i].to_string().unwrap(), serder.pre().unwrap());
+        assert_eq!(ked[Ids::s].to_string().unwrap(), "0");
+
+        let cigars: Vec<Cigar> =
+            witnesses.iter().map(|w| w.sign_unindexed(&serder.raw()).unwrap()).collect();
+        let message = nontrans_receipt(&serder, &cigars).unwrap();
+        assert_eq!(message.body, Body::Serder(rct.clone()));
+        assert!(verify_receipt(&serder, &message, &[]).is_ok());
+
+        // receipts survive a trip through the parser
+        let mut ims = message.body.raw();
+        ims.append(&mut Attachments::new(&message.attachments).qb64b().unwrap());
+        let parsed = Parser::new_with_ims(&ims).parse_one().unwrap();
+        assert!(verify_receipt(&serder, &parsed, &[]).is_ok());
+
+        let sigers = indexed(&vsigners, &serder.raw());
+        let (vpre, vsaid) = (validator.pre().unwrap(), validator.said().unwrap());
+        for quadruples in [None, Some(true)] {
+            let message = trans_receipt(&serder, &vpre, 0, &vsaid, &sigers, quadruples).unwrap();
+            assert_eq!(message.body, Body::Serder(rct.clone()));
+            assert!(verify_receipt(&serder, &message, std::slice::from_ref(&validator)).is_ok());
+
+            // unknown validator event
+            assert!(verify_receipt(&serder, &message, &[]).is_err());
+            assert!(verify_receipt(&serder, &message, std::slice::from_ref(&serder)).is_err());
+
+            // the validator threshold is not met
+            let message =
+                trans_receipt(&serder, &vpre, 0, &vsaid, &sigers[..1], quadruples).unwrap();
+            assert!(verify_receipt(&serder, &message, std::slice::from_ref(&validator)).is_err());
+        }
+
+        // signed with keys that have since been rotated out
+        let rot = rotate(
+            &vpre,
+            &strs(&keys(&signers(2, 12))),
+            &vsaid,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let message = trans_receipt(&serder, &vpre, 0, &vsaid, &sigers, None).unwrap();
+        assert!(verify_receipt(&serder, &message, &[validator.clone(), rot]).is_err());
+
+        match &trans_receipt(&serder, &vpre, 0, &vsaid, &sigers, Some(true)).unwrap().attachments[..]
+        {
+            [Group::TransReceiptQuadruples(quadruples)] => assert_eq!(quadruples.len(), 2),
+            attachments => panic!("unexpected attachments {attachments:?}"),
+        }
+    }
+
+    #[test]
+    fn receipts_unhappy() {
+        let (serder, signers) = controller(3, 0, 2);
+        let (other, _) = controller(1, 5, 1);
+        let witness = Signer::new_with_raw(&[20; 32], Some(false), None).unwrap();
+
+        // signatures of another event
+        let cigar = witness.sign_unindexed(&other.raw()).unwrap();
+        let message = nontrans_receipt(&serder, &[cigar]).unwrap();
+        assert!(verify_receipt(&serder, &message, &[]).is_err());
+
+        // a receipt of another event
+        let cigar = witness.sign_unindexed(&serder.raw()).unwrap();
+        let message = nontrans_receipt(&other, std::slice::from_ref(&cigar)).unwrap();
+        assert!(verify_receipt(&serder, &message, &[]).is_err());
+
+        let mut message = nontrans_receipt(&serder, &[cigar]).unwrap();
+        message.attachments = vec![];
+        assert!(verify_receipt(&serder, &message, &[]).is_err());
+
+        let sigers = indexed(&signers, &serder.raw());
+        message.attachments = vec![Group::ControllerIdxSigs(sigers.clone())];
+        assert!(verify_receipt(&serder, &message, &[]).is_err());
+
+        // receipts are not events
+        message.body = Body::Serder(serder.clone());
+        message.attachments = vec![];
+        assert!(verify_receipt(&serder, &message, &[]).is_err());
+
+        // transferable witnesses and non-transferable validators
+        let cigar = signers[0].sign_unindexed(&serder.raw()).unwrap();
+        assert!(nontrans_receipt(&serder, &[cigar]).is_err());
+        let said = serder.said().unwrap();
+        assert!(trans_receipt(&serder, AIDS[0], 0, &said, &sigers, None).is_err());
+    }
+
+    #[rstest]
+    #[case(0, 0, true)]
+    #[case(1, 0, false)]
+    #[case(0, 1, false)]
+    #[case(1, 1, true)]
+    #[case(2, 1, false)]
+    #[case(1, 3, true)]
+    #[case(3, 3, true)]
+    #[case(4, 3, false)]
+    fn witness_thresholds(#[case] toad: u128, #[case] n: usize, #[case] valid: bool) {
+        assert_eq!(validate_toad(toad, &AIDS[..n]).is_ok(), valid);
+    }
+
+    #[rstest]
+    #[case(&AIDS[..2], &[], &[AIDS[2]], AIDS)]
+    #[case(AIDS, &[AIDS[0], AIDS[2]], &[], &[AIDS[1]])]
+    #[case(&AIDS[1..], &[AIDS[1]], &[AIDS[0]], &[AIDS[2], AIDS[0]])]
+    #[case(&[], &[], &[], &[])]
+    fn witness_rotation(
+        #[case] wits: &[&str],
+        #[case] cuts: &[&str],
+        #[case] adds: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        assert_eq!(rotate_wits(wits, cuts, adds).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(&[AIDS[0], AIDS[0]], &[], &[])]
+    #[case(&AIDS[..2], &[AIDS[0], AIDS[0]], &[])]
+    #[case(&AIDS[..2], &[], &[AIDS[2], AIDS[2]])]
+    #[case(&AIDS[..2], &[AIDS[2]], &[])]
+    #[case(&AIDS[..2], &[], &[AIDS[1]])]
+    #[case(&AIDS[..2], &[AIDS[1]], &[AIDS[1]])]
+    #[case(&AIDS[..2], &[], &["not a prefix"])]
+    fn witness_rotation_unhappy(
+        #[case] wits: &[&str],
+        #[case] cuts: &[&str],
+        #[case] adds: &[&str],
+    ) {
+        assert!(rotate_wits(wits, cuts, adds).is_err());
+    }
+
+    #[test]
+    fn witness_receipts() {
+        let (serder, _) = controller(1, 0, 1);
+        let witnesses = witnesses(3, 20);
+        let wits = keys(&witnesses);
+        let wits = strs(&wits);
+        let wigers = indexed(&witnesses, &serder.raw());
+
+        assert!(witnessed(&serder.raw(), &wigers, &wits, 3).unwrap());
+        assert!(witnessed(&serder.raw(), &wigers[..2], &wits, 2).unwrap());
+        assert!(!witnessed(&serder.raw(), &wigers[..2], &wits, 3).unwrap());
+        assert!(witnessed(&[], &[], &[], 0).unwrap());
+
+        // duplicates count once, signatures of other material not at all
+        let duplicates = [wigers[0].clone(), wigers[0].clone()];
+        assert!(!witnessed(&serder.raw(), &duplicates, &wits, 2).unwrap());
+        assert!(!witnessed(b"abcdefg", &wigers, &wits, 1).unwrap());
+
+        assert!(witnessed(&serder.raw(), &wigers, &wits, 4).is_err());
+        assert!(witnessed(&serder.raw(), &wigers, &wits, 0).is_err());
+    }
+
+    // locates `anchor` with a source couple, or a triple when `triple`
+    fn source(anchor: &Serder, triple: bool) -> Group {
+        let seqner = Seqner::new_with_sn(anchor.sn().unwrap()).unwrap();
+        let saider = Saider::new_with_qb64(&anchor.said().unwrap()).unwrap();
+        if triple {
+            let prefixer = Prefixer::new_with_qb64(&anchor.pre().unwrap()).unwrap();
+            Group::SealSourceTriples(vec![(prefixer, seqner, saider)])
+        } else {
+            Group::SealSourceCouples(vec![(seqner, saider)])
+        }
+    }
+
+    #[test]
+    fn delegation() {
+        let (delegator, _) = controller(1, 0, 1);
+        let delpre = delegator.pre().unwrap();
+        let dip = delcept(
+            &strs(&keys(&signers(1, 5))),
+            &delpre,
+            None,
+            Some(&strs(&digs(&signers(1, 6)))),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let data = anchors(std::slice::from_ref(&dip)).unwrap();
+        assert_eq!(
+            data,
+            vec![dat!({
+                "i": &dip.pre().unwrap(),
+                "s": "0",
+                "d": &dip.said().unwrap()
+            })]
+        );
+
+        // other seals may share the anchoring event
+        let dig = dip.said().unwrap();
+        let data = [SealDigest::new(&dig).unwrap().value(), data[0].clone()];
+        let ixn = interact(&delpre, &delegator.said().unwrap(), Some(1), Some(&data), None, None)
+            .unwrap();
+        assert!(anchored(&ixn, &SealEvent::new_with_serder(&dip).unwrap()).unwrap());
+        assert!(!anchored(&delegator, &SealEvent::new_with_serder(&dip).unwrap()).unwrap());
+
+        validate_delegation(&dip, &delpre, &ixn, &source(&ixn, false)).unwrap();
+        validate_delegation(&dip, &delpre, &ixn, &source(&ixn, true)).unwrap();
+
+        let drt = deltate(
+            &dip.pre().unwrap(),
+            &strs(&keys(&signers(1, 6))),
+            &dip.said().unwrap(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let rot = rotate(
+            &delpre,
+            &strs(&keys(&signers(1, 1))),
+            &ixn.said().unwrap(),
+            None,
+            Some(2),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(&anchors(std::slice::from_ref(&drt)).unwrap()),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        validate_delegation(&drt, &delpre, &rot, &source(&rot, false)).unwrap();
+
+        // a body swapped under the anchored said, or under the said of the anchoring event
+        let mut ked = drt.ked();
+        ked["a"] = dat!([{"d": "EBabiu_JCkE0GbiglDXNB5C4NQq-hiGgxhHKXBxkiojg"}]);
+        let forged = Serder::new_with_ked(&ked, None, None).unwrap();
+        assert_eq!(forged.said().unwrap(), drt.said().unwrap());
+        assert!(validate_delegation(&forged, &delpre, &rot, &source(&rot, false)).is_err());
+        let mut ked = rot.ked();
+        ked["a"] = dat!(&[ked["a"][0].clone(), dat!({"d": &dig})][..]);
+        let forged = Serder::new_with_ked(&ked, None, None).unwrap();
+        assert!(validate_delegation(&drt, &delpre, &forged, &source(&rot, false)).is_err());
+
+        // not anchored, or located by the wrong source seal
+        assert!(validate_delegation(&drt, &delpre, &ixn, &source(&ixn, false)).is_err());
+        assert!(validate_delegation(&dip, &delpre, &ixn, &source(&delegator, false)).is_err());
+        assert!(validate_delegation(&dip, &delpre, &ixn, &source(&dip, true)).is_err());
+        let couples = match source(&ixn, false) {
+            Group::SealSourceCouples(couples) => [couples.clone(), couples].concat(),
+            _ => unreachable!(),
+        };
+        let group = Group::SealSourceCouples(couples);
+        assert!(validate_delegation(&dip, &delpre, &ixn, &group).is_err());
+        assert!(
+            validate_delegation(&dip, &delpre, &ixn, &Group::ControllerIdxSigs(vec![])).is_err()
+        );
+
+        // another delegator, or an event that is not delegated
+        let other = dip.pre().unwrap();
+        assert!(validate_delegation(&dip, &other, &ixn, &source(&ixn, false)).is_err());
+        assert!(validate_delegation(&ixn, &delpre, &rot, &source(&rot, false)).is_err());
+    }
+}
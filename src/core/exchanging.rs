@@ -0,0 +1,521 @@
+use indexmap::IndexMap;
+
+use crate::{
+    core::{
+        attachments::Group,
+        bexter::Bext,
+        common::{deversify, versify, Identage, Ids, Ilkage, Serialage, Version, CURRENT_VERSION},
+        creder::Creder,
+        dater::Dater,
+        kever::verify_trans,
+        matter::Matter,
+        parser::{Body, Message},
+        pather::Pather,
+        prefixer::Prefixer,
+        sadder::Sadder,
+        saider::Saider,
+        seqner::Seqner,
+        serder::Serder,
+        siger::Siger,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+fn now() -> Result<String> {
+    Dater::new(None, None, None, None, None, None)?.dts()
+}
+
+// the path of the attachments of the embedded event with `label`
+fn embed_path(label: &str) -> Value {
+    dat!([Ids::e, label])
+}
+
+// rebuilds the body of an embedded event from its ked
+fn embedded(ked: &Value) -> Result<Body> {
+    let vs = deversify(&ked[Ids::v].to_string()?)?;
+    let code = Saider::new_with_qb64(&ked[Ids::d].to_string()?)?.code();
+    Ok(match vs.ident.as_str() {
+        Identage::KERI => Body::Serder(Serder::new_with_ked(ked, Some(&code), Some(&vs.kind))?),
+        Identage::ACDC => Body::Creder(Creder::new_with_ked(ked, Some(&code), Some(&vs.kind))?),
+        ident => return err!(Error::Value(format!("unexpected embedded ident = {ident}"))),
+    })
+}
+
+// whether the said of an embedded event, and a self-addressing prefix it incepts, match its
+// content
+fn verify_embed(body: &Body) -> Result<bool> {
+    match body {
+        Body::Creder(creder) => creder.verify(),
+        Body::Serder(serder) => serder.verify(),
+    }
+}
+
+/// Builds a peer to peer exchange message from `sender` on `route`, returning it with the
+/// attachments of its embedded events.
+///
+/// Each of the `embeds` is placed in the `e` section under its label, after the said of the
+/// section, and its attachments are returned as PathedMaterialQuadlets with path `-e-<label>`
+/// to follow the sender's signatures. `dig` is the said of the prior exchange in the
+/// conversation, and `dt` defaults to now.
+#[allow(clippy::too_many_arguments)]
+pub fn exchange(
+    route: &str,                         // route of the exchange
+    payload: &Value,                     // attributes
+    sender: &str,                        // sender identifier prefix qb64
+    recipient: Option<&str>,             // recipient identifier prefix qb64
+    dig: Option<&str>,                   // said of the prior exchange qb64
+    modifiers: Option<&Value>,           // query modifiers
+    embeds: Option<&[(&str, &Message)]>, // labelled embedded events and their attachments
+    dt: Option<&str>,                    // datetime, ISO 8601
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<(Serder, Vec<Group>)> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+    let embeds = embeds.unwrap_or(&[]);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    Prefixer::new_with_qb64(sender)?;
+    let dt = match dt {
+        Some(dt) => dt.to_string(),
+        None => now()?,
+    };
+
+    let mut attributes = dat!({});
+    if let Some(recipient) = recipient {
+        Prefixer::new_with_qb64(recipient)?;
+        attributes[Ids::i] = dat!(recipient);
+    }
+    for (label, value) in payload.to_map()? {
+        attributes[label.as_str()] = value;
+    }
+
+    let mut e = dat!({});
+    let mut pathed = vec![];
+    if !embeds.is_empty() {
+        e[Ids::d] = dat!("");
+        for (label, message) in embeds {
+            if *label == Ids::d || e.to_map()?.contains_key(*label) {
+                return err!(Error::Value(format!("invalid embed label = {label}")));
+            }
+
+            e[*label] = match &message.body {
+                Body::Serder(serder) => serder.ked(),
+                Body::Creder(creder) => creder.ked(),
+            };
+            if !message.attachments.is_empty() {
+                pathed.push(Group::PathedMaterialQuadlets(
+                    Pather::new_with_path(&embed_path(label))?,
+                    message.attachments.clone(),
+                ));
+            }
+        }
+        e = Saider::saidify(&e, None, None, None, None)?.1;
+    }
+
+    let ked = dat!({
+        "v": vs,
+        "t": Ilkage::exn,
+        "d": "",
+        "i": sender,
+        "rp": recipient.unwrap_or(""),
+        "p": dig.unwrap_or(""),
+        "dt": &dt,
+        "r": route,
+        "q": modifiers.cloned().unwrap_or(dat!({})),
+        "a": attributes,
+        "e": e
+    });
+
+    let (_, ked) = Saider::saidify(&ked, None, Some(kind), None, None)?;
+    Ok((Serder::new(None, None, None, Some(&ked), None)?, pathed))
+}
+
+/// Attaches to `exn` the indexed signatures of its sender, sealed to the sender's
+/// establishment event `said` at `sn`, followed by the attachments of its embedded events.
+pub fn messagize(
+    exn: &Serder,
+    pathed: &[Group],
+    sn: u128,   // sequence number of the sender's establishment event
+    said: &str, // said of the sender's establishment event
+    sigers: &[Siger],
+) -> Result<Message> {
+    let prefixer = Prefixer::new_with_qb64(&exn.pre()?)?;
+    let seqner = Seqner::new_with_sn(sn)?;
+    let saider = Saider::new_with_qb64(said)?;
+
+    let mut attachments =
+        vec![Group::TransIdxSigGroups(vec![(prefixer, seqner, saider, sigers.to_vec())])];
+    attachments.append(&mut pathed.to_vec());
+
+    Ok(Message { body: Body::Serder(exn.clone()), attachments })
+}
+
+/// A parsed exchange message, its sender's signatures and its embedded events with their
+/// attachments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exchange {
+    serder: Serder,
+    signatures: Vec<(Prefixer, Seqner, Saider, Vec<Siger>)>,
+    embeds: IndexMap<String, Message>,
+}
+
+impl Exchange {
+    /// Parses `message`, verifying the said of the exchange, of its embed section and of each
+    /// embedded event.
+    pub fn new(message: &Message) -> Result<Self> {
+        let serder = match &message.body {
+            Body::Serder(serder) => serder.clone(),
+            _ => return err!(Error::Validation("exchange is not a KERI message".to_string())),
+        };
+
+        let ked = serder.ked();
+        let ilk = ked[Ids::t].to_string()?;
+        if ilk != Ilkage::exn {
+            return err!(Error::Validation(format!("unexpected ilk = {ilk} for exchange")));
+        }
+        if !serder.saider().verify(
+            &ked,
+            Some(true),
+            Some(true),
+            Some(&serder.kind()),
+            None,
+            None,
+        )? {
+            return err!(Error::Validation(format!(
+                "invalid exchange said = {said}",
+                said = serder.said()?
+            )));
+        }
+
+        let e = ked[Ids::e].to_map()?;
+        let mut embeds = IndexMap::new();
+        if !e.is_empty() {
+            let said = match e.get(Ids::d) {
+                Some(said) => said.to_string()?,
+                None => return err!(Error::Validation("missing said of embeds".to_string())),
+            };
+            let saider = Saider::new_with_qb64(&said)?;
+            if !saider.verify(&ked[Ids::e], Some(true), Some(false), None, None, None)? {
+                return err!(Error::Validation(format!("invalid said of embeds = {said}")));
+            }
+
+            for (label, value) in e.iter().filter(|(label, _)| *label != Ids::d) {
+                let body = embedded(value)?;
+                if !verify_embed(&body)? {
+                    return err!(Error::Validation(format!("invalid embedded event = {label}")));
+                }
+                embeds.insert(label.clone(), Message { body, attachments: vec![] });
+            }
+        }
+
+        let mut signatures = vec![];
+        for group in &message.attachments {
+            match group {
+                Group::TransIdxSigGroups(groups) => signatures.append(&mut groups.clone()),
+                Group::PathedMaterialQuadlets(pather, groups) => {
+                    let path = pather.path()?.to_vec()?;
+                    let label = match path.as_slice() {
+                        [e, label] if e.to_string()? == Ids::e => label.to_string()?,
+                        _ => {
+                            return err!(Error::Validation(format!(
+                                "unexpected embed path = {path}",
+                                path = pather.bext()?
+                            )))
+                        }
+                    };
+                    match embeds.get_mut(&label) {
+                        Some(embed) => embed.attachments.append(&mut groups.clone()),
+                        None => return err!(Error::Validation(format!("unknown embed = {label}"))),
+                    }
+                }
+                _ => {
+                    return err!(Error::Validation(format!(
+                        "unexpected attachment in exchange: group = {group:?}"
+                    )))
+                }
+            }
+        }
+
+        Ok(Exchange { serder, signatures, embeds })
+    }
+
+    pub fn serder(&self) -> Serder {
+        self.serder.clone()
+    }
+
+    pub fn said(&self) -> Result<String> {
+        self.serder.said()
+    }
+
+    pub fn route(&self) -> Result<String> {
+        self.serder.ked()[Ids::r].to_string()
+    }
+
+    pub fn sender(&self) -> Result<String> {
+        self.serder.pre()
+    }
+
+    pub fn recipient(&self) -> Result<Option<String>> {
+        let recipient = self.serder.ked()[Ids::rp].to_string()?;
+        Ok(if recipient.is_empty() { None } else { Some(recipient) })
+    }
+
+    /// The said of the prior exchange in the conversation, if any.
+    pub fn prior(&self) -> Result<Option<String>> {
+        let dig = self.serder.ked()[Ids::p].to_string()?;
+        Ok(if dig.is_empty() { None } else { Some(dig) })
+    }
+
+    pub fn dt(&self) -> Result<String> {
+        self.serder.ked()[Ids::dt].to_string()
+    }
+
+    pub fn payload(&self) -> Value {
+        self.serder.ked()[Ids::a].clone()
+    }
+
+    pub fn modifiers(&self) -> Value {
+        self.serder.ked()[Ids::q].clone()
+    }
+
+    pub fn signatures(&self) -> Vec<(Prefixer, Seqner, Saider, Vec<Siger>)> {
+        self.signatures.clone()
+    }
+
+    /// The labels of the embedded events, in order.
+    pub fn labels(&self) -> Vec<String> {
+        self.embeds.keys().cloned().collect()
+    }
+
+    /// The embedded event with `label` and the attachments pathed to it.
+    pub fn embed(&self, label: &str) -> Option<Message> {
+        self.embeds.get(label).cloned()
+    }
+
+    /// Verifies that the exchange is signed by its sender, against the keys and threshold of
+    /// the sender's establishment event in `validators` that the signatures are sealed to, which
+    /// must be the sender's latest establishment event there.
+    pub fn verify(&self, validators: &[Serder]) -> Result<()> {
+        let sender = self.sender()?;
+        if self.signatures.is_empty() {
+            return err!(Error::Validation("exchange without signatures".to_string()));
+        }
+
+        for (prefixer, seqner, saider, sigers) in &self.signatures {
+            let pre = prefixer.qb64()?;
+            if pre != sender {
+                return err!(Error::Validation(format!("signature of another sender = {pre}")));
+            }
+
+            verify_trans(&self.serder.raw(), validators, prefixer, seqner, saider, sigers)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{exchange, messagize, Exchange};
+    use crate::core::{
+        attachments::Group,
+        common::Ids,
+        eventing::rotate,
+        fixtures::identifier,
+        matter::Matter,
+        parser::{Body, Message, Parser},
+        proving::credential,
+        sadder::Sadder,
+        saider::Saider,
+        serder::Serder,
+        signer::Signer,
+    };
+
+    const DT: &str = "2023-04-30T00:34:11.853572+00:00";
+    const SCHEMA: &str = "EE5uDJTq5cc6AEdqbyMpvARUjsK_chNdInf3xyRoCBcT";
+
+    fn stream(message: &Message) -> Vec<u8> {
+        let mut ims = message.body.raw();
+        for group in &message.attachments {
+            ims.append(&mut group.qb64b().unwrap());
+        }
+        ims
+    }
+
+    fn signed(signer: &Signer, icp: &Serder, exn: &Serder, pathed: &[Group]) -> Message {
+        let siger = signer.sign_indexed(&exn.raw(), false, 0, None).unwrap();
+        messagize(exn, pathed, 0, &icp.said().unwrap(), &[siger]).unwrap()
+    }
+
+    #[test]
+    fn exchange_embeds() {
+        let (signer, icp) = identifier(1);
+        let (_, other) = identifier(2);
+        let sender = icp.pre().unwrap();
+        let recipient = other.pre().unwrap();
+
+        let (acdc, _) = credential(
+            &sender,
+            SCHEMA,
+            &dat!({ "dt": DT }),
+            Some(&recipient),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let acdc_ked = acdc.ked();
+        let acdc = Message { body: Body::Creder(acdc), attachments: vec![] };
+        let siger = signer.sign_indexed(&icp.raw(), false, 0, None).unwrap();
+        let anc = Message {
+            body: Body::Serder(icp.clone()),
+            attachments: vec![Group::ControllerIdxSigs(vec![siger])],
+        };
+
+        let (exn, pathed) = exchange(
+            "/credential/issue",
+            &dat!({ "m": "hello" }),
+            &sender,
+            Some(&recipient),
+            None,
+            Some(&dat!({ "r": "x" })),
+            Some(&[("acdc", &acdc), ("anc", &anc)]),
+            Some(DT),
+            None,
+            None,
+        )
+        .unwrap();
+
+        let ked = exn.ked();
+        assert_eq!(
+            ked.to_map().unwrap().keys().collect::<Vec<_>>(),
+            vec!["v", "t", "d", "i", "rp", "p", "dt", "r", "q", "a", "e"]
+        );
+        assert_eq!(ked[Ids::a], dat!({ "i": &recipient, "m": "hello" }));
+        assert_eq!(ked[Ids::e]["acdc"], acdc_ked);
+        let (said, _) = Saider::saidify(&ked[Ids::e], None, None, None, None).unwrap();
+        assert_eq!(ked[Ids::e][Ids::d].to_string().unwrap(), said.qb64().unwrap());
+
+        // only the embeds with attachments are pathed
+        assert_eq!(pathed.len(), 1);
+        match &pathed[0] {
+            Group::PathedMaterialQuadlets(pather, groups) => {
+                assert_eq!(pather.path().unwrap(), dat!(["e", "anc"]));
+                assert_eq!(groups, &anc.attachments);
+            }
+            group => panic!("unexpected group = {group:?}"),
+        }
+
+        let message = signed(&signer, &icp, &exn, &pathed);
+        let mut parser = Parser::new_with_ims(&stream(&message));
+        parser.finish();
+        let parsed = parser.parse_one().unwrap();
+        assert_eq!(stream(&parsed), stream(&message));
+
+        let exchange = Exchange::new(&parsed).unwrap();
+        assert_eq!(exchange.serder(), exn);
+        assert_eq!(exchange.said().unwrap(), exn.said().unwrap());
+        assert_eq!(exchange.route().unwrap(), "/credential/issue");
+        assert_eq!(exchange.sender().unwrap(), sender);
+        assert_eq!(exchange.recipient().unwrap(), Some(recipient.clone()));
+        assert_eq!(exchange.prior().unwrap(), None);
+        assert_eq!(exchange.dt().unwrap(), DT);
+        assert_eq!(exchange.payload()["m"], dat!("hello"));
+        assert_eq!(exchange.modifiers(), dat!({ "r": "x" }));
+        assert_eq!(exchange.labels(), vec!["acdc", "anc"]);
+        assert_eq!(exchange.embed("acdc"), Some(acdc));
+        assert_eq!(stream(&exchange.embed("anc").unwrap()), stream(&anc));
+        assert_eq!(exchange.embed("iss"), None);
+        assert_eq!(exchange.signatures().len(), 1);
+        exchange.verify(std::slice::from_ref(&icp)).unwrap();
+
+        // signed by another key, or verified without the sender's establishment event
+        let (impostor, _) = identifier(3);
+        let forged = signed(&impostor, &icp, &exn, &pathed);
+        assert!(Exchange::new(&forged).unwrap().verify(std::slice::from_ref(&icp)).is_err());
+        assert!(exchange.verify(std::slice::from_ref(&other)).is_err());
+        let unsigned = Message { body: Body::Serder(exn.clone()), attachments: vec![] };
+        assert!(Exchange::new(&unsigned).unwrap().verify(std::slice::from_ref(&icp)).is_err());
+
+        // signed with keys that have since been rotated out
+        let rot = rotate(
+            &icp.pre().unwrap(),
+            &[&signer.verfer().qb64().unwrap()],
+            &icp.said().unwrap(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(exchange.verify(&[icp, rot]).is_err());
+    }
+
+    #[test]
+    fn exchange_unhappy_paths() {
+        let (signer, icp) = identifier(1);
+        let sender = icp.pre().unwrap();
+        let anc = Message { body: Body::Serder(icp.clone()), attachments: vec![] };
+
+        let build = |embeds: &[(&str, &Message)]| {
+            exchange("/r", &dat!({}), &sender, None, None, None, Some(embeds), None, None, None)
+        };
+        assert!(build(&[("d", &anc)]).is_err());
+        assert!(build(&[("anc", &anc), ("anc", &anc)]).is_err());
+        assert!(
+            exchange("/r", &dat!([]), &sender, None, None, None, None, None, None, None).is_err()
+        );
+        assert!(exchange("/r", &dat!({}), "", None, None, None, None, None, None, None).is_err());
+
+        let (exn, pathed) = build(&[("anc", &anc)]).unwrap();
+        assert_eq!(exn.ked()[Ids::rp], dat!(""));
+        assert!(Exchange::new(&signed(&signer, &icp, &exn, &pathed)).is_ok());
+
+        // a tampered embed breaks the said of the embeds, and re-saidifying them does not help
+        let mut ked = exn.ked();
+        ked[Ids::e]["anc"]["kt"] = dat!("2");
+        let (_, tampered) = Saider::saidify(&ked, None, None, None, None).unwrap();
+        let tampered = Serder::new_with_ked(&tampered, None, None).unwrap();
+        assert!(Exchange::new(&signed(&signer, &icp, &tampered, &[])).is_err());
+        let (_, e) = Saider::saidify(&ked[Ids::e], None, None, None, None).unwrap();
+        ked[Ids::e] = e;
+        let (_, tampered) = Saider::saidify(&ked, None, None, None, None).unwrap();
+        let tampered = Serder::new_with_ked(&tampered, None, None).unwrap();
+        assert!(Exchange::new(&signed(&signer, &icp, &tampered, &[])).is_err());
+
+        // attachments pathed to an unknown embed or elsewhere, and foreign groups
+        let group = |path: crate::data::Value| {
+            Group::PathedMaterialQuadlets(
+                crate::core::pather::Pather::new_with_path(&path).unwrap(),
+                vec![],
+            )
+        };
+        assert!(Exchange::new(&signed(&signer, &icp, &exn, &[group(dat!(["e", "iss"]))])).is_err());
+        assert!(Exchange::new(&signed(&signer, &icp, &exn, &[group(dat!(["a", "anc"]))])).is_err());
+        let message = Message {
+            body: Body::Serder(exn.clone()),
+            attachments: vec![Group::ControllerIdxSigs(vec![])],
+        };
+        assert!(Exchange::new(&message).is_err());
+
+        // only exchange messages
+        let message = Message { body: Body::Serder(icp), attachments: vec![] };
+        assert!(Exchange::new(&message).is_err());
+    }
+}
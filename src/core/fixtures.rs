@@ -0,0 +1,68 @@
+// key material and identifiers shared by the tests of the event modules. signers are derived
+// from raw seeds `[offset + i; 32]`, so the same offset always gives the same keys.
+
+use crate::core::{
+    diger::Diger,
+    eventing::incept,
+    matter::{tables as matter, Matter},
+    serder::Serder,
+    siger::Siger,
+    signer::Signer,
+};
+
+pub(crate) fn signers(count: u8, offset: u8) -> Vec<Signer> {
+    (0..count).map(|i| Signer::new_with_raw(&[i + offset; 32], Some(true), None).unwrap()).collect()
+}
+
+// non-transferable signers, as witnesses have
+pub(crate) fn witnesses(count: u8, offset: u8) -> Vec<Signer> {
+    (0..count)
+        .map(|i| Signer::new_with_raw(&[i + offset; 32], Some(false), None).unwrap())
+        .collect()
+}
+
+pub(crate) fn keys(signers: &[Signer]) -> Vec<String> {
+    signers.iter().map(|s| s.verfer().qb64().unwrap()).collect()
+}
+
+pub(crate) fn digs(signers: &[Signer]) -> Vec<String> {
+    signers
+        .iter()
+        .map(|s| {
+            Diger::new_with_ser(&s.verfer().qb64b().unwrap(), Some(matter::Codex::Blake3_256))
+                .unwrap()
+                .qb64()
+                .unwrap()
+        })
+        .collect()
+}
+
+// signs with the signers at the given indices, with ondex equal to index
+pub(crate) fn sign(ser: &[u8], signers: &[Signer], indices: &[u32]) -> Vec<Siger> {
+    indices
+        .iter()
+        .map(|&i| signers[i as usize].sign_indexed(ser, false, i, None).unwrap())
+        .collect()
+}
+
+// a single key identifier with a self-addressing prefix and no next keys
+pub(crate) fn identifier(seed: u8) -> (Signer, Serder) {
+    let signer = Signer::new_with_raw(&[seed; 32], Some(true), None).unwrap();
+    let icp = incept(
+        &[&signer.verfer().qb64().unwrap()],
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(matter::Codex::Blake3_256),
+        None,
+        None,
+    )
+    .unwrap();
+    (signer, icp)
+}
@@ -0,0 +1,608 @@
+use crate::{
+    core::{
+        common::{Ids, Ilkage, Version},
+        diger::Diger,
+        eventing,
+        indexer::{tables as indexer, Indexer},
+        kever::Kever,
+        matter::{tables as matter, Matter},
+        sadder::Sadder,
+        serder::Serder,
+        siger::Siger,
+        signer::Signer,
+        tholder::{Evaluation, Tholder},
+        util::strs,
+        verfer::Verfer,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+// the current keys and next key digests of the members, in member order
+fn pooled(members: &[Kever]) -> Result<(Vec<String>, Vec<String>)> {
+    if members.is_empty() {
+        return err!(Error::Value("empty group members".to_string()));
+    }
+
+    let mut pres = vec![];
+    let mut keys = vec![];
+    let mut digs = vec![];
+    for member in members {
+        let pre = member.pre()?;
+        if !member.transferable() {
+            return err!(Error::Value(format!("non-transferable group member = {pre}")));
+        }
+        if pres.contains(&pre) {
+            return err!(Error::Value(format!("duplicate group member = {pre}")));
+        }
+        pres.push(pre);
+
+        for verfer in member.verfers() {
+            keys.push(verfer.qb64()?);
+        }
+        for diger in member.digers() {
+            digs.push(diger.qb64()?);
+        }
+    }
+
+    Ok((keys, digs))
+}
+
+/// Builds the inception event of a group identifier, or a delegated inception event when
+/// `delpre` is given. The group's keys and next key digests are those of its members, in member
+/// order. The prefix is always self-addressing.
+#[allow(clippy::too_many_arguments)]
+pub fn incept(
+    members: &[Kever],      // member key states
+    sith: Option<&Value>,   // current signing threshold
+    nsith: Option<&Value>,  // next signing threshold
+    toad: Option<u128>,     // witness threshold number
+    wits: Option<&[&str]>,  // witness identifier prefixes qb64
+    cnfg: Option<&[&str]>,  // configuration traits from common::Traitage
+    data: Option<&[Value]>, // seal dicts
+    version: Option<&Version>,
+    kind: Option<&str>,
+    intive: Option<bool>,
+    delpre: Option<&str>, // delegator identifier prefix
+) -> Result<Serder> {
+    let (keys, digs) = pooled(members)?;
+    eventing::incept(
+        &strs(&keys),
+        sith,
+        Some(&strs(&digs)),
+        nsith,
+        toad,
+        wits,
+        cnfg,
+        data,
+        version,
+        kind,
+        Some(matter::Codex::Blake3_256),
+        intive,
+        delpre,
+    )
+}
+
+/// Builds the next rotation event of a group identifier from its key state `group` and the key
+/// states of its members after they have rotated. The members' current keys must expose enough
+/// of the group's prior next key digests to satisfy its prior next threshold.
+#[allow(clippy::too_many_arguments)]
+pub fn rotate(
+    group: &Kever,          // group key state
+    members: &[Kever],      // member key states
+    sith: Option<&Value>,   // current signing threshold
+    nsith: Option<&Value>,  // next signing threshold
+    toad: Option<u128>,     // witness threshold number after cuts and adds
+    cuts: Option<&[&str]>,  // witness prefixes to remove
+    adds: Option<&[&str]>,  // witness prefixes to add
+    data: Option<&[Value]>, // seal dicts
+    version: Option<&Version>,
+    kind: Option<&str>,
+    intive: Option<bool>,
+) -> Result<Serder> {
+    let (keys, digs) = pooled(members)?;
+    let ilk = if group.delpre().is_some() { Ilkage::drt } else { Ilkage::rot };
+    let wits = group.wits();
+    eventing::rotate(
+        &group.pre()?,
+        &strs(&keys),
+        &group.serder().said()?,
+        Some(ilk),
+        Some(group.sn()? + 1),
+        sith,
+        Some(&strs(&digs)),
+        nsith,
+        toad,
+        Some(&strs(&wits)),
+        cuts,
+        adds,
+        data,
+        version,
+        kind,
+        intive,
+    )
+}
+
+/// Signs a group establishment event with one member's signers.
+///
+/// Each signature is indexed by the position of the signer's key in the event's keys. For a
+/// rotation, `prior` are the group's prior next key digests: the ondex is the position of the
+/// digest committing to the signer's key, and keys without such a commitment sign as current
+/// only.
+pub fn sign(serder: &Serder, signers: &[Signer], prior: Option<&[Diger]>) -> Result<Vec<Siger>> {
+    let ser = serder.raw();
+    let keys = serder.verfers()?.iter().map(|verfer| verfer.qb64()).collect::<Result<Vec<_>>>()?;
+
+    let mut sigers = vec![];
+    for signer in signers {
+        let verfer = signer.verfer();
+        let key = verfer.qb64()?;
+        let index = match keys.iter().position(|k| *k == key) {
+            Some(index) => index as u32,
+            None => return err!(Error::Value(format!("signer key = {key} not in group keys"))),
+        };
+
+        let siger = match prior {
+            Some(digers) => match committed(&verfer, digers)? {
+                Some(ondex) => signer.sign_indexed(&ser, false, index, Some(ondex))?,
+                None => signer.sign_indexed(&ser, true, index, None)?,
+            },
+            None => signer.sign_indexed(&ser, false, index, None)?,
+        };
+        sigers.push(siger);
+    }
+
+    Ok(sigers)
+}
+
+// the position of the digest in `digers` that commits to the key of `verfer`
+fn committed(verfer: &Verfer, digers: &[Diger]) -> Result<Option<u32>> {
+    let ser = verfer.qb64b()?;
+    for (ondex, diger) in digers.iter().enumerate() {
+        if diger.verify(&ser)? {
+            return Ok(Some(ondex as u32));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Collects the signatures of group members on a group establishment event and reports when
+/// the event's current signing threshold and, for rotations, the group's prior next threshold
+/// are satisfied.
+///
+/// Every signature is verified as it is added. A signature at an index that already holds one
+/// with a different ondex is a conflict, and the whole batch is rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregator {
+    serder: Serder,
+    tholder: Tholder,
+    verfers: Vec<Verfer>,
+    ntholder: Option<Tholder>, // prior next threshold
+    digers: Vec<Diger>,        // prior next key digests
+    sigers: Vec<Siger>,
+}
+
+impl Aggregator {
+    /// `group` is the key state of the group before the event, required for rotations.
+    pub fn new(serder: &Serder, group: Option<&Kever>) -> Result<Self> {
+        let label = Ids::t;
+        let ilk = serder.ked()[label].to_string()?;
+        let (ntholder, digers) = match (ilk.as_str(), group) {
+            (Ilkage::icp | Ilkage::dip, None) => (None, vec![]),
+            (Ilkage::rot | Ilkage::drt, Some(group)) => {
+                if serder.pre()? != group.pre()? {
+                    return err!(Error::Value(format!(
+                        "mismatched event prefix = {pre}, expected {expected}",
+                        pre = serder.pre()?,
+                        expected = group.pre()?
+                    )));
+                }
+                (Some(group.ntholder()), group.digers())
+            }
+            (Ilkage::rot | Ilkage::drt, None) => {
+                return err!(Error::Value(format!("missing group key state for ilk = {ilk}")))
+            }
+            (Ilkage::icp | Ilkage::dip, Some(_)) => {
+                return err!(Error::Value(format!("unexpected group key state for ilk = {ilk}")))
+            }
+            _ => return err!(Error::Value(format!("unexpected ilk = {ilk} for group event"))),
+        };
+
+        let tholder = match serder.tholder()? {
+            Some(tholder) => tholder,
+            None => return err!(Error::Value("missing signing threshold".to_string())),
+        };
+
+        Ok(Aggregator {
+            serder: serder.clone(),
+            tholder,
+            verfers: serder.verfers()?,
+            ntholder,
+            digers,
+            sigers: vec![],
+        })
+    }
+
+    /// Verifies and merges `sigers`, returning the number of new signatures. Repeated
+    /// signatures are ignored. Nothing is merged when any signature is invalid or conflicts.
+    pub fn add(&mut self, sigers: &[Siger]) -> Result<usize> {
+        let ser = self.serder.raw();
+        let mut merged = self.sigers.clone();
+        let mut added = 0;
+
+        for siger in sigers {
+            let index = siger.index();
+            let verfer = match self.verfers.get(index as usize) {
+                Some(verfer) => verfer,
+                None => {
+                    return err!(Error::Validation(format!(
+                        "signature index = {index} out of range for {n} keys",
+                        n = self.verfers.len()
+                    )))
+                }
+            };
+
+            if !verfer.verify(&siger.raw(), &ser)? {
+                return err!(Error::Validation(format!("invalid signature at index = {index}")));
+            }
+
+            let ondex = self.ondex(siger, verfer)?;
+            match merged.iter().find(|other| other.index() == index) {
+                Some(other) if self.ondex(other, verfer)? == ondex => continue,
+                Some(_) => {
+                    return err!(Error::Validation(format!(
+                        "conflicting signatures at index = {index}"
+                    )))
+                }
+                None => {
+                    merged.push(siger.clone());
+                    added += 1;
+                }
+            }
+        }
+
+        merged.sort_by_key(|siger| siger.index());
+        self.sigers = merged;
+        Ok(added)
+    }
+
+    // the validated prior next ondex of a rotation signature, if it claims one
+    fn ondex(&self, siger: &Siger, verfer: &Verfer) -> Result<Option<u32>> {
+        if self.ntholder.is_none() || indexer::CurrentSigCodex::has_code(&siger.code()) {
+            return Ok(None);
+        }
+
+        let ondex = siger.ondex();
+        match self.digers.get(ondex as usize) {
+            Some(diger) if diger.verify(&verfer.qb64b()?)? => Ok(Some(ondex)),
+            _ => err!(Error::Validation(format!(
+                "invalid prior next ondex = {ondex} for signature at index = {index}",
+                index = siger.index()
+            ))),
+        }
+    }
+
+    pub fn serder(&self) -> Serder {
+        self.serder.clone()
+    }
+
+    /// The merged signatures, ordered by index.
+    pub fn sigers(&self) -> Vec<Siger> {
+        self.sigers.clone()
+    }
+
+    /// Evaluates the merged signatures against the event's current signing threshold.
+    pub fn evaluate(&self) -> Result<Evaluation> {
+        let indices = self.sigers.iter().map(|siger| siger.index()).collect::<Vec<_>>();
+        self.tholder.evaluate(&indices)
+    }
+
+    /// Evaluates the merged signatures against the group's prior next threshold. None for
+    /// inceptions.
+    pub fn evaluate_prior(&self) -> Result<Option<Evaluation>> {
+        let ntholder = match &self.ntholder {
+            Some(ntholder) => ntholder,
+            None => return Ok(None),
+        };
+
+        let mut ondices = vec![];
+        for siger in &self.sigers {
+            if let Some(ondex) = self.ondex(siger, &self.verfers[siger.index() as usize])? {
+                ondices.push(ondex);
+            }
+        }
+
+        Ok(Some(ntholder.evaluate(&ondices)?))
+    }
+
+    /// Whether every applicable threshold is satisfied.
+    pub fn complete(&self) -> Result<bool> {
+        let prior = match self.evaluate_prior()? {
+            Some(evaluation) => evaluation.satisfied,
+            None => true,
+        };
+
+        Ok(prior && self.evaluate()?.satisfied)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{incept, rotate, sign, Aggregator};
+    use crate::core::{
+        eventing,
+        fixtures::{digs, keys, signers},
+        indexer::Indexer,
+        kever::Kever,
+        matter::{tables as matter, Matter},
+        sadder::Sadder,
+        serder::Serder,
+        signer::Signer,
+        util::strs,
+    };
+
+    // a member whose current signers all sign each of its establishment events
+    struct Member {
+        offset: u8,
+        signers: Vec<Signer>,
+        nsigners: Vec<Signer>,
+        kever: Kever,
+    }
+
+    impl Member {
+        fn new(n: u8, offset: u8) -> Self {
+            let (signers, nsigners) = (signers(n, offset), signers(n, offset + 100));
+            let icp = eventing::incept(
+                &strs(&keys(&signers)),
+                Some(&dat!(n)),
+                Some(&strs(&digs(&nsigners))),
+                Some(&dat!(n)),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(matter::Codex::Blake3_256),
+                None,
+                None,
+            )
+            .unwrap();
+            let kever = Kever::new(&icp, &sign(&icp, &signers, None).unwrap()).unwrap();
+            Member { offset, signers, nsigners, kever }
+        }
+
+        // rotates once to the committed next signers
+        fn rotate(&mut self) {
+            let n = self.signers.len() as u8;
+            let signers = std::mem::replace(&mut self.nsigners, signers(n, self.offset + 150));
+            let rot = eventing::rotate(
+                &self.kever.pre().unwrap(),
+                &strs(&keys(&signers)),
+                &self.kever.serder().said().unwrap(),
+                None,
+                Some(self.kever.sn().unwrap() + 1),
+                Some(&dat!(n)),
+                Some(&strs(&digs(&self.nsigners))),
+                Some(&dat!(n)),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+            let digers = self.kever.digers();
+            self.kever.update(&rot, &sign(&rot, &signers, Some(&digers)).unwrap()).unwrap();
+            self.signers = signers;
+        }
+    }
+
+    fn kevers(members: &[Member]) -> Vec<Kever> {
+        members.iter().map(|member| member.kever.clone()).collect()
+    }
+
+    fn group_inception(members: &[Member]) -> Serder {
+        incept(
+            &kevers(members),
+            Some(&dat!(["1/2", "1/2", "1/2"])),
+            Some(&dat!(["1/2", "1/2", "1/2"])),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn group_inception_aggregation() {
+        let members = vec![Member::new(1, 0), Member::new(1, 10), Member::new(1, 20)];
+        let icp = group_inception(&members);
+
+        assert!(icp.pre().unwrap().starts_with('E'));
+        let expected: Vec<String> = members.iter().flat_map(|m| keys(&m.signers)).collect();
+        let actual: Vec<String> =
+            icp.verfers().unwrap().iter().map(|v| v.qb64().unwrap()).collect();
+        assert_eq!(actual, expected);
+        let expected: Vec<String> = members.iter().flat_map(|m| digs(&m.nsigners)).collect();
+        let actual: Vec<String> = icp.digers().unwrap().iter().map(|d| d.qb64().unwrap()).collect();
+        assert_eq!(actual, expected);
+
+        let mut aggregator = Aggregator::new(&icp, None).unwrap();
+        assert!(aggregator.evaluate_prior().unwrap().is_none());
+
+        let sigers = sign(&icp, &members[2].signers, None).unwrap();
+        assert_eq!(sigers[0].index(), 2);
+        assert_eq!(aggregator.add(&sigers).unwrap(), 1);
+        assert_eq!(aggregator.add(&sigers).unwrap(), 0);
+        assert!(!aggregator.complete().unwrap());
+        assert_eq!(aggregator.evaluate().unwrap().needed, vec![0]);
+
+        let sigers = sign(&icp, &members[0].signers, None).unwrap();
+        assert_eq!(aggregator.add(&sigers).unwrap(), 1);
+        assert!(aggregator.complete().unwrap());
+        let indices: Vec<u32> = aggregator.sigers().iter().map(|s| s.index()).collect();
+        assert_eq!(indices, vec![0, 2]);
+
+        let kever = Kever::new(&icp, &aggregator.sigers()).unwrap();
+        assert_eq!(kever.pre().unwrap(), icp.pre().unwrap());
+    }
+
+    #[test]
+    fn group_rotation_aggregation() {
+        let mut members = vec![Member::new(1, 0), Member::new(2, 10), Member::new(1, 20)];
+        let icp = incept(
+            &kevers(&members),
+            Some(&dat!(2)),
+            Some(&dat!(3)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(icp.verfers().unwrap().len(), 4);
+
+        let mut aggregator = Aggregator::new(&icp, None).unwrap();
+        for member in &members {
+            aggregator.add(&sign(&icp, &member.signers, None).unwrap()).unwrap();
+        }
+        let mut group = Kever::new(&icp, &aggregator.sigers()).unwrap();
+
+        // the first two members rotate, the third keeps its committed next key unexposed
+        members[0].rotate();
+        members[1].rotate();
+        let rot = rotate(
+            &group,
+            &kevers(&members[..2]),
+            Some(&dat!(2)),
+            Some(&dat!(2)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(rot.pre().unwrap(), group.pre().unwrap());
+        assert_eq!(rot.sn().unwrap(), 1);
+
+        let prior = group.digers();
+        let mut aggregator = Aggregator::new(&rot, Some(&group)).unwrap();
+        let sigers = sign(&rot, &members[1].signers, Some(&prior)).unwrap();
+        assert_eq!(
+            sigers.iter().map(|s| (s.index(), s.ondex())).collect::<Vec<_>>(),
+            vec![(1, 1), (2, 2)]
+        );
+        assert_eq!(aggregator.add(&sigers).unwrap(), 2);
+        assert!(aggregator.evaluate().unwrap().satisfied);
+        let evaluation = aggregator.evaluate_prior().unwrap().unwrap();
+        assert!(!evaluation.satisfied);
+        assert_eq!(evaluation.counted, vec![1, 2]);
+        assert_eq!(evaluation.needed, vec![0]);
+        assert!(!aggregator.complete().unwrap());
+
+        aggregator.add(&sign(&rot, &members[0].signers, Some(&prior)).unwrap()).unwrap();
+        assert!(aggregator.complete().unwrap());
+
+        group.update(&rot, &aggregator.sigers()).unwrap();
+        assert_eq!(group.sn().unwrap(), 1);
+        assert_eq!(group.verfers(), rot.verfers().unwrap());
+    }
+
+    #[test]
+    fn group_unhappy_paths() {
+        let members = vec![Member::new(1, 0), Member::new(1, 10)];
+        assert!(incept(&[], None, None, None, None, None, None, None, None, None, None).is_err());
+        let twice = vec![members[0].kever.clone(), members[0].kever.clone()];
+        assert!(incept(&twice, None, None, None, None, None, None, None, None, None, None).is_err());
+
+        let icp = incept(
+            &kevers(&members),
+            Some(&dat!(2)),
+            Some(&dat!(2)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let outsider = signers(1, 50);
+        assert!(sign(&icp, &outsider, None).is_err());
+
+        let group = Kever::new(
+            &icp,
+            &[
+                sign(&icp, &members[0].signers, None).unwrap(),
+                sign(&icp, &members[1].signers, None).unwrap(),
+            ]
+            .concat(),
+        )
+        .unwrap();
+        assert!(Aggregator::new(&icp, Some(&group)).is_err());
+
+        let ixn =
+            eventing::interact(&group.pre().unwrap(), &icp.said().unwrap(), None, None, None, None)
+                .unwrap();
+        assert!(Aggregator::new(&ixn, None).is_err());
+
+        let mut aggregator = Aggregator::new(&icp, None).unwrap();
+        // out of range index
+        let siger = outsider[0].sign_indexed(&icp.raw(), false, 2, None).unwrap();
+        assert!(aggregator.add(&[siger]).is_err());
+        // signature by the wrong key, the valid signature in the batch is not merged
+        let valid = sign(&icp, &members[0].signers, None).unwrap();
+        let forged = outsider[0].sign_indexed(&icp.raw(), false, 1, None).unwrap();
+        assert!(aggregator.add(&[valid[0].clone(), forged]).is_err());
+        assert!(aggregator.sigers().is_empty());
+
+        // a rotation signature claiming the wrong prior next commitment, then a conflicting ondex
+        let mut members = members;
+        members[0].rotate();
+        members[1].rotate();
+        let rot = rotate(
+            &group,
+            &kevers(&members),
+            Some(&dat!(2)),
+            Some(&dat!(2)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(Aggregator::new(&rot, None).is_err());
+
+        let mut aggregator = Aggregator::new(&rot, Some(&group)).unwrap();
+        let wrong = members[0].signers[0].sign_indexed(&rot.raw(), false, 0, Some(1)).unwrap();
+        assert!(aggregator.add(&[wrong]).is_err());
+
+        let current = members[0].signers[0].sign_indexed(&rot.raw(), true, 0, None).unwrap();
+        assert_eq!(aggregator.add(&[current]).unwrap(), 1);
+        let sigers = sign(&rot, &members[0].signers, Some(&group.digers())).unwrap();
+        assert!(aggregator.add(&sigers).is_err());
+        assert_eq!(aggregator.sigers().len(), 1);
+    }
+}
@@ -0,0 +1,527 @@
+use crate::{
+    core::{
+        attachments::Group,
+        common::{Ids, Ilkage, Version},
+        creder::Creder,
+        exchanging::{exchange, Exchange},
+        parser::{Body, Message},
+        sadder::Sadder,
+        saider::Saider,
+        serder::Serder,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+pub mod Routage {
+    pub const apply: &str = "/ipex/apply";
+    pub const offer: &str = "/ipex/offer";
+    pub const agree: &str = "/ipex/agree";
+    pub const grant: &str = "/ipex/grant";
+    pub const admit: &str = "/ipex/admit";
+}
+
+// the label of the embedded credential
+const ACDC: &str = "acdc";
+
+// the routes of the exchanges that an exchange on `route` may respond to
+fn responds(route: &str) -> &'static [&'static str] {
+    match route {
+        Routage::offer => &[Routage::apply],
+        Routage::agree => &[Routage::offer],
+        Routage::grant => &[Routage::agree],
+        Routage::admit => &[Routage::grant],
+        _ => &[],
+    }
+}
+
+// the said of `prior`, which must be an exchange that `route` responds to
+fn prior(route: &str, prior: Option<&Serder>) -> Result<Option<String>> {
+    let prior = match prior {
+        Some(prior) => prior,
+        None => return Ok(None),
+    };
+
+    let ked = prior.ked();
+    let ilk = ked[Ids::t].to_string()?;
+    let responded = if ilk == Ilkage::exn { ked[Ids::r].to_string()? } else { ilk };
+    if !responds(route).contains(&responded.as_str()) {
+        return err!(Error::Validation(format!(
+            "invalid prior exchange = {responded} for route = {route}"
+        )));
+    }
+
+    Ok(Some(prior.said()?))
+}
+
+fn credential_message(acdc: &Message) -> Result<()> {
+    match acdc.body {
+        Body::Creder(_) => Ok(()),
+        _ => err!(Error::Value("embedded credential is not an ACDC".to_string())),
+    }
+}
+
+/// Builds an apply exchange, asking `recipient` to offer a credential under schema `schema`
+/// with `attributes`.
+#[allow(clippy::too_many_arguments)]
+pub fn apply(
+    sender: &str,       // applicant identifier prefix qb64
+    recipient: &str,    // issuer identifier prefix qb64
+    message: &str,      // human readable message
+    schema: &str,       // schema said qb64
+    attributes: &Value, // requested attribute values
+    dt: Option<&str>,   // datetime, ISO 8601
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<(Serder, Vec<Group>)> {
+    Saider::new_with_qb64(schema)?;
+    attributes.to_map()?;
+
+    let payload = dat!({ "m": message, "s": schema, "a": attributes.clone() });
+    exchange(Routage::apply, &payload, sender, Some(recipient), None, None, None, dt, version, kind)
+}
+
+/// Builds an offer exchange of the credential `acdc`, optionally in response to an apply
+/// exchange.
+#[allow(clippy::too_many_arguments)]
+pub fn offer(
+    sender: &str,           // issuer identifier prefix qb64
+    recipient: &str,        // applicant identifier prefix qb64
+    message: &str,          // human readable message
+    acdc: &Message,         // credential, usually in its most compact form
+    apply: Option<&Serder>, // apply exchange responded to
+    dt: Option<&str>,       // datetime, ISO 8601
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<(Serder, Vec<Group>)> {
+    credential_message(acdc)?;
+    let dig = prior(Routage::offer, apply)?;
+
+    exchange(
+        Routage::offer,
+        &dat!({ "m": message }),
+        sender,
+        Some(recipient),
+        dig.as_deref(),
+        None,
+        Some(&[(ACDC, acdc)]),
+        dt,
+        version,
+        kind,
+    )
+}
+
+/// Builds an agree exchange, accepting an offer exchange.
+pub fn agree(
+    sender: &str,     // applicant identifier prefix qb64
+    recipient: &str,  // issuer identifier prefix qb64
+    message: &str,    // human readable message
+    offer: &Serder,   // offer exchange agreed to
+    dt: Option<&str>, // datetime, ISO 8601
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<(Serder, Vec<Group>)> {
+    let dig = prior(Routage::agree, Some(offer))?;
+
+    exchange(
+        Routage::agree,
+        &dat!({ "m": message }),
+        sender,
+        Some(recipient),
+        dig.as_deref(),
+        None,
+        None,
+        dt,
+        version,
+        kind,
+    )
+}
+
+/// Builds a grant exchange of the credential `acdc`, with the registry event `iss` that issued
+/// it and the key event `anc` that anchored the issuance, optionally in response to an agree
+/// exchange. Each embedded event carries its own attachments.
+#[allow(clippy::too_many_arguments)]
+pub fn grant(
+    sender: &str,           // issuer identifier prefix qb64
+    recipient: &str,        // issuee identifier prefix qb64
+    message: &str,          // human readable message
+    acdc: &Message,         // credential and its attachments
+    iss: Option<&Message>,  // issuance event and its attachments
+    anc: Option<&Message>,  // anchoring key event and its attachments
+    agree: Option<&Serder>, // agree exchange responded to
+    dt: Option<&str>,       // datetime, ISO 8601
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<(Serder, Vec<Group>)> {
+    credential_message(acdc)?;
+    let dig = prior(Routage::grant, agree)?;
+
+    let mut embeds = vec![(ACDC, acdc)];
+    if let Some(iss) = iss {
+        embeds.push((Ilkage::iss, iss));
+    }
+    if let Some(anc) = anc {
+        embeds.push(("anc", anc));
+    }
+
+    exchange(
+        Routage::grant,
+        &dat!({ "m": message }),
+        sender,
+        Some(recipient),
+        dig.as_deref(),
+        None,
+        Some(&embeds),
+        dt,
+        version,
+        kind,
+    )
+}
+
+/// Builds an admit exchange, accepting a grant exchange.
+pub fn admit(
+    sender: &str,     // issuee identifier prefix qb64
+    recipient: &str,  // issuer identifier prefix qb64
+    message: &str,    // human readable message
+    grant: &Serder,   // grant exchange admitted
+    dt: Option<&str>, // datetime, ISO 8601
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<(Serder, Vec<Group>)> {
+    let dig = prior(Routage::admit, Some(grant))?;
+
+    exchange(
+        Routage::admit,
+        &dat!({ "m": message }),
+        sender,
+        Some(recipient),
+        dig.as_deref(),
+        None,
+        None,
+        dt,
+        version,
+        kind,
+    )
+}
+
+/// Validates a parsed IPEX exchange: its route, the prior exchange it responds to when given,
+/// and the credential that offers and grants must embed.
+pub fn validate(exchange: &Exchange, prior: Option<&Serder>) -> Result<()> {
+    let route = exchange.route()?;
+    match route.as_str() {
+        Routage::apply => {
+            let payload = exchange.payload().to_map()?;
+            match payload.get(Ids::s) {
+                Some(schema) => Saider::new_with_qb64(&schema.to_string()?)?,
+                None => return err!(Error::Validation("missing schema in apply".to_string())),
+            };
+        }
+        Routage::offer | Routage::grant => {
+            credential(exchange)?;
+        }
+        Routage::agree | Routage::admit => {
+            if exchange.prior()?.is_none() {
+                return err!(Error::Validation(format!("missing prior exchange for {route}")));
+            }
+        }
+        _ => return err!(Error::Validation(format!("unexpected ipex route = {route}"))),
+    }
+
+    if let Some(serder) = prior {
+        let dig = self::prior(&route, Some(serder))?;
+        if exchange.prior()? != dig {
+            return err!(Error::Validation(format!(
+                "exchange does not respond to prior = {said}",
+                said = serder.said()?
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// The credential embedded in an offer or grant exchange.
+pub fn credential(exchange: &Exchange) -> Result<Creder> {
+    match exchange.embed(ACDC) {
+        Some(Message { body: Body::Creder(creder), .. }) => Ok(creder),
+        Some(_) => err!(Error::Validation("embedded credential is not an ACDC".to_string())),
+        None => err!(Error::Validation("missing embedded credential".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{admit, agree, apply, credential, grant, offer, validate, Routage};
+    use crate::core::{
+        attachments::Group,
+        common::{Ids, Traitage},
+        eventing::interact,
+        exchanging::{messagize, Exchange},
+        fixtures::identifier,
+        parser::{Body, Message, Parser},
+        proving,
+        sadder::Sadder,
+        seal::SealEvent,
+        serder::Serder,
+        signer::Signer,
+        tel,
+    };
+
+    const DT: &str = "2023-04-30T00:34:11.853572+00:00";
+    const SCHEMA: &str = "EE5uDJTq5cc6AEdqbyMpvARUjsK_chNdInf3xyRoCBcT";
+
+    struct Party {
+        signer: Signer,
+        icp: Serder,
+    }
+
+    impl Party {
+        fn new(seed: u8) -> Self {
+            let (signer, icp) = identifier(seed);
+            Party { signer, icp }
+        }
+
+        fn pre(&self) -> String {
+            self.icp.pre().unwrap()
+        }
+
+        // signs and sends `exn`, returning the exchange as parsed by the receiver
+        fn send(&self, (exn, pathed): (Serder, Vec<Group>)) -> Exchange {
+            let siger = self.signer.sign_indexed(&exn.raw(), false, 0, None).unwrap();
+            let message = messagize(&exn, &pathed, 0, &self.icp.said().unwrap(), &[siger]).unwrap();
+
+            let mut ims = message.body.raw();
+            for group in &message.attachments {
+                ims.append(&mut group.qb64b().unwrap());
+            }
+            let mut parser = Parser::new_with_ims(&ims);
+            parser.finish();
+            let exchange = Exchange::new(&parser.parse_one().unwrap()).unwrap();
+            exchange.verify(std::slice::from_ref(&self.icp)).unwrap();
+            exchange
+        }
+    }
+
+    #[test]
+    fn issuance() {
+        let issuer = Party::new(1);
+        let holder = Party::new(2);
+
+        let apl = holder.send(
+            apply(
+                &holder.pre(),
+                &issuer.pre(),
+                "please",
+                SCHEMA,
+                &dat!({ "LEI": "254900OPPU84GM83MG36" }),
+                Some(DT),
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+        validate(&apl, None).unwrap();
+        assert_eq!(apl.route().unwrap(), Routage::apply);
+        assert_eq!(
+            apl.payload(),
+            dat!({
+                "i": &issuer.pre(),
+                "m": "please",
+                "s": SCHEMA,
+                "a": { "LEI": "254900OPPU84GM83MG36" }
+            })
+        );
+
+        // the issuer offers the compact form of the credential
+        let vcp =
+            tel::incept(&issuer.pre(), None, None, None, Some(&[Traitage::NB]), None, None, None)
+                .unwrap();
+        let (acdc, compact) = proving::credential(
+            &issuer.pre(),
+            SCHEMA,
+            &dat!({ "LEI": "254900OPPU84GM83MG36" }),
+            Some(&holder.pre()),
+            Some(&vcp.pre().unwrap()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let message = |body: Body| Message { body, attachments: vec![] };
+        let ofr = issuer.send(
+            offer(
+                &issuer.pre(),
+                &holder.pre(),
+                "here",
+                &message(Body::Creder(compact.clone())),
+                Some(&apl.serder()),
+                Some(DT),
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+        validate(&ofr, Some(&apl.serder())).unwrap();
+        assert_eq!(ofr.prior().unwrap(), Some(apl.said().unwrap()));
+        assert_eq!(credential(&ofr).unwrap(), compact);
+
+        let agr = holder.send(
+            agree(&holder.pre(), &issuer.pre(), "ok", &ofr.serder(), Some(DT), None, None).unwrap(),
+        );
+        validate(&agr, Some(&ofr.serder())).unwrap();
+        assert_eq!(agr.labels(), Vec::<String>::new());
+
+        // the issuer grants the full credential with its issuance and the anchoring event
+        let iss =
+            tel::issue(&acdc.said().unwrap(), &vcp.pre().unwrap(), Some(DT), None, None).unwrap();
+        let seal = SealEvent::new_with_serder(&iss).unwrap().value();
+        let ixn = interact(
+            &issuer.pre(),
+            &issuer.icp.said().unwrap(),
+            Some(1),
+            Some(&[seal]),
+            None,
+            None,
+        )
+        .unwrap();
+        let siger = issuer.signer.sign_indexed(&ixn.raw(), false, 0, None).unwrap();
+        let anc = Message {
+            body: Body::Serder(ixn.clone()),
+            attachments: vec![Group::ControllerIdxSigs(vec![siger])],
+        };
+        let grt = issuer.send(
+            grant(
+                &issuer.pre(),
+                &holder.pre(),
+                "granted",
+                &message(Body::Creder(acdc.clone())),
+                Some(&message(Body::Serder(iss.clone()))),
+                Some(&anc),
+                Some(&agr.serder()),
+                Some(DT),
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+        validate(&grt, Some(&agr.serder())).unwrap();
+        assert_eq!(grt.labels(), vec!["acdc", "iss", "anc"]);
+        assert_eq!(credential(&grt).unwrap(), acdc);
+        assert_eq!(grt.serder().ked()[Ids::e]["iss"], iss.ked());
+        match grt.embed("anc").unwrap() {
+            Message { body: Body::Serder(serder), attachments } => {
+                assert_eq!(serder, ixn);
+                assert_eq!(attachments.len(), 1);
+            }
+            embed => panic!("unexpected embed = {embed:?}"),
+        }
+
+        let adm = holder.send(
+            admit(&holder.pre(), &issuer.pre(), "thanks", &grt.serder(), Some(DT), None, None)
+                .unwrap(),
+        );
+        validate(&adm, Some(&grt.serder())).unwrap();
+        assert_eq!(adm.prior().unwrap(), Some(grt.said().unwrap()));
+        assert_eq!(adm.recipient().unwrap(), Some(issuer.pre()));
+
+        // a grant need not respond to an agreement
+        let unsolicited = issuer.send(
+            grant(
+                &issuer.pre(),
+                &holder.pre(),
+                "granted",
+                &message(Body::Creder(acdc)),
+                None,
+                None,
+                None,
+                Some(DT),
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+        validate(&unsolicited, None).unwrap();
+        assert_eq!(unsolicited.labels(), vec!["acdc"]);
+
+        // responses out of order
+        assert!(agree(&holder.pre(), &issuer.pre(), "ok", &apl.serder(), None, None, None).is_err());
+        assert!(admit(&holder.pre(), &issuer.pre(), "ok", &ofr.serder(), None, None, None).is_err());
+        assert!(admit(&holder.pre(), &issuer.pre(), "ok", &iss, None, None, None).is_err());
+        assert!(validate(&adm, Some(&ofr.serder())).is_err());
+        assert!(validate(&agr, Some(&grt.serder())).is_err());
+    }
+
+    #[test]
+    fn ipex_unhappy_paths() {
+        let issuer = Party::new(1);
+        let holder = Party::new(2);
+        let not_acdc = Message { body: Body::Serder(issuer.icp.clone()), attachments: vec![] };
+
+        assert!(apply(&holder.pre(), &issuer.pre(), "", "", &dat!({}), None, None, None).is_err());
+        assert!(
+            apply(&holder.pre(), &issuer.pre(), "", SCHEMA, &dat!([]), None, None, None).is_err()
+        );
+        assert!(offer(&issuer.pre(), &holder.pre(), "", &not_acdc, None, None, None, None).is_err());
+        assert!(grant(
+            &issuer.pre(),
+            &holder.pre(),
+            "",
+            &not_acdc,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err());
+
+        // exchanges on other routes, or missing what their route requires
+        let other = holder.send(
+            crate::core::exchanging::exchange(
+                "/credential/other",
+                &dat!({}),
+                &holder.pre(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+        assert!(validate(&other, None).is_err());
+
+        let bare = |route: &str| {
+            holder.send(
+                crate::core::exchanging::exchange(
+                    route,
+                    &dat!({}),
+                    &holder.pre(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap(),
+            )
+        };
+        assert!(validate(&bare(Routage::apply), None).is_err());
+        assert!(validate(&bare(Routage::offer), None).is_err());
+        assert!(validate(&bare(Routage::grant), None).is_err());
+        assert!(validate(&bare(Routage::agree), None).is_err());
+        assert!(validate(&bare(Routage::admit), None).is_err());
+        assert!(credential(&bare(Routage::grant)).is_err());
+    }
+}
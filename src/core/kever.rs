@@ -0,0 +1,824 @@
+use crate::{
+    core::{
+        common::{Ids, Ilkage, Traitage},
+        diger::Diger,
+        eventing::{rotate_wits, validate_toad, witnessed},
+        indexer::{tables as indexer, Indexer},
+        matter::Matter,
+        number::Number,
+        prefixer::Prefixer,
+        sadder::Sadder,
+        saider::Saider,
+        seqner::Seqner,
+        serder::Serder,
+        siger::Siger,
+        tholder::Tholder,
+        util::strs,
+        verfer::Verfer,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+/// The sequence number and said of the latest establishment event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LastEst {
+    pub s: u128,
+    pub d: String,
+}
+
+/// Key state for a single identifier, built by validating its key event log one event at a
+/// time.
+///
+/// Events must arrive in order. Escrowing of out of order events, recovery rotations that
+/// supersede accepted events and delegator approval are left to the caller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Kever {
+    prefixer: Prefixer,
+    sner: Number,
+    serder: Serder,
+    ilk: String,
+    tholder: Tholder,
+    verfers: Vec<Verfer>,
+    ntholder: Tholder,
+    digers: Vec<Diger>,
+    toader: Number,
+    wits: Vec<String>,
+    cuts: Vec<String>,
+    adds: Vec<String>,
+    cnfg: Vec<String>,
+    last_est: LastEst,
+    delpre: Option<String>,
+}
+
+fn ilk(serder: &Serder) -> Result<String> {
+    let label = Ids::t;
+    serder.ked()[label].to_string()
+}
+
+fn tholders(serder: &Serder) -> Result<(Tholder, Tholder)> {
+    let tholder = serder.tholder()?;
+    let ntholder = serder.ntholder()?;
+
+    match (tholder, ntholder) {
+        (Some(tholder), Some(ntholder)) => Ok((tholder, ntholder)),
+        _ => err!(Error::Validation(format!(
+            "missing thresholds in establishment event said = {said}",
+            said = serder.said()?
+        ))),
+    }
+}
+
+fn strings(value: &Value) -> Result<Vec<String>> {
+    value.to_vec()?.iter().map(|v| v.to_string()).collect()
+}
+
+fn toader(value: &Value) -> Result<Number> {
+    if let Ok(toad) = value.to_i64() {
+        if toad < 0 {
+            return err!(Error::Validation(format!("invalid toad = {toad}")));
+        }
+        Number::new_with_num(toad as u128)
+    } else {
+        Number::new_with_numh(&value.to_string()?)
+    }
+}
+
+// the said, and a self-addressing prefix, must commit to the event's content
+pub(crate) fn verify_said(serder: &Serder) -> Result<()> {
+    if !serder.verify()? {
+        return err!(Error::Validation(format!(
+            "invalid said = {said} for event content",
+            said = serder.said()?
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_unique(label: &str, items: &[String]) -> Result<()> {
+    for (i, item) in items.iter().enumerate() {
+        if items[..i].contains(item) {
+            return err!(Error::Validation(format!("invalid {label} = {items:?}, has duplicates")));
+        }
+    }
+
+    Ok(())
+}
+
+// the indices of the sigers that verify against the keys they index, without duplicates
+pub(crate) fn verify_sigs(ser: &[u8], sigers: &[Siger], verfers: &[Verfer]) -> Result<Vec<u32>> {
+    let mut indices = vec![];
+    for siger in sigers {
+        let index = siger.index();
+        if indices.contains(&index) {
+            continue;
+        }
+
+        if let Some(verfer) = verfers.get(index as usize) {
+            if verfer.verify(&siger.raw(), ser)? {
+                indices.push(index);
+            }
+        }
+    }
+
+    Ok(indices)
+}
+
+// verifies the indexed signatures of a transferable signer against the keys and threshold of
+// the establishment event in `validators` they are sealed to, which must be the signer's latest
+// establishment event in `validators` so that keys that have been rotated out are not accepted
+pub(crate) fn verify_trans(
+    ser: &[u8],
+    validators: &[Serder],
+    prefixer: &Prefixer,
+    seqner: &Seqner,
+    saider: &Saider,
+    sigers: &[Siger],
+) -> Result<()> {
+    let pre = prefixer.qb64()?;
+    let sn = seqner.sn()?;
+    let said = saider.qb64()?;
+
+    let mut validator = None;
+    for candidate in validators {
+        if candidate.pre()? == pre && candidate.sn()? == sn && candidate.said()? == said {
+            validator = Some(candidate);
+            break;
+        }
+    }
+    let validator = match validator {
+        Some(validator) if validator.est()? => validator,
+        _ => {
+            return err!(Error::Validation(format!(
+                "unknown signer establishment event: pre = {pre}, sn = {sn}"
+            )))
+        }
+    };
+
+    for candidate in validators {
+        if candidate.pre()? == pre && candidate.sn()? > sn && candidate.est()? {
+            return err!(Error::Validation(format!(
+                "stale signer establishment event: pre = {pre}, sn = {sn}, latest sn = {latest}",
+                latest = candidate.sn()?
+            )));
+        }
+    }
+
+    let indices = verify_sigs(ser, sigers, &validator.verfers()?)?;
+    if indices.len() != sigers.len() {
+        return err!(Error::Validation(format!("invalid signature: pre = {pre}")));
+    }
+
+    let tholder = match validator.tholder()? {
+        Some(tholder) => tholder,
+        None => return err!(Error::Validation(format!("missing threshold: pre = {pre}"))),
+    };
+    if !tholder.satisfy(&indices)? {
+        return err!(Error::Validation(format!("unsatisfied signing threshold: pre = {pre}")));
+    }
+
+    Ok(())
+}
+
+// the ondices of the sigers that verify against the keys they index and whose keys were
+// committed to by the prior next key digests
+fn exposeds(
+    ser: &[u8],
+    sigers: &[Siger],
+    verfers: &[Verfer],
+    digers: &[Diger],
+) -> Result<Vec<u32>> {
+    let mut ondices = vec![];
+    for siger in sigers {
+        if indexer::CurrentSigCodex::has_code(&siger.code()) {
+            continue;
+        }
+
+        let ondex = siger.ondex();
+        if ondices.contains(&ondex) {
+            continue;
+        }
+
+        let (verfer, prior) =
+            match (verfers.get(siger.index() as usize), digers.get(ondex as usize)) {
+                (Some(verfer), Some(prior)) => (verfer, prior),
+                _ => continue,
+            };
+
+        if prior.verify(&verfer.qb64b()?)? && verfer.verify(&siger.raw(), ser)? {
+            ondices.push(ondex);
+        }
+    }
+
+    Ok(ondices)
+}
+
+impl Kever {
+    /// Validates an inception event and its signatures and builds the initial key state.
+    pub fn new(serder: &Serder, sigers: &[Siger]) -> Result<Self> {
+        verify_said(serder)?;
+
+        let ilk = ilk(serder)?;
+        if ilk != Ilkage::icp && ilk != Ilkage::dip {
+            return err!(Error::Validation(format!("expected inception event, got ilk = {ilk}")));
+        }
+
+        let sner = serder.sner()?;
+        if sner.num()? != 0 {
+            return err!(Error::Validation(format!(
+                "invalid sn = {sn} for inception event",
+                sn = sner.num()?
+            )));
+        }
+
+        let ked = serder.ked();
+        let prefixer = Prefixer::new_with_qb64(&serder.pre()?)?;
+        if !prefixer.verify(&ked, Some(true))? {
+            return err!(Error::Validation(format!(
+                "invalid prefix = {pre} for inception event",
+                pre = prefixer.qb64()?
+            )));
+        }
+
+        let (tholder, ntholder) = tholders(serder)?;
+        let verfers = serder.verfers()?;
+        let digers = serder.digers()?;
+        if !prefixer.transferable() && !digers.is_empty() {
+            return err!(Error::Validation(format!(
+                "non-transferable prefix = {pre} with next key digests",
+                pre = prefixer.qb64()?
+            )));
+        }
+
+        let label = Ids::b;
+        let wits = strings(&ked[label])?;
+        validate_unique("wits", &wits)?;
+        let label = Ids::bt;
+        let toader = toader(&ked[label])?;
+        validate_toad(toader.num()?, &strs(&wits))?;
+        let label = Ids::c;
+        let cnfg = strings(&ked[label])?;
+
+        let delpre = if ilk == Ilkage::dip {
+            let label = Ids::di;
+            Some(ked[label].to_string()?)
+        } else {
+            None
+        };
+
+        let indices = verify_sigs(&serder.raw(), sigers, &verfers)?;
+        if !tholder.satisfy(&indices)? {
+            return err!(Error::Validation(format!(
+                "unsatisfied signing threshold for inception said = {said}",
+                said = serder.said()?
+            )));
+        }
+
+        let said = serder.said()?;
+        Ok(Kever {
+            prefixer,
+            sner,
+            serder: serder.clone(),
+            ilk,
+            tholder,
+            verfers,
+            ntholder,
+            digers,
+            toader,
+            wits,
+            cuts: vec![],
+            adds: vec![],
+            cnfg,
+            last_est: LastEst { s: 0, d: said },
+            delpre,
+        })
+    }
+
+    /// Validates the next event in the log and its signatures, then applies it to the key
+    /// state. The key state is unchanged when validation fails.
+    pub fn update(&mut self, serder: &Serder, sigers: &[Siger]) -> Result<()> {
+        verify_said(serder)?;
+
+        if !self.transferable() {
+            return err!(Error::Validation(format!(
+                "unexpected event for non-transferable prefix = {pre}",
+                pre = self.pre()?
+            )));
+        }
+
+        let pre = serder.pre()?;
+        if pre != self.pre()? {
+            return err!(Error::Validation(format!(
+                "mismatched event prefix = {pre}, expected {expected}",
+                expected = self.pre()?
+            )));
+        }
+
+        let sn = serder.sn()?;
+        let expected = self.sn()? + 1;
+        if sn != expected {
+            return err!(Error::Validation(format!(
+                "invalid sn = {sn}, expected {expected} for prefix = {pre}"
+            )));
+        }
+
+        let label = Ids::p;
+        let prior = serder.ked()[label].to_string()?;
+        if prior != self.serder.said()? {
+            return err!(Error::Validation(format!(
+                "invalid prior event digest = {prior}, expected {expected}",
+                expected = self.serder.said()?
+            )));
+        }
+
+        let ilk = ilk(serder)?;
+        match ilk.as_str() {
+            Ilkage::rot if self.delpre.is_none() => self.rotate(serder, sigers)?,
+            Ilkage::drt if self.delpre.is_some() => self.rotate(serder, sigers)?,
+            Ilkage::ixn => {
+                if self.cnfg.iter().any(|cnfg| cnfg == Traitage::EO) {
+                    return err!(Error::Validation(format!(
+                        "unexpected interaction for establishment only prefix = {pre}"
+                    )));
+                }
+
+                let indices = verify_sigs(&serder.raw(), sigers, &self.verfers)?;
+                if !self.tholder.satisfy(&indices)? {
+                    return err!(Error::Validation(format!(
+                        "unsatisfied signing threshold for interaction said = {said}",
+                        said = serder.said()?
+                    )));
+                }
+            }
+            _ => {
+                return err!(Error::Validation(format!(
+                    "unexpected ilk = {ilk} for prefix = {pre}"
+                )))
+            }
+        }
+
+        self.sner = serder.sner()?;
+        self.serder = serder.clone();
+        self.ilk = ilk;
+
+        Ok(())
+    }
+
+    fn rotate(&mut self, serder: &Serder, sigers: &[Siger]) -> Result<()> {
+        let ked = serder.ked();
+        let (tholder, ntholder) = tholders(serder)?;
+        let verfers = serder.verfers()?;
+        let digers = serder.digers()?;
+
+        let label = Ids::br;
+        let cuts = strings(&ked[label])?;
+        let label = Ids::ba;
+        let adds = strings(&ked[label])?;
+        let wits = rotate_wits(&strs(&self.wits), &strs(&cuts), &strs(&adds))?;
+        let label = Ids::bt;
+        let toader = toader(&ked[label])?;
+        validate_toad(toader.num()?, &strs(&wits))?;
+
+        let ser = serder.raw();
+        let indices = verify_sigs(&ser, sigers, &verfers)?;
+        if !tholder.satisfy(&indices)? {
+            return err!(Error::Validation(format!(
+                "unsatisfied signing threshold for rotation said = {said}",
+                said = serder.said()?
+            )));
+        }
+
+        let ondices = exposeds(&ser, sigers, &verfers, &self.digers)?;
+        if !self.ntholder.satisfy(&ondices)? {
+            return err!(Error::Validation(format!(
+                "unsatisfied prior next threshold for rotation said = {said}",
+                said = serder.said()?
+            )));
+        }
+
+        self.tholder = tholder;
+        self.verfers = verfers;
+        self.ntholder = ntholder;
+        self.digers = digers;
+        self.toader = toader;
+        self.wits = wits;
+        self.cuts = cuts;
+        self.adds = adds;
+        self.last_est = LastEst { s: serder.sn()?, d: serder.said()? };
+
+        Ok(())
+    }
+
+    pub fn prefixer(&self) -> Prefixer {
+        self.prefixer.clone()
+    }
+
+    pub fn pre(&self) -> Result<String> {
+        self.prefixer.qb64()
+    }
+
+    pub fn sner(&self) -> Number {
+        self.sner.clone()
+    }
+
+    pub fn sn(&self) -> Result<u128> {
+        self.sner.num()
+    }
+
+    /// The latest accepted event.
+    pub fn serder(&self) -> Serder {
+        self.serder.clone()
+    }
+
+    pub fn ilk(&self) -> String {
+        self.ilk.clone()
+    }
+
+    pub fn tholder(&self) -> Tholder {
+        self.tholder.clone()
+    }
+
+    pub fn verfers(&self) -> Vec<Verfer> {
+        self.verfers.clone()
+    }
+
+    pub fn ntholder(&self) -> Tholder {
+        self.ntholder.clone()
+    }
+
+    pub fn digers(&self) -> Vec<Diger> {
+        self.digers.clone()
+    }
+
+    pub fn toader(&self) -> Number {
+        self.toader.clone()
+    }
+
+    pub fn wits(&self) -> Vec<String> {
+        self.wits.clone()
+    }
+
+    /// The witnesses cut by the latest establishment event.
+    pub fn cuts(&self) -> Vec<String> {
+        self.cuts.clone()
+    }
+
+    /// The witnesses added by the latest establishment event.
+    pub fn adds(&self) -> Vec<String> {
+        self.adds.clone()
+    }
+
+    /// The configuration traits of the inception event.
+    pub fn cnfg(&self) -> Vec<String> {
+        self.cnfg.clone()
+    }
+
+    /// Whether the indexed witness signatures `wigers` on the latest event satisfy the witness
+    /// threshold in effect.
+    pub fn witnessed(&self, wigers: &[Siger]) -> Result<bool> {
+        witnessed(&self.serder.raw(), wigers, &strs(&self.wits), self.toader.num()?)
+    }
+
+    pub fn last_est(&self) -> LastEst {
+        self.last_est.clone()
+    }
+
+    pub fn delpre(&self) -> Option<String> {
+        self.delpre.clone()
+    }
+
+    /// Whether the identifier may issue further events. False for non-transferable prefixes
+    /// and once the keys have been abandoned by rotating to an empty next key list.
+    pub fn transferable(&self) -> bool {
+        self.prefixer.transferable() && !self.digers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Kever, LastEst};
+    use crate::core::{
+        common::Traitage,
+        diger::Diger,
+        eventing::{incept, interact, rotate},
+        fixtures::{self, digs, keys, sign, signers},
+        matter::{tables as matter, Matter},
+        sadder::Sadder,
+        serder::Serder,
+        siger::Siger,
+        signer::Signer,
+        util::strs,
+    };
+
+    fn inception(signers: &[Signer], nsigners: &[Signer]) -> Serder {
+        incept(
+            &strs(&keys(signers)),
+            Some(&dat!(2)),
+            Some(&strs(&digs(nsigners))),
+            Some(&dat!(2)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    fn rotation(kever: &Kever, signers: &[Signer], nsigners: &[Signer]) -> Serder {
+        rotate(
+            &kever.pre().unwrap(),
+            &strs(&keys(signers)),
+            &kever.serder().said().unwrap(),
+            None,
+            Some(kever.sn().unwrap() + 1),
+            Some(&dat!(2)),
+            Some(&strs(&digs(nsigners))),
+            Some(&dat!(2)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn validate_kel() {
+        let (s0, s1, s2) = (signers(3, 0), signers(3, 3), signers(3, 6));
+
+        let icp = inception(&s0, &s1);
+        let mut kever = Kever::new(&icp, &sign(&icp.raw(), &s0, &[0, 2])).unwrap();
+        assert_eq!(kever.pre().unwrap(), icp.pre().unwrap());
+        assert_eq!(kever.sn().unwrap(), 0);
+        assert_eq!(kever.verfers(), icp.verfers().unwrap());
+        assert!(kever.transferable());
+        assert!(kever.delpre().is_none());
+        assert!(kever.cnfg().is_empty());
+
+        let ixn = interact(&kever.pre().unwrap(), &icp.said().unwrap(), Some(1), None, None, None)
+            .unwrap();
+        kever.update(&ixn, &sign(&ixn.raw(), &s0, &[1, 2])).unwrap();
+        assert_eq!(kever.sn().unwrap(), 1);
+        assert_eq!(kever.last_est(), LastEst { s: 0, d: icp.said().unwrap() });
+
+        let rot = rotation(&kever, &s1, &s2);
+        kever.update(&rot, &sign(&rot.raw(), &s1, &[0, 1])).unwrap();
+        assert_eq!(kever.sn().unwrap(), 2);
+        assert_eq!(kever.ilk(), "rot");
+        assert_eq!(kever.verfers(), rot.verfers().unwrap());
+        assert_eq!(kever.digers(), rot.digers().unwrap());
+        assert_eq!(kever.last_est(), LastEst { s: 2, d: rot.said().unwrap() });
+        assert_eq!(kever.serder(), rot);
+    }
+
+    #[test]
+    fn witnesses() {
+        let (s0, s1) = (signers(3, 0), signers(3, 3));
+        let witnesses = fixtures::witnesses(3, 20);
+        let wits = keys(&witnesses);
+
+        let icp = incept(
+            &strs(&keys(&s0)),
+            Some(&dat!(2)),
+            Some(&strs(&digs(&s1))),
+            Some(&dat!(2)),
+            Some(2),
+            Some(&strs(&wits[..2])),
+            None,
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .unwrap();
+        let kever = Kever::new(&icp, &sign(&icp.raw(), &s0, &[0, 1])).unwrap();
+        assert_eq!(kever.wits(), wits[..2]);
+        assert_eq!(kever.toader().num().unwrap(), 2);
+        assert!(kever.witnessed(&sign(&icp.raw(), &witnesses, &[0, 1])).unwrap());
+        assert!(!kever.witnessed(&sign(&icp.raw(), &witnesses, &[1])).unwrap());
+
+        let rotation = |toad, cuts: &[String], adds: &[String]| {
+            rotate(
+                &kever.pre().unwrap(),
+                &strs(&keys(&s1)),
+                &icp.said().unwrap(),
+                None,
+                None,
+                Some(&dat!(2)),
+                None,
+                None,
+                toad,
+                Some(&strs(&wits)),
+                Some(&strs(cuts)),
+                Some(&strs(adds)),
+                None,
+                None,
+                None,
+                None,
+            )
+        };
+
+        // the builder is told about every witness so that it accepts what the kever must reject
+        let rot = rotation(Some(1), &wits[..1], &[]).unwrap();
+        let mut rotated = kever.clone();
+        rotated.update(&rot, &sign(&rot.raw(), &s1, &[0, 1])).unwrap();
+        assert_eq!(rotated.wits(), wits[1..2]);
+        assert_eq!(rotated.toader().num().unwrap(), 1);
+        assert_eq!(rotated.cuts(), wits[..1]);
+        assert!(rotated.adds().is_empty());
+
+        let rot = rotation(Some(3), &[], &[]).unwrap();
+        assert!(kever.clone().update(&rot, &sign(&rot.raw(), &s1, &[0, 1])).is_err());
+        let rot = rotation(Some(1), &wits[2..], &[]).unwrap();
+        assert!(kever.clone().update(&rot, &sign(&rot.raw(), &s1, &[0, 1])).is_err());
+    }
+
+    #[test]
+    fn unhappy_inception() {
+        let (s0, s1) = (signers(3, 0), signers(3, 3));
+        let icp = inception(&s0, &s1);
+
+        // below threshold
+        assert!(Kever::new(&icp, &sign(&icp.raw(), &s0, &[0])).is_err());
+        assert!(Kever::new(&icp, &sign(&icp.raw(), &s0, &[0, 0])).is_err());
+
+        // signed by the wrong keys
+        assert!(Kever::new(&icp, &sign(&icp.raw(), &s1, &[0, 1])).is_err());
+
+        // not an inception event
+        let ixn =
+            interact(&icp.pre().unwrap(), &icp.said().unwrap(), None, None, None, None).unwrap();
+        assert!(Kever::new(&ixn, &sign(&ixn.raw(), &s0, &[0, 1])).is_err());
+
+        // tampered prefix
+        let mut ked = icp.ked();
+        ked["i"] = dat!(&keys(&s0)[0]);
+        let serder = Serder::new_with_ked(&ked, None, None).unwrap();
+        assert!(Kever::new(&serder, &sign(&serder.raw(), &s0, &[0, 1])).is_err());
+    }
+
+    #[test]
+    fn unhappy_updates() {
+        let (s0, s1, s2) = (signers(3, 0), signers(3, 3), signers(3, 6));
+        let icp = inception(&s0, &s1);
+        let kever = Kever::new(&icp, &sign(&icp.raw(), &s0, &[0, 1])).unwrap();
+        let pre = kever.pre().unwrap();
+        let said = icp.said().unwrap();
+
+        // out of order sequence number
+        let ixn = interact(&pre, &said, Some(2), None, None, None).unwrap();
+        assert!(kever.clone().update(&ixn, &sign(&ixn.raw(), &s0, &[0, 1])).is_err());
+
+        // different prefix
+        let other = inception(&s1, &s0);
+        let ixn = interact(&other.pre().unwrap(), &said, None, None, None, None).unwrap();
+        assert!(kever.clone().update(&ixn, &sign(&ixn.raw(), &s0, &[0, 1])).is_err());
+
+        // broken digest chain
+        let dig = Diger::new_with_ser(b"other", None).unwrap().qb64().unwrap();
+        let ixn = interact(&pre, &dig, None, None, None, None).unwrap();
+        assert!(kever.clone().update(&ixn, &sign(&ixn.raw(), &s0, &[0, 1])).is_err());
+
+        // interaction below threshold
+        let ixn = interact(&pre, &said, None, None, None, None).unwrap();
+        assert!(kever.clone().update(&ixn, &sign(&ixn.raw(), &s0, &[2])).is_err());
+
+        // rotation to keys that were not committed to
+        let rot = rotation(&kever, &s2, &s0);
+        assert!(kever.clone().update(&rot, &sign(&rot.raw(), &s2, &[0, 1])).is_err());
+
+        // rotation signed with current only signatures does not expose the next keys
+        let rot = rotation(&kever, &s1, &s2);
+        let sigers: Vec<Siger> = [0u32, 1]
+            .iter()
+            .map(|&i| s1[i as usize].sign_indexed(&rot.raw(), true, i, None).unwrap())
+            .collect();
+        assert!(kever.clone().update(&rot, &sigers).is_err());
+
+        // a forged signature at the index of a committed key does not expose it
+        let mixed = vec![s1[0].clone(), s1[1].clone(), s2[0].clone()];
+        let rot = rotation(&kever, &mixed, &s2);
+        let sigers = vec![
+            s1[0].sign_indexed(&rot.raw(), false, 0, None).unwrap(),
+            s1[1].sign_indexed(b"forged", false, 1, None).unwrap(),
+            s2[0].sign_indexed(&rot.raw(), true, 2, None).unwrap(),
+        ];
+        assert!(kever.clone().update(&rot, &sigers).is_err());
+        let mut sigers = sigers;
+        sigers[1] = s1[1].sign_indexed(&rot.raw(), false, 1, None).unwrap();
+        assert!(kever.clone().update(&rot, &sigers).is_ok());
+
+        // delegated rotation for an identifier without a delegator
+        let mut ked = rot.ked();
+        ked["t"] = dat!("drt");
+        let (_, ked) = crate::core::saider::Saider::saidify(&ked, None, None, None, None).unwrap();
+        let drt = Serder::new_with_ked(&ked, None, None).unwrap();
+        assert!(kever.clone().update(&drt, &sign(&drt.raw(), &s1, &[0, 1])).is_err());
+
+        // a failed update leaves the state untouched
+        let mut kever = kever;
+        let before = kever.clone();
+        assert!(kever.update(&rot, &sign(&rot.raw(), &s1, &[0])).is_err());
+        assert_eq!(kever, before);
+    }
+
+    #[test]
+    fn establishment_only() {
+        let (s0, s1, s2) = (signers(3, 0), signers(3, 3), signers(3, 6));
+        let icp = incept(
+            &strs(&keys(&s0)),
+            Some(&dat!(2)),
+            Some(&strs(&digs(&s1))),
+            Some(&dat!(2)),
+            None,
+            None,
+            Some(&[Traitage::EO]),
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .unwrap();
+        let mut kever = Kever::new(&icp, &sign(&icp.raw(), &s0, &[0, 1])).unwrap();
+        assert_eq!(kever.cnfg(), vec![Traitage::EO]);
+
+        let ixn =
+            interact(&kever.pre().unwrap(), &icp.said().unwrap(), None, None, None, None).unwrap();
+        assert!(kever.clone().update(&ixn, &sign(&ixn.raw(), &s0, &[0, 1])).is_err());
+
+        let rot = rotation(&kever, &s1, &s2);
+        kever.update(&rot, &sign(&rot.raw(), &s1, &[0, 1])).unwrap();
+        assert_eq!(kever.sn().unwrap(), 1);
+    }
+
+    #[test]
+    fn abandoned() {
+        let (s0, s1) = (signers(3, 0), signers(3, 3));
+        let icp = inception(&s0, &s1);
+        let mut kever = Kever::new(&icp, &sign(&icp.raw(), &s0, &[0, 1])).unwrap();
+
+        let rot = rotate(
+            &kever.pre().unwrap(),
+            &strs(&keys(&s1)),
+            &icp.said().unwrap(),
+            None,
+            None,
+            Some(&dat!(2)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        kever.update(&rot, &sign(&rot.raw(), &s1, &[0, 1])).unwrap();
+        assert!(!kever.transferable());
+
+        let ixn = interact(&kever.pre().unwrap(), &rot.said().unwrap(), Some(2), None, None, None)
+            .unwrap();
+        assert!(kever.update(&ixn, &sign(&ixn.raw(), &s1, &[0, 1])).is_err());
+    }
+
+    #[test]
+    fn tampered_said() {
+        let (s0, s1) = (signers(3, 0), signers(3, 3));
+        let icp = inception(&s0, &s1);
+
+        // the body changes under the original said and prefix, and is signed as changed
+        let mut ked = icp.ked();
+        ked["c"] = dat!([Traitage::EO]);
+        let forged = Serder::new_with_ked(&ked, None, None).unwrap();
+        assert_eq!(forged.said().unwrap(), icp.said().unwrap());
+        assert!(Kever::new(&forged, &sign(&forged.raw(), &s0, &[0, 1])).is_err());
+
+        let mut kever = Kever::new(&icp, &sign(&icp.raw(), &s0, &[0, 1])).unwrap();
+        let ixn =
+            interact(&kever.pre().unwrap(), &icp.said().unwrap(), None, None, None, None).unwrap();
+        let mut ked = ixn.ked();
+        ked["a"] = dat!([{"d": "EBabiu_JCkE0GbiglDXNB5C4NQq-hiGgxhHKXBxkiojg"}]);
+        let forged = Serder::new_with_ked(&ked, None, None).unwrap();
+        assert_eq!(forged.said().unwrap(), ixn.said().unwrap());
+        assert!(kever.clone().update(&forged, &sign(&forged.raw(), &s0, &[0, 1])).is_err());
+
+        kever.update(&ixn, &sign(&ixn.raw(), &s0, &[0, 1])).unwrap();
+    }
+}
@@ -45,7 +45,7 @@ pub(crate) fn sizage(s: &str) -> Result<Sizage> {
         "1AAB" => Sizage { hs: 4, ss: 0, fs: 48, ls: 0 },
         "1AAC" => Sizage { hs: 4, ss: 0, fs: 80, ls: 0 },
         "1AAD" => Sizage { hs: 4, ss: 0, fs: 80, ls: 0 },
-        "1AAE" => Sizage { hs: 4, ss: 0, fs: 56, ls: 0 },
+        "1AAE" => Sizage { hs: 4, ss: 0, fs: 156, ls: 0 },
         "1AAF" => Sizage { hs: 4, ss: 0, fs: 8, ls: 0 },
         "1AAG" => Sizage { hs: 4, ss: 0, fs: 36, ls: 0 },
         "1AAH" => Sizage { hs: 4, ss: 0, fs: 100, ls: 0 },
@@ -192,7 +192,7 @@ mod test {
     #[case("1AAB", 4, 0, 48, 0)]
     #[case("1AAC", 4, 0, 80, 0)]
     #[case("1AAD", 4, 0, 80, 0)]
-    #[case("1AAE", 4, 0, 56, 0)]
+    #[case("1AAE", 4, 0, 156, 0)]
     #[case("1AAF", 4, 0, 8, 0)]
     #[case("1AAG", 4, 0, 36, 0)]
     #[case("1AAH", 4, 0, 100, 0)]
@@ -1,22 +1,43 @@
+pub mod attachments;
 pub mod bexter;
 pub mod cigar;
+pub mod cipher;
 pub mod common;
 pub mod counter;
 pub mod creder;
 pub mod dater;
+pub mod decrypter;
 pub mod diger;
+pub mod encrypter;
+pub mod eventing;
+pub mod exchanging;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod grouping;
 pub mod indexer;
+pub mod ipex;
+pub mod kever;
 pub mod matter;
 pub mod number;
+pub mod oobing;
+pub mod parser;
 pub mod pather;
 pub mod prefixer;
+pub mod proving;
+pub mod routing;
 pub mod sadder;
 pub mod saider;
 pub mod salter;
+pub mod schemer;
+pub mod seal;
 pub mod seqner;
 pub mod serder;
 pub mod siger;
 pub mod signer;
+pub mod signing;
+pub mod state;
+pub mod tel;
+pub mod tever;
 pub mod tholder;
 pub mod util;
 pub mod verfer;
@@ -0,0 +1,1010 @@
+use indexmap::IndexMap;
+
+use crate::{
+    core::{
+        attachments::Group,
+        common::{Ids, Ilkage, Rolage, Schemage, Version},
+        eventing::reply,
+        kever::Kever,
+        parser::{Body, Message, Parser},
+        prefixer::Prefixer,
+        routing::Revery,
+        sadder::Sadder,
+        serder::Serder,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+pub mod Routage {
+    pub const end_role_add: &str = "/end/role/add";
+    pub const end_role_cut: &str = "/end/role/cut";
+    pub const loc_scheme: &str = "/loc/scheme";
+}
+
+const OOBI: &str = "oobi";
+const WELL_KNOWN: &str = ".well-known/keri/oobi";
+const ROLES: &[&str] = &[
+    Rolage::controller,
+    Rolage::witness,
+    Rolage::registrar,
+    Rolage::watcher,
+    Rolage::judge,
+    Rolage::juror,
+    Rolage::peer,
+    Rolage::mailbox,
+    Rolage::agent,
+];
+const SCHEMES: &[&str] = &[Schemage::http, Schemage::https, Schemage::tcp];
+
+// the parts of a url: scheme, host, port, path without its leading slash and query
+struct Url {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+    path: String,
+    query: String,
+}
+
+fn parse_url(url: &str) -> Result<Url> {
+    let invalid = || err!(Error::Value(format!("invalid url = {url}")));
+
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (scheme.to_lowercase(), rest),
+        None => return invalid(),
+    };
+    if !SCHEMES.contains(&scheme.as_str()) {
+        return err!(Error::Value(format!("unsupported url scheme = {scheme}")));
+    }
+
+    let rest = rest.split_once('#').map_or(rest, |(rest, _)| rest);
+    let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+
+    // ipv6 hosts are bracketed so that their colons are not taken for the port separator
+    let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+        match bracketed.split_once(']') {
+            Some((host, "")) => (format!("[{host}]"), None),
+            Some((host, port)) => match port.strip_prefix(':') {
+                Some(port) => (format!("[{host}]"), Some(port)),
+                None => return invalid(),
+            },
+            None => return invalid(),
+        }
+    } else {
+        match authority.split_once(':') {
+            Some((host, port)) => (host.to_string(), Some(port)),
+            None => (authority.to_string(), None),
+        }
+    };
+    if host.is_empty() || host == "[]" || host.contains('@') {
+        return invalid();
+    }
+    let port = match port {
+        Some(port) => match port.parse::<u16>() {
+            Ok(port) => Some(port),
+            Err(_) => return invalid(),
+        },
+        None => None,
+    };
+
+    Ok(Url { scheme, host, port, path: path.to_string(), query: query.to_string() })
+}
+
+fn validate_prefix(label: &str, pre: &str) -> Result<()> {
+    if Prefixer::new_with_qb64(pre).is_err() {
+        return err!(Error::Value(format!("invalid {label} = {pre}")));
+    }
+
+    Ok(())
+}
+
+fn validate_role(role: &str) -> Result<()> {
+    if !ROLES.contains(&role) {
+        return err!(Error::Value(format!("invalid role = {role}")));
+    }
+
+    Ok(())
+}
+
+// the data of a reply on one of `routes`, with exactly `labels`
+fn reply_data(rpy: &Serder, routes: &[&str], labels: &[&str]) -> Result<(String, Value)> {
+    let ked = rpy.ked();
+    let label = Ids::t;
+    let ilk = ked[label].to_string()?;
+    if ilk != Ilkage::rpy {
+        return err!(Error::Validation(format!("expected reply, got ilk = {ilk}")));
+    }
+
+    let label = Ids::r;
+    let route = ked[label].to_string()?;
+    if !routes.contains(&route.as_str()) {
+        return err!(Error::Validation(format!("unexpected reply route = {route}")));
+    }
+
+    let label = Ids::a;
+    let data = ked[label].clone();
+    let keys = data.to_map()?.keys().cloned().collect::<Vec<_>>();
+    if keys != labels {
+        return err!(Error::Validation(format!(
+            "invalid reply fields = {keys:?} on route = {route}, expected {labels:?}"
+        )));
+    }
+
+    Ok((route, data))
+}
+
+// verifies the signatures on `message` with a copy of `revery`, which replaces it only when
+// `signer` is among the signers
+fn accept(
+    message: &Message,
+    revery: &mut Revery,
+    validators: &[Serder],
+    signer: &str,
+) -> Result<()> {
+    let mut trial = revery.clone();
+    let signers = trial.verify(message, validators)?;
+    if !signers.iter().any(|pre| pre == signer) {
+        return err!(Error::Validation(format!(
+            "reply not signed by {signer}, signers = {signers:?}"
+        )));
+    }
+
+    *revery = trial;
+    Ok(())
+}
+
+fn serder(message: &Message) -> Result<&Serder> {
+    match &message.body {
+        Body::Serder(serder) => Ok(serder),
+        Body::Creder(_) => err!(Error::Validation("unexpected ACDC for reply".to_string())),
+    }
+}
+
+/// An out-of-band introduction (OOBI): a url at which the key event log of the controller `cid`
+/// is served, optionally by the endpoint provider `eid` in `role`.
+///
+/// Urls have the form `scheme://host:port/oobi/{cid}`, `.../oobi/{cid}/{role}` or
+/// `.../oobi/{cid}/{role}/{eid}`, or `scheme://host:port/.well-known/keri/oobi/{cid}`, with an
+/// optional `name` query parameter suggesting an alias for `cid`. Other query parameters are
+/// ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Oobi {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+    cid: String,
+    role: Option<String>,
+    eid: Option<String>,
+    name: Option<String>,
+    well_known: bool,
+}
+
+impl Oobi {
+    /// Builds the OOBI for `cid` served at the endpoint url `base`, such as the url of a
+    /// location scheme. An OOBI with `eid` must have a `role`.
+    pub fn new(
+        base: &str,
+        cid: &str,
+        role: Option<&str>,
+        eid: Option<&str>,
+        name: Option<&str>,
+    ) -> Result<Self> {
+        let url = parse_url(base)?;
+        if !url.path.is_empty() || !url.query.is_empty() {
+            return err!(Error::Value(format!("invalid oobi base url = {base}, has a path")));
+        }
+        if url.scheme == Schemage::tcp {
+            return err!(Error::Value(format!("invalid oobi scheme = {}", url.scheme)));
+        }
+
+        validate_prefix("cid", cid)?;
+        if let Some(role) = role {
+            validate_role(role)?;
+        }
+        if let Some(eid) = eid {
+            if role.is_none() {
+                return err!(Error::Value(format!("missing role for eid = {eid}")));
+            }
+            validate_prefix("eid", eid)?;
+        }
+        if let Some(name) = name {
+            if name.is_empty()
+                || !name.chars().all(|c| c.is_ascii_alphanumeric() || "-_.~".contains(c))
+            {
+                return err!(Error::Value(format!("invalid oobi name = {name}")));
+            }
+        }
+
+        Ok(Oobi {
+            scheme: url.scheme,
+            host: url.host,
+            port: url.port,
+            cid: cid.to_string(),
+            role: role.map(|role| role.to_string()),
+            eid: eid.map(|eid| eid.to_string()),
+            name: name.map(|name| name.to_string()),
+            well_known: false,
+        })
+    }
+
+    /// Builds the well-known OOBI for `cid` served at the endpoint url `base`.
+    pub fn new_well_known(base: &str, cid: &str) -> Result<Self> {
+        let mut oobi = Self::new(base, cid, None, None, None)?;
+        oobi.well_known = true;
+        Ok(oobi)
+    }
+
+    /// Parses an OOBI url.
+    pub fn new_with_url(url: &str) -> Result<Self> {
+        let parsed = parse_url(url)?;
+        let port = parsed.port.map(|port| format!(":{port}")).unwrap_or_default();
+        let base = format!("{}://{}{port}", parsed.scheme, parsed.host);
+
+        let name = parsed
+            .query
+            .split('&')
+            .filter_map(|param| param.split_once('='))
+            .find(|(key, _)| *key == "name")
+            .map(|(_, name)| name.to_string());
+
+        let path = parsed.path.trim_end_matches('/');
+        if let Some(cid) = path.strip_prefix(&format!("{WELL_KNOWN}/")) {
+            let mut oobi = Self::new(&base, cid, None, None, name.as_deref())?;
+            oobi.well_known = true;
+            return Ok(oobi);
+        }
+
+        let segments = path.split('/').collect::<Vec<_>>();
+        match segments.as_slice() {
+            [OOBI, cid] => Self::new(&base, cid, None, None, name.as_deref()),
+            [OOBI, cid, role] => Self::new(&base, cid, Some(role), None, name.as_deref()),
+            [OOBI, cid, role, eid] => Self::new(&base, cid, Some(role), Some(eid), name.as_deref()),
+            _ => err!(Error::Value(format!("invalid oobi url = {url}"))),
+        }
+    }
+
+    /// The OOBI url.
+    pub fn url(&self) -> String {
+        let mut url = format!("{}/", self.base());
+        if self.well_known {
+            url.push_str(&format!("{WELL_KNOWN}/{cid}", cid = self.cid));
+        } else {
+            url.push_str(&format!("{OOBI}/{cid}", cid = self.cid));
+            if let Some(role) = &self.role {
+                url.push_str(&format!("/{role}"));
+            }
+            if let Some(eid) = &self.eid {
+                url.push_str(&format!("/{eid}"));
+            }
+        }
+        if let Some(name) = &self.name {
+            url.push_str(&format!("?name={name}"));
+        }
+
+        url
+    }
+
+    /// The url of the endpoint serving the OOBI, without a path.
+    pub fn base(&self) -> String {
+        let port = self.port.map(|port| format!(":{port}")).unwrap_or_default();
+        format!("{}://{}{port}", self.scheme, self.host)
+    }
+
+    pub fn scheme(&self) -> String {
+        self.scheme.clone()
+    }
+
+    pub fn host(&self) -> String {
+        self.host.clone()
+    }
+
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    pub fn cid(&self) -> String {
+        self.cid.clone()
+    }
+
+    pub fn role(&self) -> Option<String> {
+        self.role.clone()
+    }
+
+    pub fn eid(&self) -> Option<String> {
+        self.eid.clone()
+    }
+
+    pub fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    pub fn well_known(&self) -> bool {
+        self.well_known
+    }
+}
+
+/// Authorization by the controller `cid` of the endpoint provider `eid` in `role`, from an
+/// `/end/role/add` reply, or its revocation, from an `/end/role/cut` reply. End role replies
+/// must be signed by `cid`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndRole {
+    cid: String,
+    role: String,
+    eid: String,
+    allowed: bool,
+}
+
+impl EndRole {
+    pub fn new(cid: &str, role: &str, eid: &str, allowed: Option<bool>) -> Result<Self> {
+        validate_prefix("cid", cid)?;
+        validate_role(role)?;
+        validate_prefix("eid", eid)?;
+
+        Ok(EndRole {
+            cid: cid.to_string(),
+            role: role.to_string(),
+            eid: eid.to_string(),
+            allowed: allowed.unwrap_or(true),
+        })
+    }
+
+    /// Reads the end role in `rpy`, without checking its signatures.
+    pub fn new_with_reply(rpy: &Serder) -> Result<Self> {
+        let (route, data) = reply_data(
+            rpy,
+            &[Routage::end_role_add, Routage::end_role_cut],
+            &["cid", "role", "eid"],
+        )?;
+        Self::new(
+            &data["cid"].to_string()?,
+            &data["role"].to_string()?,
+            &data["eid"].to_string()?,
+            Some(route == Routage::end_role_add),
+        )
+    }
+
+    /// Verifies the signatures on the end role reply `message` with `revery`, which must include
+    /// a signature of `cid`, and reads its end role. Transferable signatures are checked against
+    /// the establishment events in `validators`.
+    pub fn accept(message: &Message, revery: &mut Revery, validators: &[Serder]) -> Result<Self> {
+        let end_role = Self::new_with_reply(serder(message)?)?;
+        accept(message, revery, validators, &end_role.cid)?;
+        Ok(end_role)
+    }
+
+    /// The unsigned reply for the end role, stamped `stamp`, now by default.
+    pub fn reply(
+        &self,
+        stamp: Option<&str>,
+        version: Option<&Version>,
+        kind: Option<&str>,
+    ) -> Result<Serder> {
+        let route = if self.allowed { Routage::end_role_add } else { Routage::end_role_cut };
+        let data = dat!({ "cid": &self.cid, "role": &self.role, "eid": &self.eid });
+        reply(route, Some(&data), stamp, version, kind)
+    }
+
+    pub fn cid(&self) -> String {
+        self.cid.clone()
+    }
+
+    pub fn role(&self) -> String {
+        self.role.clone()
+    }
+
+    pub fn eid(&self) -> String {
+        self.eid.clone()
+    }
+
+    /// Whether the role is added, rather than cut.
+    pub fn allowed(&self) -> bool {
+        self.allowed
+    }
+}
+
+/// The url at which the endpoint provider `eid` serves `scheme`, from a `/loc/scheme` reply.
+/// Location scheme replies must be signed by `eid`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocScheme {
+    eid: String,
+    scheme: String,
+    url: String,
+}
+
+impl LocScheme {
+    pub fn new(eid: &str, scheme: &str, url: &str) -> Result<Self> {
+        validate_prefix("eid", eid)?;
+        if !SCHEMES.contains(&scheme) {
+            return err!(Error::Value(format!("unsupported scheme = {scheme}")));
+        }
+        if parse_url(url)?.scheme != scheme {
+            return err!(Error::Value(format!("mismatched scheme = {scheme} for url = {url}")));
+        }
+
+        Ok(LocScheme { eid: eid.to_string(), scheme: scheme.to_string(), url: url.to_string() })
+    }
+
+    /// Reads the location scheme in `rpy`, without checking its signatures.
+    pub fn new_with_reply(rpy: &Serder) -> Result<Self> {
+        let (_, data) = reply_data(rpy, &[Routage::loc_scheme], &["eid", "scheme", "url"])?;
+        Self::new(
+            &data["eid"].to_string()?,
+            &data["scheme"].to_string()?,
+            &data["url"].to_string()?,
+        )
+    }
+
+    /// Verifies the signatures on the location scheme reply `message` with `revery`, which must
+    /// include a signature of `eid`, and reads its location scheme. Transferable signatures are
+    /// checked against the establishment events in `validators`.
+    pub fn accept(message: &Message, revery: &mut Revery, validators: &[Serder]) -> Result<Self> {
+        let loc_scheme = Self::new_with_reply(serder(message)?)?;
+        accept(message, revery, validators, &loc_scheme.eid)?;
+        Ok(loc_scheme)
+    }
+
+    /// The unsigned reply for the location scheme, stamped `stamp`, now by default.
+    pub fn reply(
+        &self,
+        stamp: Option<&str>,
+        version: Option<&Version>,
+        kind: Option<&str>,
+    ) -> Result<Serder> {
+        let data = dat!({ "eid": &self.eid, "scheme": &self.scheme, "url": &self.url });
+        reply(Routage::loc_scheme, Some(&data), stamp, version, kind)
+    }
+
+    pub fn eid(&self) -> String {
+        self.eid.clone()
+    }
+
+    pub fn scheme(&self) -> String {
+        self.scheme.clone()
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+}
+
+/// Fetches the CESR stream served at an OOBI, so that OOBIs can be resolved over whatever
+/// transport the application provides.
+pub trait Fetcher {
+    fn fetch(&mut self, oobi: &Oobi) -> Result<Vec<u8>>;
+}
+
+/// The result of resolving an OOBI: the key state of its controller, and the end roles and
+/// location schemes in effect after the replies served with it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolution {
+    pub kever: Kever,
+    pub end_roles: Vec<EndRole>,
+    pub loc_schemes: Vec<LocScheme>,
+}
+
+/// Resolves `oobi`: fetches the stream it serves with `fetcher`, validates the key event logs
+/// in it and accepts its end role and location scheme replies with `revery`.
+///
+/// The stream must contain the key event log of the controller of the OOBI, and, for an OOBI
+/// naming an endpoint provider, an end role authorizing it in the OOBI's role and a location
+/// scheme for it. Any other message, an invalid event, or a reply that `revery` rejects fails
+/// the resolution. Because `revery` rejects replayed replies, resolving the same stream twice
+/// with one `revery` fails.
+pub fn resolve(oobi: &Oobi, fetcher: &mut impl Fetcher, revery: &mut Revery) -> Result<Resolution> {
+    let ims = fetcher.fetch(oobi)?;
+    let mut parser = Parser::new_with_ims(&ims);
+    parser.finish();
+    let messages = parser.parse_all()?;
+    if !parser.ims().is_empty() {
+        return err!(Error::Validation(format!(
+            "truncated oobi stream at {url}",
+            url = oobi.url()
+        )));
+    }
+
+    let mut trial = revery.clone();
+    let mut kevers: IndexMap<String, Kever> = IndexMap::new();
+    let mut validators: Vec<Serder> = vec![];
+    let mut end_roles: Vec<EndRole> = vec![];
+    let mut loc_schemes: Vec<LocScheme> = vec![];
+
+    for message in &messages {
+        let serder = serder(message)?;
+        let label = Ids::t;
+        let ilk = serder.ked()[label].to_string()?;
+        match ilk.as_str() {
+            Ilkage::icp | Ilkage::dip | Ilkage::rot | Ilkage::drt | Ilkage::ixn => {
+                let sigers = message
+                    .attachments
+                    .iter()
+                    .filter_map(|group| match group {
+                        Group::ControllerIdxSigs(sigers) => Some(sigers.clone()),
+                        _ => None,
+                    })
+                    .flatten()
+                    .collect::<Vec<_>>();
+
+                let pre = serder.pre()?;
+                match kevers.get_mut(&pre) {
+                    Some(kever) => kever.update(serder, &sigers)?,
+                    None => {
+                        kevers.insert(pre, Kever::new(serder, &sigers)?);
+                    }
+                }
+                if serder.est()? {
+                    validators.push(serder.clone());
+                }
+            }
+            Ilkage::rpy => {
+                let label = Ids::r;
+                let route = serder.ked()[label].to_string()?;
+                match route.as_str() {
+                    Routage::end_role_add | Routage::end_role_cut => {
+                        let end_role = EndRole::accept(message, &mut trial, &validators)?;
+                        end_roles.retain(|er| {
+                            (&er.cid, &er.role, &er.eid)
+                                != (&end_role.cid, &end_role.role, &end_role.eid)
+                        });
+                        if end_role.allowed {
+                            end_roles.push(end_role);
+                        }
+                    }
+                    Routage::loc_scheme => {
+                        let loc_scheme = LocScheme::accept(message, &mut trial, &validators)?;
+                        loc_schemes.retain(|ls| {
+                            (&ls.eid, &ls.scheme) != (&loc_scheme.eid, &loc_scheme.scheme)
+                        });
+                        loc_schemes.push(loc_scheme);
+                    }
+                    _ => {
+                        return err!(Error::Validation(format!(
+                            "unexpected reply route = {route} in oobi stream"
+                        )))
+                    }
+                }
+            }
+            _ => {
+                return err!(Error::Validation(format!(
+                    "unexpected message ilk = {ilk} in oobi stream"
+                )))
+            }
+        }
+    }
+
+    let kever = match kevers.get(&oobi.cid) {
+        Some(kever) => kever.clone(),
+        None => {
+            return err!(Error::Validation(format!(
+                "missing key event log for cid = {cid}",
+                cid = oobi.cid
+            )))
+        }
+    };
+
+    if let (Some(role), Some(eid)) = (&oobi.role, &oobi.eid) {
+        if !end_roles.iter().any(|er| er.cid == oobi.cid && er.role == *role && er.eid == *eid) {
+            return err!(Error::Validation(format!(
+                "unauthorized endpoint eid = {eid} for role = {role} of cid = {cid}",
+                cid = oobi.cid
+            )));
+        }
+        if !loc_schemes.iter().any(|ls| ls.eid == *eid) {
+            return err!(Error::Validation(format!("missing location for eid = {eid}")));
+        }
+    }
+
+    *revery = trial;
+    Ok(Resolution { kever, end_roles, loc_schemes })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    use super::{resolve, EndRole, Fetcher, LocScheme, Oobi};
+    use crate::{
+        core::{
+            attachments::Group,
+            common::Rolage,
+            eventing::{incept, reply},
+            matter::{tables as matter, Matter},
+            parser::{Body, Message},
+            prefixer::Prefixer,
+            routing::Revery,
+            sadder::Sadder,
+            saider::Saider,
+            seqner::Seqner,
+            serder::Serder,
+            signer::Signer,
+        },
+        error::{err, Error, Result},
+    };
+
+    const CID: &str = "EIaGMMWJFPmtXznY1IIiKDIrg-vIyge6mBl2QV8dDjI3";
+    const EID: &str = "BLskRTInXnMxWaGqcpSyMgo0nYbalW99cGZESrz3zapM";
+    const DTS: &[&str] = &[
+        "2023-04-30T00:34:11.853572+00:00",
+        "2023-04-30T00:35:11.853572+00:00",
+        "2023-04-30T00:36:11.853572+00:00",
+    ];
+
+    // a controller with a single key and a single witness
+    struct Controller {
+        signer: Signer,
+        icp: Serder,
+        witness: Signer,
+    }
+
+    impl Controller {
+        fn new() -> Self {
+            let signer = Signer::new_with_raw(&[1; 32], Some(true), None).unwrap();
+            let witness = Signer::new_with_raw(&[2; 32], Some(false), None).unwrap();
+            let icp = incept(
+                &[&signer.verfer().qb64().unwrap()],
+                None,
+                None,
+                None,
+                Some(1),
+                Some(&[&witness.verfer().qb64().unwrap()]),
+                None,
+                None,
+                None,
+                None,
+                Some(matter::Codex::Blake3_256),
+                None,
+                None,
+            )
+            .unwrap();
+            Controller { signer, icp, witness }
+        }
+
+        fn cid(&self) -> String {
+            self.icp.pre().unwrap()
+        }
+
+        fn eid(&self) -> String {
+            self.witness.verfer().qb64().unwrap()
+        }
+
+        fn kel(&self) -> Message {
+            let siger = self.signer.sign_indexed(&self.icp.raw(), false, 0, None).unwrap();
+            Message {
+                body: Body::Serder(self.icp.clone()),
+                attachments: vec![Group::ControllerIdxSigs(vec![siger])],
+            }
+        }
+
+        // signed by the controller, sealed to its inception
+        fn sealed(&self, rpy: &Serder) -> Message {
+            let siger = self.signer.sign_indexed(&rpy.raw(), false, 0, None).unwrap();
+            let group = Group::TransIdxSigGroups(vec![(
+                Prefixer::new_with_qb64(&self.cid()).unwrap(),
+                Seqner::new_with_sn(0).unwrap(),
+                Saider::new_with_qb64(&self.icp.said().unwrap()).unwrap(),
+                vec![siger],
+            )]);
+            Message { body: Body::Serder(rpy.clone()), attachments: vec![group] }
+        }
+
+        // signed by the witness
+        fn witnessed(&self, rpy: &Serder) -> Message {
+            let couple = (
+                Prefixer::new_with_qb64(&self.eid()).unwrap(),
+                self.witness.sign_unindexed(&rpy.raw()).unwrap(),
+            );
+            Message {
+                body: Body::Serder(rpy.clone()),
+                attachments: vec![Group::NonTransReceiptCouples(vec![couple])],
+            }
+        }
+
+        fn end_role(&self, allowed: bool, dt: &str) -> Message {
+            let end_role =
+                EndRole::new(&self.cid(), Rolage::witness, &self.eid(), Some(allowed)).unwrap();
+            self.sealed(&end_role.reply(Some(dt), None, None).unwrap())
+        }
+
+        fn loc_scheme(&self, url: &str, dt: &str) -> Message {
+            let loc_scheme = LocScheme::new(&self.eid(), "http", url).unwrap();
+            self.witnessed(&loc_scheme.reply(Some(dt), None, None).unwrap())
+        }
+    }
+
+    fn stream(messages: &[Message]) -> Vec<u8> {
+        let mut ims = vec![];
+        for message in messages {
+            ims.append(&mut message.body.raw());
+            for group in &message.attachments {
+                ims.append(&mut group.qb64b().unwrap());
+            }
+        }
+        ims
+    }
+
+    struct Static(Vec<u8>);
+
+    impl Fetcher for Static {
+        fn fetch(&mut self, _: &Oobi) -> Result<Vec<u8>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    // a minimal HTTP/1.0 client
+    struct Http;
+
+    impl Fetcher for Http {
+        fn fetch(&mut self, oobi: &Oobi) -> Result<Vec<u8>> {
+            let url = oobi.url();
+            let path = &url[oobi.base().len()..];
+            let address = format!("{}:{}", oobi.host(), oobi.port().unwrap_or(80));
+
+            let mut response = vec![];
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(format!("GET {path} HTTP/1.0\r\n\r\n").as_bytes()).unwrap();
+            stream.read_to_end(&mut response).unwrap();
+
+            let split = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+            let head = String::from_utf8(response[..split].to_vec()).unwrap();
+            if !head.starts_with("HTTP/1.0 200") {
+                return err!(Error::Validation(format!("failed to fetch {url}: {head}")));
+            }
+            Ok(response[split + 4..].to_vec())
+        }
+    }
+
+    // serves `body` at `path` with `listener` for `connections` requests
+    fn serve(listener: TcpListener, path: String, body: Vec<u8>, connections: usize) {
+        thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let mut stream = stream.unwrap();
+                let mut request = vec![];
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8(request).unwrap();
+                let target = request.split(' ').nth(1).unwrap_or("");
+
+                let (status, body) = if target.split('?').next() == Some(path.as_str()) {
+                    ("200 OK", body.clone())
+                } else {
+                    ("404 Not Found", vec![])
+                };
+                let head = format!(
+                    "HTTP/1.0 {status}\r\nContent-Type: application/json+cesr\r\nContent-Length: {len}\r\n\r\n",
+                    len = body.len()
+                );
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+    }
+
+    #[test]
+    fn oobi_urls() {
+        let url = format!("http://127.0.0.1:5642/oobi/{CID}/witness/{EID}");
+        let oobi = Oobi::new_with_url(&url).unwrap();
+        assert_eq!(oobi.scheme(), "http");
+        assert_eq!(oobi.host(), "127.0.0.1");
+        assert_eq!(oobi.port(), Some(5642));
+        assert_eq!(oobi.base(), "http://127.0.0.1:5642");
+        assert_eq!(oobi.cid(), CID);
+        assert_eq!(oobi.role().unwrap(), Rolage::witness);
+        assert_eq!(oobi.eid().unwrap(), EID);
+        assert!(oobi.name().is_none());
+        assert!(!oobi.well_known());
+        assert_eq!(oobi.url(), url);
+        assert_eq!(
+            Oobi::new("http://127.0.0.1:5642/", CID, Some("witness"), Some(EID), None).unwrap(),
+            oobi
+        );
+
+        for url in [
+            format!("https://example.com/oobi/{CID}"),
+            format!("https://example.com/oobi/{CID}/controller"),
+            format!("http://[::1]:8080/oobi/{CID}/agent/{EID}?name=alice"),
+            format!("http://example.com/.well-known/keri/oobi/{CID}"),
+        ] {
+            assert_eq!(Oobi::new_with_url(&url).unwrap().url(), url);
+        }
+
+        let oobi =
+            Oobi::new_with_url(&format!("HTTP://[::1]/oobi/{CID}/?name=alice&other=1#x")).unwrap();
+        assert_eq!(oobi.host(), "[::1]");
+        assert_eq!(oobi.port(), None);
+        assert_eq!(oobi.name().unwrap(), "alice");
+        assert_eq!(oobi.url(), format!("http://[::1]/oobi/{CID}?name=alice"));
+        let oobi = Oobi::new_well_known("https://example.com", CID).unwrap();
+        assert!(oobi.well_known());
+        assert_eq!(oobi.url(), format!("https://example.com/.well-known/keri/oobi/{CID}"));
+
+        for url in [
+            format!("ftp://example.com/oobi/{CID}"),
+            format!("tcp://example.com/oobi/{CID}"),
+            format!("example.com/oobi/{CID}"),
+            format!("http://:80/oobi/{CID}"),
+            format!("http://user@example.com/oobi/{CID}"),
+            format!("http://example.com:port/oobi/{CID}"),
+            format!("http://example.com:65536/oobi/{CID}"),
+            format!("http://[::1/oobi/{CID}"),
+            "http://example.com/oobi/not-a-prefix".to_string(),
+            format!("http://example.com/oobi/{CID}/owner"),
+            format!("http://example.com/oobi/{CID}/witness/{EID}/extra"),
+            format!("http://example.com/keri/{CID}"),
+            format!("http://example.com/oobi/{CID}?name=a%20b"),
+            "http://example.com/oobi".to_string(),
+        ] {
+            assert!(Oobi::new_with_url(&url).is_err(), "{url}");
+        }
+        assert!(Oobi::new("http://example.com/keri", CID, None, None, None).is_err());
+        assert!(Oobi::new("http://example.com", CID, None, Some(EID), None).is_err());
+    }
+
+    #[test]
+    fn records() {
+        let end_role = EndRole::new(CID, Rolage::mailbox, EID, None).unwrap();
+        assert!(end_role.allowed());
+        let rpy = end_role.reply(Some(DTS[0]), None, None).unwrap();
+        assert_eq!(rpy.ked()["r"], dat!("/end/role/add"));
+        assert_eq!(rpy.ked()["a"], dat!({ "cid": CID, "role": "mailbox", "eid": EID }));
+        assert_eq!(EndRole::new_with_reply(&rpy).unwrap(), end_role);
+
+        let cut = EndRole::new(CID, Rolage::mailbox, EID, Some(false)).unwrap();
+        let rpy = cut.reply(None, None, None).unwrap();
+        assert_eq!(rpy.ked()["r"], dat!("/end/role/cut"));
+        assert!(!EndRole::new_with_reply(&rpy).unwrap().allowed());
+
+        let loc_scheme = LocScheme::new(EID, "tcp", "tcp://127.0.0.1:5632/").unwrap();
+        let rpy = loc_scheme.reply(Some(DTS[0]), None, None).unwrap();
+        assert_eq!(rpy.ked()["r"], dat!("/loc/scheme"));
+        assert_eq!(
+            rpy.ked()["a"],
+            dat!({ "eid": EID, "scheme": "tcp", "url": "tcp://127.0.0.1:5632/" })
+        );
+        assert_eq!(LocScheme::new_with_reply(&rpy).unwrap(), loc_scheme);
+
+        assert!(EndRole::new(CID, "owner", EID, None).is_err());
+        assert!(EndRole::new(CID, Rolage::witness, "not a prefix", None).is_err());
+        assert!(LocScheme::new(EID, "ftp", "ftp://127.0.0.1/").is_err());
+        assert!(LocScheme::new(EID, "https", "http://127.0.0.1/").is_err());
+        // replies on other routes or with other fields
+        assert!(EndRole::new_with_reply(&rpy).is_err());
+        assert!(LocScheme::new_with_reply(&end_role.reply(None, None, None).unwrap()).is_err());
+        let extra = dat!({ "cid": CID, "role": "mailbox", "eid": EID, "extra": 1 });
+        let rpy = reply("/end/role/add", Some(&extra), None, None, None).unwrap();
+        assert!(EndRole::new_with_reply(&rpy).is_err());
+    }
+
+    #[test]
+    fn signed_records() {
+        let controller = Controller::new();
+        let validators = [controller.icp.clone()];
+        let mut revery = Revery::new();
+
+        let end_role =
+            EndRole::accept(&controller.end_role(true, DTS[0]), &mut revery, &validators).unwrap();
+        assert_eq!(end_role.cid(), controller.cid());
+        assert_eq!(end_role.eid(), controller.eid());
+
+        // an end role signed by the endpoint provider instead of the controller
+        let rpy = EndRole::new(&controller.cid(), Rolage::witness, &controller.eid(), None)
+            .unwrap()
+            .reply(Some(DTS[1]), None, None)
+            .unwrap();
+        let before = revery.clone();
+        assert!(EndRole::accept(&controller.witnessed(&rpy), &mut revery, &validators).is_err());
+        assert_eq!(revery, before);
+
+        let url = "http://127.0.0.1:5642/";
+        let loc_scheme =
+            LocScheme::accept(&controller.loc_scheme(url, DTS[0]), &mut revery, &[]).unwrap();
+        assert_eq!(loc_scheme.url(), url);
+        // replayed
+        assert!(LocScheme::accept(&controller.loc_scheme(url, DTS[0]), &mut revery, &[]).is_err());
+        // a location scheme signed by the controller instead of the endpoint provider
+        let rpy = LocScheme::new(&controller.eid(), "http", url)
+            .unwrap()
+            .reply(Some(DTS[2]), None, None)
+            .unwrap();
+        assert!(LocScheme::accept(&controller.sealed(&rpy), &mut revery, &validators).is_err());
+    }
+
+    #[test]
+    fn resolve_over_http() {
+        let controller = Controller::new();
+        let (cid, eid) = (controller.cid(), controller.eid());
+        let path = format!("/oobi/{cid}/witness/{eid}");
+
+        // the witness serves the location it signed
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+        let ims = stream(&[
+            controller.kel(),
+            controller.end_role(true, DTS[0]),
+            controller.loc_scheme(&base, DTS[0]),
+        ]);
+        serve(listener, path, ims, 2);
+
+        let oobi = Oobi::new(&base, &cid, Some(Rolage::witness), Some(&eid), None).unwrap();
+        let mut revery = Revery::new();
+        let resolution = resolve(&oobi, &mut Http, &mut revery).unwrap();
+        assert_eq!(resolution.kever.pre().unwrap(), cid);
+        assert_eq!(resolution.kever.wits(), vec![eid.clone()]);
+        assert_eq!(
+            resolution.end_roles,
+            vec![EndRole::new(&cid, Rolage::witness, &eid, None).unwrap()]
+        );
+        assert_eq!(resolution.loc_schemes.len(), 1);
+        assert_eq!(resolution.loc_schemes[0].eid(), eid);
+        assert_eq!(resolution.loc_schemes[0].url(), base);
+        assert!(revery.accepted(&cid, "/end/role/add").is_some());
+        assert!(revery.accepted(&eid, "/loc/scheme").is_some());
+
+        // not served
+        let oobi = Oobi::new(&base, &cid, Some(Rolage::mailbox), None, None).unwrap();
+        assert!(resolve(&oobi, &mut Http, &mut Revery::new()).is_err());
+    }
+
+    #[test]
+    fn resolve_unhappy_paths() {
+        let controller = Controller::new();
+        let (cid, eid) = (controller.cid(), controller.eid());
+        let base = "http://127.0.0.1:5642";
+        let oobi = Oobi::new(base, &cid, Some(Rolage::witness), Some(&eid), None).unwrap();
+        let resolved = |messages: &[Message]| {
+            let mut revery = Revery::new();
+            let before = revery.clone();
+            let result = resolve(&oobi, &mut Static(stream(messages)), &mut revery);
+            if result.is_err() {
+                assert_eq!(revery, before);
+            }
+            result
+        };
+
+        let kel = controller.kel();
+        let add = controller.end_role(true, DTS[0]);
+        let loc = controller.loc_scheme(base, DTS[0]);
+        assert!(resolved(&[kel.clone(), add.clone(), loc.clone()]).is_ok());
+
+        // the controller's key event log is required
+        let other = Oobi::new(base, CID, None, None, None).unwrap();
+        assert!(resolve(
+            &other,
+            &mut Static(stream(std::slice::from_ref(&kel))),
+            &mut Revery::new()
+        )
+        .is_err());
+        assert!(resolved(&[add.clone(), loc.clone()]).is_err());
+        // and must be valid
+        let mut unsigned = kel.clone();
+        unsigned.attachments.clear();
+        assert!(resolved(&[unsigned, add.clone(), loc.clone()]).is_err());
+        // as must the end role and location of the endpoint provider
+        assert!(resolved(&[kel.clone(), loc.clone()]).is_err());
+        assert!(resolved(&[kel.clone(), add.clone()]).is_err());
+        let cut = controller.end_role(false, DTS[1]);
+        assert!(resolved(&[kel.clone(), add.clone(), cut.clone(), loc.clone()]).is_err());
+        assert!(resolved(&[
+            kel.clone(),
+            add.clone(),
+            cut,
+            controller.end_role(true, DTS[2]),
+            loc.clone()
+        ])
+        .is_ok());
+        // replies from other routes, other messages, and truncated streams are rejected
+        let ksn = controller.witnessed(&reply("/ksn", None, None, None, None).unwrap());
+        assert!(resolved(&[kel.clone(), ksn, add.clone(), loc.clone()]).is_err());
+        let mut ims = stream(&[kel, add, loc]);
+        ims.truncate(ims.len() - 10);
+        assert!(resolve(&oobi, &mut Static(ims), &mut Revery::new()).is_err());
+    }
+}
@@ -0,0 +1,521 @@
+use crate::core::{
+    attachments::{Domain, Extractor, Group},
+    common::{
+        cold_start, resync, sniff, ColdCode, Identage, Version, CURRENT_VERSION, MINIMUM_SNIFF_SIZE,
+    },
+    creder::Creder,
+    sadder::Sadder,
+    serder::Serder,
+};
+use crate::error::{err, Error, Result};
+
+/// The body of a message, typed by the protocol identifier in its version string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Body {
+    Serder(Serder),
+    Creder(Creder),
+}
+
+impl Body {
+    pub fn raw(&self) -> Vec<u8> {
+        match self {
+            Body::Serder(serder) => serder.raw(),
+            Body::Creder(creder) => creder.raw(),
+        }
+    }
+}
+
+/// A message body and the attachment groups that followed it in the stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub body: Body,
+    pub attachments: Vec<Group>,
+}
+
+/// An incremental parser for CESR streams.
+///
+/// Bytes may be fed in arbitrary chunks with `extend`. Parsing a message that is not yet
+/// complete fails with `Error::Shortage` and leaves the buffer untouched so the caller can feed
+/// more bytes and try again.
+///
+/// Unframed attachments are consumed up to the next message. When they run to the end of the
+/// buffer more groups may still follow, so parsing fails with `Error::Shortage` until the
+/// caller signals the end of the stream with `finish`. Attachments framed with
+/// `AttachedMaterialQuadlets` are complete once the frame has been read.
+///
+/// Counters are read from the CESR 1.0 table until a genus version counter, either between
+/// messages or among attachments, switches the table for the rest of the stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parser {
+    ims: Vec<u8>,
+    gvrsn: Version,
+    eos: bool,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Parser { ims: vec![], gvrsn: CURRENT_VERSION.clone(), eos: false }
+    }
+}
+
+fn shortage(e: &anyhow::Error) -> bool {
+    matches!(e.downcast_ref::<Error>(), Some(Error::Shortage(_)))
+}
+
+fn body(ims: &[u8]) -> Result<(Body, usize)> {
+    if ims.len() < MINIMUM_SNIFF_SIZE {
+        return err!(Error::Shortage(format!(
+            "need more than {len} bytes to sniff message",
+            len = ims.len()
+        )));
+    }
+
+    let result = sniff(&ims[..MINIMUM_SNIFF_SIZE])?;
+    let size = result.size as usize;
+    if ims.len() < size {
+        return err!(Error::Shortage(format!(
+            "need {bytes} more bytes to extract message",
+            bytes = size - ims.len()
+        )));
+    }
+
+    let raw = &ims[..size];
+    let body = match result.ident.as_str() {
+        Identage::KERI => Body::Serder(Serder::new_with_raw(raw)?),
+        Identage::ACDC => Body::Creder(Creder::new_with_raw(raw)?),
+        _ => return err!(Error::Parsing(format!("unexpected ident = {}", result.ident))),
+    };
+
+    Ok((body, size))
+}
+
+impl Parser {
+    pub fn new() -> Self {
+        Parser::default()
+    }
+
+    pub fn new_with_ims(ims: &[u8]) -> Self {
+        Parser { ims: ims.to_vec(), ..Default::default() }
+    }
+
+    /// Appends bytes to the end of the buffered stream.
+    pub fn extend(&mut self, ims: &[u8]) {
+        self.ims.extend_from_slice(ims);
+    }
+
+    /// Signals that no more bytes will be fed, so attachments that end the buffer are complete.
+    pub fn finish(&mut self) {
+        self.eos = true;
+    }
+
+    /// The bytes that have not yet been parsed.
+    pub fn ims(&self) -> &[u8] {
+        &self.ims
+    }
+
+    /// The genus version whose counter table is active.
+    pub fn gvrsn(&self) -> Version {
+        self.gvrsn.clone()
+    }
+
+    /// Parses the next message and its attachments, removing them from the buffer.
+    pub fn parse_one(&mut self) -> Result<Message> {
+        if self.ims.is_empty() {
+            return err!(Error::Shortage("empty stream".to_string()));
+        }
+
+        // genus version counters may precede the message. the active version is only committed
+        // once the whole message has been parsed, so a shortage leaves the parser untouched.
+        let mut gvrsn = self.gvrsn.clone();
+        let mut start = 0;
+        loop {
+            if start >= self.ims.len() {
+                return err!(Error::Shortage("no message after genus version".to_string()));
+            }
+
+            let domain = match cold_start(self.ims[start]) {
+                // whitespace or annotation separating messages
+                ColdCode::AnnotatedB64 => {
+                    start += 1;
+                    continue;
+                }
+                ColdCode::CountB64 => Domain::Text,
+                ColdCode::CountOpB2 => Domain::Binary,
+                cold if cold.message() => break,
+                cold => {
+                    return err!(Error::Parsing(format!(
+                        "expected message, got cold start = {cold:?}"
+                    )))
+                }
+            };
+
+            let mut extractor = Extractor::new(&self.ims[start..], domain, &gvrsn);
+            if !extractor.next_groups()?.is_empty() {
+                return err!(Error::Parsing("expected message, got attachments".to_string()));
+            }
+            gvrsn = extractor.gvrsn();
+            start += extractor.offset();
+        }
+
+        let (body, size) = body(&self.ims[start..])?;
+
+        let mut attachments = vec![];
+        let mut offset = start + size;
+        let mut framed = false;
+        loop {
+            if offset >= self.ims.len() {
+                if framed || self.eos {
+                    break;
+                }
+                return err!(Error::Shortage("more attachments may follow".to_string()));
+            }
+
+            let domain = match cold_start(self.ims[offset]) {
+                ColdCode::CountB64 => Domain::Text,
+                ColdCode::CountOpB2 => Domain::Binary,
+                ColdCode::OpB64 => {
+                    return err!(Error::UnexpectedOpCode(
+                        "unsupported op code in attachments".to_string()
+                    ))
+                }
+                // the next message, or whitespace or annotation ending the attachments
+                cold if cold.message() || cold == ColdCode::AnnotatedB64 => break,
+                cold => {
+                    return err!(Error::Parsing(format!(
+                        "unexpected cold start = {cold:?} in attachments"
+                    )))
+                }
+            };
+
+            let mut extractor = Extractor::new(&self.ims[offset..], domain, &gvrsn);
+            attachments.append(&mut extractor.next_groups()?);
+            gvrsn = extractor.gvrsn();
+            framed |= extractor.framed();
+            offset += extractor.offset();
+        }
+
+        self.ims.drain(..offset);
+        self.gvrsn = gvrsn;
+        Ok(Message { body, attachments })
+    }
+
+    /// Discards bytes up to the next frame that may start a message, after a parse error.
+    /// The first byte is always discarded. Returns the number of bytes discarded.
+    pub fn resync(&mut self) -> usize {
+        if self.ims.is_empty() {
+            return 0;
+        }
+
+        let skipped = match resync(&self.ims[1..]) {
+            Some(offset) => offset + 1,
+            None => self.ims.len(),
+        };
+        self.ims.drain(..skipped);
+        skipped
+    }
+
+    /// Parses every complete message in the buffer, leaving any partial message in place.
+    pub fn parse_all(&mut self) -> Result<Vec<Message>> {
+        let mut messages = vec![];
+        loop {
+            match self.parse_one() {
+                Ok(message) => messages.push(message),
+                Err(e) if shortage(&e) => return Ok(messages),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Body, Parser};
+    use crate::core::{
+        attachments::Group,
+        common::{Serialage, Version},
+        counter::{tables as counter, Counter},
+        eventing::incept,
+        indexer::Indexer,
+        matter::{tables as matter, Matter},
+        sadder::Sadder,
+        siger::Siger,
+        signer::Signer,
+    };
+    use crate::error::Error;
+    use rstest::rstest;
+
+    fn event() -> (Vec<u8>, Vec<Siger>) {
+        let signers: Vec<Signer> =
+            (0..2u8).map(|i| Signer::new_with_raw(&[i; 32], Some(true), None).unwrap()).collect();
+        let keys: Vec<String> = signers.iter().map(|s| s.verfer().qb64().unwrap()).collect();
+        let keys: Vec<&str> = keys.iter().map(|k| k.as_str()).collect();
+        let serder = incept(
+            &keys,
+            Some(&dat!(2)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .unwrap();
+        let sigers = signers
+            .iter()
+            .enumerate()
+            .map(|(i, s)| s.sign_indexed(&serder.raw(), false, i as u32, None).unwrap())
+            .collect();
+
+        (serder.raw(), sigers)
+    }
+
+    fn text(raw: &[u8], sigers: &[Siger]) -> Vec<u8> {
+        let mut msg = raw.to_vec();
+        msg.append(
+            &mut Counter::new_with_code_and_count(
+                counter::Codex::ControllerIdxSigs,
+                sigers.len() as u32,
+            )
+            .unwrap()
+            .qb64b()
+            .unwrap(),
+        );
+        for siger in sigers {
+            msg.append(&mut siger.qb64b().unwrap());
+        }
+        msg
+    }
+
+    // parsed sigers carry no verfer, so compare them by their qualified encoding
+    fn qb64s(groups: &[Group]) -> Vec<String> {
+        groups
+            .iter()
+            .flat_map(|group| match group {
+                Group::ControllerIdxSigs(sigers) => sigers.iter().map(|s| s.qb64().unwrap()),
+                _ => panic!("unexpected group {group:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_text_and_binary() {
+        let (raw, sigers) = event();
+
+        let mut ims = text(&raw, &sigers);
+        ims.append(&mut raw.clone());
+        ims.append(
+            &mut Counter::new_with_code_and_count(counter::Codex::ControllerIdxSigs, 2)
+                .unwrap()
+                .qb2()
+                .unwrap(),
+        );
+        for siger in &sigers {
+            ims.append(&mut siger.qb2().unwrap());
+        }
+
+        let mut parser = Parser::new_with_ims(&ims);
+        parser.finish();
+        let messages = parser.parse_all().unwrap();
+        assert_eq!(messages.len(), 2);
+        for message in messages {
+            assert!(matches!(message.body, Body::Serder(_)));
+            assert_eq!(message.body.raw(), raw);
+            assert_eq!(
+                qb64s(&message.attachments),
+                qb64s(&[Group::ControllerIdxSigs(sigers.clone())])
+            );
+        }
+    }
+
+    #[rstest]
+    fn parse_binary_kinds(#[values(Serialage::MGPK, Serialage::CBOR)] kind: &str) {
+        let serder = incept(
+            &["BEy_EvE8OUMqj0AgCJ3wOCOrIVHVtwubYAysPyaAv9VI"],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(kind),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let (_, sigers) = event();
+
+        let mut ims = text(&serder.raw(), &sigers);
+        ims.append(&mut serder.raw());
+
+        let mut parser = Parser::new_with_ims(&ims[..ims.len() - 1]);
+        let message = parser.parse_one().unwrap();
+        assert_eq!(message.body, Body::Serder(serder.clone()));
+        assert_eq!(qb64s(&message.attachments), qb64s(&[Group::ControllerIdxSigs(sigers)]));
+
+        let e = parser.parse_one().unwrap_err();
+        assert!(matches!(e.downcast_ref::<Error>(), Some(Error::Shortage(_))));
+        parser.extend(&ims[ims.len() - 1..]);
+        let e = parser.parse_one().unwrap_err();
+        assert!(matches!(e.downcast_ref::<Error>(), Some(Error::Shortage(_))));
+        parser.finish();
+        assert_eq!(parser.parse_one().unwrap().body, Body::Serder(serder));
+    }
+
+    #[test]
+    fn parse_incrementally() {
+        let (raw, sigers) = event();
+        let ims = text(&raw, &sigers);
+        let inner = ims[raw.len()..].to_vec();
+
+        // frame the attachments so the parser waits for all of them
+        let mut framed = raw.clone();
+        framed.append(
+            &mut Counter::new_with_code_and_count(
+                counter::Codex::AttachedMaterialQuadlets,
+                inner.len() as u32 / 4,
+            )
+            .unwrap()
+            .qb64b()
+            .unwrap(),
+        );
+        framed.extend_from_slice(&inner);
+
+        let mut parser = Parser::new();
+        for chunk in framed.chunks(7) {
+            assert!(parser.parse_all().unwrap().is_empty());
+            parser.extend(chunk);
+        }
+
+        let e = Parser::new_with_ims(&framed[..framed.len() - 1]).parse_one().unwrap_err();
+        assert!(matches!(e.downcast_ref::<Error>(), Some(Error::Shortage(_))));
+
+        let message = parser.parse_one().unwrap();
+        assert_eq!(qb64s(&message.attachments), qb64s(&[Group::ControllerIdxSigs(sigers)]));
+        assert!(parser.ims().is_empty());
+    }
+
+    #[test]
+    fn parse_unframed_incrementally() {
+        let (raw, sigers) = event();
+        let ims = text(&raw, &sigers);
+
+        // unframed attachments may continue in the next chunk, even when a chunk ends on the
+        // body or on a group boundary
+        let mut parser = Parser::new();
+        for chunk in ims.chunks(7) {
+            parser.extend(chunk);
+            assert!(parser.parse_all().unwrap().is_empty());
+        }
+        for end in [raw.len(), raw.len() + 4 + 88] {
+            let e = Parser::new_with_ims(&ims[..end]).parse_one().unwrap_err();
+            assert!(matches!(e.downcast_ref::<Error>(), Some(Error::Shortage(_))));
+        }
+
+        parser.finish();
+        let message = parser.parse_one().unwrap();
+        assert_eq!(message.body.raw(), raw);
+        assert_eq!(qb64s(&message.attachments), qb64s(&[Group::ControllerIdxSigs(sigers)]));
+        assert!(parser.ims().is_empty());
+    }
+
+    #[test]
+    fn parse_genus_version() {
+        let (raw, sigers) = event();
+        let gvrsn = Version { major: 2, minor: 0 };
+        let sigs: Vec<u8> = sigers.iter().flat_map(|s| s.qb64b().unwrap()).collect();
+        let count = sigs.len() as u32 / 4;
+
+        let mut ims = Counter::new_with_genus_version(&gvrsn).unwrap().qb64b().unwrap();
+        for code in
+            [counter::Codex_2_0::ControllerIdxSigs, counter::Codex_2_0::BigControllerIdxSigs]
+        {
+            ims.extend_from_slice(&raw);
+            ims.append(
+                &mut Counter::new_with_gvrsn(
+                    Some(count),
+                    None,
+                    Some(code),
+                    None,
+                    None,
+                    None,
+                    &gvrsn,
+                )
+                .unwrap()
+                .qb64b()
+                .unwrap(),
+            );
+            ims.extend_from_slice(&sigs);
+        }
+
+        let mut parser = Parser::new_with_ims(&ims);
+        parser.finish();
+        let messages = parser.parse_all().unwrap();
+        assert_eq!(messages.len(), 2);
+        for message in messages {
+            assert_eq!(message.body.raw(), raw);
+            assert_eq!(
+                qb64s(&message.attachments),
+                qb64s(&[Group::ControllerIdxSigs(sigers.clone())])
+            );
+        }
+        assert_eq!(parser.gvrsn(), gvrsn);
+
+        // the version only sticks once a message has been parsed
+        let mut parser = Parser::new_with_ims(&ims[..8 + raw.len() - 1]);
+        assert!(parser.parse_one().is_err());
+        assert_eq!(parser.gvrsn(), Version { major: 1, minor: 0 });
+    }
+
+    #[test]
+    fn unhappy_paths() {
+        let (raw, sigers) = event();
+        let ims = text(&raw, &sigers);
+
+        assert!(Parser::new_with_ims(&ims[raw.len()..]).parse_one().is_err());
+        assert!(Parser::new_with_ims(b"not a message").parse_one().is_err());
+
+        let mut ims = raw.clone();
+        ims.extend_from_slice(b"_AAA");
+        assert!(Parser::new_with_ims(&ims).parse_one().is_err());
+
+        let e = Parser::new().parse_one().unwrap_err();
+        assert!(matches!(e.downcast_ref::<Error>(), Some(Error::Shortage(_))));
+    }
+
+    #[test]
+    fn resync() {
+        let (raw, sigers) = event();
+        let msg = text(&raw, &sigers);
+
+        let mut ims = b"garbage{-AAB".to_vec();
+        ims.extend_from_slice(&msg);
+        ims.extend_from_slice(b"\r\n");
+        ims.extend_from_slice(&msg);
+
+        let mut parser = Parser::new_with_ims(&ims);
+        parser.finish();
+        assert!(parser.parse_one().is_err());
+        assert_eq!(parser.resync(), 7);
+        assert!(parser.parse_one().is_err());
+        assert_eq!(parser.resync(), 5);
+
+        // whitespace ends the attachments of a message and is skipped before the next one
+        let message = parser.parse_one().unwrap();
+        assert_eq!(message.body.raw(), raw);
+        assert_eq!(message.attachments.len(), 1);
+        assert_eq!(parser.ims().len(), msg.len() + 2);
+        let message = parser.parse_one().unwrap();
+        assert_eq!(message.body.raw(), raw);
+        assert_eq!(message.attachments.len(), 1);
+        assert!(parser.ims().is_empty());
+        assert_eq!(parser.resync(), 0);
+    }
+}
@@ -171,7 +171,7 @@ impl Pather {
         } else if val.to_map().is_ok() || val.to_vec().is_ok() {
             val.to_json()
         } else {
-            return err!(Error::Value("bad tail value".to_string()));
+            err!(Error::Value("bad tail value".to_string()))
         }
     }
 
@@ -267,14 +267,11 @@ impl Matter for Pather {
 #[cfg(test)]
 mod test {
     use super::Pather;
-    use crate::{
-        core::{
-            bexter::Bext,
-            matter::{tables as matter, Matter},
-            saider::Saider,
-            serder::Serder,
-        },
-        data::dat,
+    use crate::core::{
+        bexter::Bext,
+        matter::{tables as matter, Matter},
+        saider::Saider,
+        serder::Serder,
     };
 
     #[test]
@@ -29,10 +29,12 @@ fn validate_code(code: &str) -> Result<()> {
         matter::Codex::Ed25519N,
         matter::Codex::ECDSA_256k1N,
         matter::Codex::ECDSA_256r1N,
+        matter::Codex::Ed448N,
         // transferable
         matter::Codex::Ed25519,
         matter::Codex::ECDSA_256k1,
         matter::Codex::ECDSA_256r1,
+        matter::Codex::Ed448,
         // digests
         matter::Codex::Blake3_256,
         matter::Codex::Blake3_512,
@@ -63,13 +65,15 @@ fn derive(ked: &Value, code: &str) -> Result<(Vec<u8>, String)> {
 
     match code {
         // non-transferable codes
-        matter::Codex::Ed25519N | matter::Codex::ECDSA_256k1N | matter::Codex::ECDSA_256r1N => {
-            derive_nontransferable(ked, code)
-        }
+        matter::Codex::Ed25519N
+        | matter::Codex::ECDSA_256k1N
+        | matter::Codex::ECDSA_256r1N
+        | matter::Codex::Ed448N => derive_nontransferable(ked, code),
         // transferable codes
-        matter::Codex::Ed25519 | matter::Codex::ECDSA_256k1 | matter::Codex::ECDSA_256r1 => {
-            derive_transferable(ked, code)
-        }
+        matter::Codex::Ed25519
+        | matter::Codex::ECDSA_256k1
+        | matter::Codex::ECDSA_256r1
+        | matter::Codex::Ed448 => derive_transferable(ked, code),
         // digests
         matter::Codex::Blake3_256
         | matter::Codex::Blake3_512
@@ -339,13 +343,15 @@ impl Prefixer {
 
         match self.code().as_str() {
             // non-transferable codes
-            matter::Codex::Ed25519N | matter::Codex::ECDSA_256k1N | matter::Codex::ECDSA_256r1N => {
-                verify_nontransferable(ked, &self.qb64()?, prefixed)
-            }
+            matter::Codex::Ed25519N
+            | matter::Codex::ECDSA_256k1N
+            | matter::Codex::ECDSA_256r1N
+            | matter::Codex::Ed448N => verify_nontransferable(ked, &self.qb64()?, prefixed),
             // transferable codes
-            matter::Codex::Ed25519 | matter::Codex::ECDSA_256k1 | matter::Codex::ECDSA_256r1 => {
-                verify_transferable(ked, &self.qb64()?, prefixed)
-            }
+            matter::Codex::Ed25519
+            | matter::Codex::ECDSA_256k1
+            | matter::Codex::ECDSA_256r1
+            | matter::Codex::Ed448 => verify_transferable(ked, &self.qb64()?, prefixed),
             // digests
             matter::Codex::Blake3_256
             | matter::Codex::Blake3_512
@@ -390,16 +396,13 @@ impl Matter for Prefixer {
 
 #[cfg(test)]
 mod test {
-    use crate::{
-        core::{
-            common::{sizeify, versify, Ilkage, Serialage, CURRENT_VERSION},
-            diger::Diger,
-            matter::{tables as matter, Matter},
-            prefixer::Prefixer,
-            signer::Signer,
-            verfer::Verfer,
-        },
-        data::dat,
+    use crate::core::{
+        common::{sizeify, versify, Ilkage, Serialage, CURRENT_VERSION},
+        diger::Diger,
+        matter::{tables as matter, Matter},
+        prefixer::Prefixer,
+        signer::Signer,
+        verfer::Verfer,
     };
     use rstest::rstest;
 
@@ -662,6 +665,24 @@ mod test {
         assert!(prefixer.verify(&ked, None).is_err());
     }
 
+    #[rstest]
+    fn ed448_basic_derivation(#[values(true, false)] transferable: bool) {
+        let signer =
+            Signer::new_with_defaults(Some(transferable), Some(matter::Codex::Ed448_Seed)).unwrap();
+        let verfer = signer.verfer();
+        let ked = dat!({
+            "k": [&verfer.qb64().unwrap()],
+            "n": "",
+            "t": "icp",
+        });
+
+        let prefixer =
+            Prefixer::new(Some(&ked), None, Some(&verfer.code()), None, None, None, None).unwrap();
+        assert_eq!(prefixer.qb64().unwrap(), verfer.qb64().unwrap());
+        assert_eq!(prefixer.transferable(), transferable);
+        assert!(prefixer.verify(&ked, Some(false)).unwrap());
+    }
+
     #[test]
     fn verify_unhappy_non_transferable() {
         let pre_n = "BKxy2sgzfplyr-tgwIxS19f2OchFHtLwPWD3v4oYimBx";
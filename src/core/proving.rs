@@ -0,0 +1,450 @@
+use crate::{
+    core::{
+        common::{versify, Identage, Ids, Serialage, Version, CURRENT_VERSION},
+        creder::{compacted, saidify_section, Creder},
+        matter::{tables as matter, Matter},
+        prefixer::Prefixer,
+        saider::Saider,
+        salter::Salter,
+    },
+    data::{Object, Value},
+    error::{err, Error, Result},
+};
+
+// a section with a said field first, saidified along with any edge or rule blocks nested in it
+fn section(label: &str, block: &Value, code: &str, kind: &str) -> Result<Value> {
+    let map = match block {
+        Value::Object(map) => map,
+        _ => return err!(Error::Value(format!("invalid section = {label}, must be a map"))),
+    };
+
+    let mut section = Object::new();
+    section.insert(Ids::d.to_string(), map.get(Ids::d).cloned().unwrap_or(dat!("")));
+    for (key, value) in map {
+        section.insert(key.clone(), value.clone());
+    }
+
+    saidify_section(label, &Value::Object(section), code, kind)
+}
+
+/// Builds an ACDC credential issued by `issuer` under schema `schema`, returning its full form
+/// and its most compact form.
+///
+/// The attribute section holds `data` after a said, a nonce when `private`, and the
+/// `recipient`. The attribute, edge and rule sections, and the edge and rule blocks nested in
+/// them that have a `d` field, are saidified before the top level. Attribute values are left
+/// as they are, even when they hold a `d` field. In the compact form each section is
+/// replaced by its said. The top level said is computed over the compact form, so both forms
+/// have the same said.
+#[allow(clippy::too_many_arguments)]
+pub fn credential(
+    issuer: &str,            // issuer identifier prefix qb64
+    schema: &str,            // schema said qb64
+    data: &Value,            // attribute fields
+    recipient: Option<&str>, // issuee identifier prefix qb64
+    status: Option<&str>,    // credential registry identifier prefix qb64
+    edges: Option<&Value>,   // edge section
+    rules: Option<&Value>,   // rule section
+    private: Option<bool>,   // adds nonces to the credential and its attributes
+    salt: Option<&str>,      // credential nonce qb64 when private, random when absent
+    version: Option<&Version>,
+    kind: Option<&str>,
+    code: Option<&str>, // said derivation code
+) -> Result<(Creder, Creder)> {
+    let kind = kind.unwrap_or(Serialage::JSON);
+    let code = code.unwrap_or(matter::Codex::Blake3_256);
+    let private = private.unwrap_or(false);
+
+    Prefixer::new_with_qb64(issuer)?;
+    Saider::new_with_qb64(schema)?;
+
+    let mut subject = dat!({ "d": "" });
+    if private {
+        subject[Ids::u] = dat!(&Salter::new_with_defaults(None)?.qb64()?);
+    }
+    if let Some(recipient) = recipient {
+        Prefixer::new_with_qb64(recipient)?;
+        subject[Ids::i] = dat!(recipient);
+    }
+    for (key, value) in data.to_map()? {
+        if [Ids::d, Ids::u, Ids::i].contains(&key.as_str()) {
+            return err!(Error::Value(format!("reserved attribute label = {key}")));
+        }
+        subject[key.as_str()] = value;
+    }
+    let subject = section(Ids::a, &subject, code, kind)?;
+
+    let nonce = match (private, salt) {
+        (false, _) => None,
+        (true, Some(salt)) => Some(salt.to_string()),
+        (true, None) => Some(Salter::new_with_defaults(None)?.qb64()?),
+    };
+
+    assemble(
+        issuer,
+        schema,
+        (Ids::a, subject),
+        status,
+        edges,
+        rules,
+        nonce.as_deref(),
+        version,
+        kind,
+        code,
+    )
+}
+
+/// Builds a selectively disclosable ACDC credential issued by `issuer` under schema `schema`,
+/// returning its full form and its most compact form.
+///
+/// Each of the `attributes` is blinded in a block of its own with a salty nonce, see
+/// `Creder::blind`, and the attribute aggregate `A` holds the aggregate said followed by the
+/// blocks. In the compact form the aggregate is replaced by its said, and both forms have the
+/// same said. A holder discloses a
+/// subset of the attributes with `Creder::disclose`.
+#[allow(clippy::too_many_arguments)]
+pub fn selective(
+    issuer: &str,            // issuer identifier prefix qb64
+    schema: &str,            // schema said qb64
+    attributes: &Value,      // attribute fields, each blinded in a block of its own
+    status: Option<&str>,    // credential registry identifier prefix qb64
+    edges: Option<&Value>,   // edge section
+    rules: Option<&Value>,   // rule section
+    salter: Option<&Salter>, // derives the attribute nonces, random when absent
+    salt: Option<&str>,      // credential nonce qb64, none when absent
+    version: Option<&Version>,
+    kind: Option<&str>,
+    code: Option<&str>, // said derivation code
+) -> Result<(Creder, Creder)> {
+    let kind = kind.unwrap_or(Serialage::JSON);
+    let code = code.unwrap_or(matter::Codex::Blake3_256);
+
+    Prefixer::new_with_qb64(issuer)?;
+    Saider::new_with_qb64(schema)?;
+
+    let aggregate = Creder::blind(attributes, salter, Some(code), Some(kind))?;
+
+    assemble(issuer, schema, (Ids::A, aggregate), status, edges, rules, salt, version, kind, code)
+}
+
+// the credential with its attribute section `attributes` under its label, saidified at the
+// top level over its most compact form so that the full and compact forms share the said
+#[allow(clippy::too_many_arguments)]
+fn assemble(
+    issuer: &str,
+    schema: &str,
+    attributes: (&str, Value),
+    status: Option<&str>,
+    edges: Option<&Value>,
+    rules: Option<&Value>,
+    nonce: Option<&str>,
+    version: Option<&Version>,
+    kind: &str,
+    code: &str,
+) -> Result<(Creder, Creder)> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let vs = &versify(Some(Identage::ACDC), Some(version), Some(kind), Some(0))?;
+
+    let mut vc = dat!({ "v": vs, "d": "" });
+    if let Some(nonce) = nonce {
+        vc[Ids::u] = dat!(nonce);
+    }
+    vc[Ids::i] = dat!(issuer);
+    if let Some(status) = status {
+        Prefixer::new_with_qb64(status)?;
+        vc[Ids::ri] = dat!(status);
+    }
+    vc[Ids::s] = dat!(schema);
+
+    let (label, attributes) = attributes;
+    vc[label] = attributes;
+
+    if let Some(edges) = edges {
+        vc[Ids::e] = section(Ids::e, edges, code, kind)?;
+    }
+    if let Some(rules) = rules {
+        vc[Ids::r] = section(Ids::r, rules, code, kind)?;
+    }
+
+    let (saider, _) = Saider::saidify(&compacted(&vc)?, Some(code), Some(kind), None, None)?;
+    vc[Ids::d] = dat!(&saider.qb64()?);
+    let creder = Creder::new_with_ked(&vc, Some(code), Some(kind))?;
+    let compact = creder.compact()?;
+
+    Ok((creder, compact))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{credential, selective};
+    use crate::core::{
+        common::{Ids, Tierage},
+        matter::Matter,
+        sadder::Sadder,
+        saider::Saider,
+        salter::Salter,
+    };
+
+    const ISSUER: &str = "ENayINhHQnx6525EpcTmkvo6ZixiJyiskwkVNbMPohYa";
+    const SCHEMA: &str = "EE5uDJTq5cc6AEdqbyMpvARUjsK_chNdInf3xyRoCBcT";
+    const REGISTRY: &str = "EINZnO3Z30Q7y2oV1sDCQphieRH244-XJFRAbzuFbU7n";
+    const DT: &str = "2023-04-30T00:34:11.853572+00:00";
+
+    fn edges() -> crate::data::Value {
+        dat!({
+            "acceptedBlock": {
+                "d": "",
+                "n": "EE_Wrv2OHqIOptEni3mE3Ckc4C6jO1RvgtxdpDZBiuB0",
+                "s": "EDiWb-53cI8FBPOpF69LrLCSElNjG-BAChHp2-OsLmbC"
+            }
+        })
+    }
+
+    #[test]
+    fn full_and_compact() {
+        let (creder, compact) = credential(
+            ISSUER,
+            SCHEMA,
+            &dat!({ "dt": DT }),
+            None,
+            Some(REGISTRY),
+            Some(&edges()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        // the nested saids match a credential issued by keripy, the top level said is computed
+        // over the compact form
+        assert_eq!(creder.said().unwrap(), "EBm384Rj1mL6Ajb-aEH2O_a41jwntqi-4j0EvV9HjTfW");
+        assert_eq!(
+            creder.subject(),
+            dat!({ "d": "EOsCUbK6Ve7qb-h15ljNyvVhLz2rq6iaCcA86AAoeZyX", "dt": DT })
+        );
+        assert_eq!(
+            creder.chains().unwrap()[Ids::d].to_string().unwrap(),
+            "ECuynR9pRY6A6dWRlc2DTSF7AWY2a-w-6qhx7vd-pWT-"
+        );
+        assert_eq!(
+            creder.ked().to_map().unwrap().keys().collect::<Vec<_>>(),
+            vec!["v", "d", "i", "ri", "s", "a", "e"]
+        );
+        assert_eq!(creder.status().unwrap().unwrap(), REGISTRY);
+        assert!(creder.verify().unwrap());
+
+        assert_eq!(compact.subject(), dat!("EOsCUbK6Ve7qb-h15ljNyvVhLz2rq6iaCcA86AAoeZyX"));
+        assert_eq!(compact.chains().unwrap(), dat!("ECuynR9pRY6A6dWRlc2DTSF7AWY2a-w-6qhx7vd-pWT-"));
+        assert_eq!(compact.issuer().unwrap(), ISSUER);
+        assert!(compact.size() < creder.size());
+        assert_eq!(compact.said().unwrap(), creder.said().unwrap());
+        assert!(compact.verify().unwrap());
+        assert_eq!(compact.compact().unwrap(), compact);
+
+        // tampering with a nested block breaks the credential
+        let mut ked = creder.ked();
+        ked[Ids::e]["acceptedBlock"]["s"] = dat!(SCHEMA);
+        let (_, ked) = Saider::saidify(&ked, None, None, None, None).unwrap();
+        let tampered = crate::core::creder::Creder::new_with_ked(&ked, None, None).unwrap();
+        assert!(!tampered.verify().unwrap());
+    }
+
+    #[test]
+    fn private_with_rules() {
+        let rules = dat!({
+            "usageDisclaimer": { "l": "Usage of a valid credential does not assert trust." },
+            "issuanceDisclaimer": { "d": "", "l": "All information is accurate." }
+        });
+        let (creder, compact) = credential(
+            ISSUER,
+            SCHEMA,
+            &dat!({ "LEI": "254900OPPU84GM83MG36" }),
+            Some(REGISTRY),
+            None,
+            None,
+            Some(&rules),
+            Some(true),
+            Some("0AAB2P4dlcMqwU6Y9zWwu6Rj"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let ked = creder.ked();
+        assert_eq!(
+            ked.to_map().unwrap().keys().collect::<Vec<_>>(),
+            vec!["v", "d", "u", "i", "s", "a", "r"]
+        );
+        assert_eq!(ked[Ids::u], dat!("0AAB2P4dlcMqwU6Y9zWwu6Rj"));
+        assert_eq!(
+            creder.subject().to_map().unwrap().keys().collect::<Vec<_>>(),
+            vec!["d", "u", "i", "LEI"]
+        );
+        assert_eq!(creder.subject()[Ids::i], dat!(REGISTRY));
+        let said = Saider::saidify(&ked[Ids::r]["issuanceDisclaimer"], None, None, None, None)
+            .unwrap()
+            .0
+            .qb64()
+            .unwrap();
+        assert_eq!(ked[Ids::r]["issuanceDisclaimer"][Ids::d], dat!(&said));
+        assert!(creder.verify().unwrap());
+        assert_eq!(compact.ked()[Ids::r], ked[Ids::r][Ids::d]);
+
+        // the attribute nonce is random
+        let (other, _) = credential(
+            ISSUER,
+            SCHEMA,
+            &dat!({ "LEI": "254900OPPU84GM83MG36" }),
+            Some(REGISTRY),
+            None,
+            None,
+            Some(&rules),
+            Some(true),
+            Some("0AAB2P4dlcMqwU6Y9zWwu6Rj"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_ne!(other.subject()[Ids::d], creder.subject()[Ids::d]);
+    }
+
+    #[test]
+    fn attribute_values() {
+        // a `d` field in an attribute value is payload, not a block to saidify
+        let data = dat!({ "x": { "d": "foo", "l": [{ "d": "bar" }] } });
+        let (creder, compact) =
+            credential(ISSUER, SCHEMA, &data, None, None, None, None, None, None, None, None, None)
+                .unwrap();
+        assert_eq!(creder.subject()["x"], data["x"]);
+        let (said, _) = Saider::saidify(&creder.subject(), None, None, None, None).unwrap();
+        assert_eq!(creder.subject()[Ids::d], dat!(&said.qb64().unwrap()));
+        assert!(creder.verify().unwrap());
+        assert!(compact.verify().unwrap());
+
+        let salter = Salter::new_with_raw(b"0123456789abcdef", None, Some(Tierage::min)).unwrap();
+        let (creder, _) = selective(
+            ISSUER,
+            SCHEMA,
+            &data,
+            None,
+            None,
+            None,
+            Some(&salter),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(creder.ked()[Ids::A][1]["x"], data["x"]);
+        assert!(creder.verify().unwrap());
+    }
+
+    #[test]
+    fn credential_unhappy_paths() {
+        let data = dat!({ "dt": DT });
+        let build = |issuer: &str, schema: &str, data: &crate::data::Value| {
+            credential(issuer, schema, data, None, None, None, None, None, None, None, None, None)
+        };
+        assert!(build("", SCHEMA, &data).is_err());
+        assert!(build(ISSUER, "", &data).is_err());
+        assert!(build(ISSUER, SCHEMA, &dat!({ "i": ISSUER })).is_err());
+        assert!(build(ISSUER, SCHEMA, &dat!({ "d": "" })).is_err());
+        assert!(build(ISSUER, SCHEMA, &dat!(["dt"])).is_err());
+        assert!(credential(
+            ISSUER,
+            SCHEMA,
+            &data,
+            None,
+            None,
+            Some(&dat!(["not", "a", "map"])),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn selective_disclosure() {
+        let attributes = dat!({ "LEI": "254900OPPU84GM83MG36", "dt": DT });
+        let salter = Salter::new_with_raw(b"0123456789abcdef", None, Some(Tierage::min)).unwrap();
+        let (creder, compact) = selective(
+            ISSUER,
+            SCHEMA,
+            &attributes,
+            Some(REGISTRY),
+            Some(&edges()),
+            None,
+            Some(&salter),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let ked = creder.ked();
+        assert_eq!(
+            ked.to_map().unwrap().keys().collect::<Vec<_>>(),
+            vec!["v", "d", "i", "ri", "s", "A", "e"]
+        );
+        let aggregate =
+            crate::core::creder::Creder::blind(&attributes, Some(&salter), None, None).unwrap();
+        assert_eq!(ked[Ids::A], aggregate);
+        assert_eq!(
+            creder.chains().unwrap()[Ids::d].to_string().unwrap(),
+            "ECuynR9pRY6A6dWRlc2DTSF7AWY2a-w-6qhx7vd-pWT-"
+        );
+        assert!(creder.verify().unwrap());
+
+        assert_eq!(compact.ked()[Ids::A], aggregate[0]);
+        assert!(compact.verify().unwrap());
+
+        // the holder discloses the date only
+        let disclosure = creder.disclose(&["dt"]).unwrap();
+        assert_eq!(disclosure[1], aggregate[1][Ids::d]);
+        assert_eq!(disclosure[2], aggregate[2]);
+        assert!(compact.disclosed(&disclosure).unwrap());
+
+        let (other, _) = selective(
+            ISSUER,
+            SCHEMA,
+            &attributes,
+            None,
+            None,
+            None,
+            None,
+            Some("0AAB2P4dlcMqwU6Y9zWwu6Rj"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(other.ked()[Ids::u], dat!("0AAB2P4dlcMqwU6Y9zWwu6Rj"));
+        assert!(!compact.disclosed(&other.disclose(&["dt"]).unwrap()).unwrap());
+
+        assert!(selective(
+            ISSUER,
+            SCHEMA,
+            &dat!({ "d": "" }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err());
+    }
+}
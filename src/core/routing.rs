@@ -0,0 +1,512 @@
+use indexmap::IndexMap;
+
+use crate::{
+    core::{
+        attachments::Group,
+        common::{Ids, Ilkage},
+        dater::Dater,
+        kever::verify_trans,
+        matter::Matter,
+        parser::{Body, Message},
+        sadder::Sadder,
+        saider::Saider,
+        serder::Serder,
+        verfer::Verfer,
+    },
+    error::{err, Error, Result},
+};
+
+// the route of a routed message, such as a qry, rpy or exn
+fn route(serder: &Serder) -> Result<String> {
+    let ked = serder.ked();
+    let label = Ids::r;
+    if !ked.to_map()?.contains_key(label) {
+        return err!(Error::Validation(format!(
+            "unrouted message said = {said}",
+            said = serder.said()?
+        )));
+    }
+
+    ked[label].to_string()
+}
+
+// the parameters captured by the {name} segments of `template` when it matches `route`
+fn matches(template: &str, route: &str) -> Option<IndexMap<String, String>> {
+    let templates = template.split('/').collect::<Vec<_>>();
+    let segments = route.split('/').collect::<Vec<_>>();
+    if templates.len() != segments.len() {
+        return None;
+    }
+
+    let mut params = IndexMap::new();
+    for (template, segment) in templates.iter().zip(segments) {
+        match template.strip_prefix('{').and_then(|name| name.strip_suffix('}')) {
+            Some(name) if !segment.is_empty() => {
+                params.insert(name.to_string(), segment.to_string());
+            }
+            Some(_) => return None,
+            None if *template != segment => return None,
+            None => (),
+        }
+    }
+
+    Some(params)
+}
+
+/// An application's handler for the messages dispatched to it by a Router.
+pub trait Handler {
+    /// Handles `message`, given the parameters captured from its route by the route template
+    /// the handler is registered on.
+    fn handle(&mut self, message: &Message, params: &IndexMap<String, String>) -> Result<()>;
+}
+
+/// Dispatches routed messages, such as qry, rpy and exn messages, to the handlers registered on
+/// their routes.
+///
+/// Route templates match routes segment by segment, where a `{name}` segment matches any
+/// non-empty segment and captures it as the parameter `name`. So `/end/role/{action}` matches
+/// `/end/role/add` with `action` = `add`. Templates are tried in the order they were added.
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<(String, Box<dyn Handler>)>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `handler` on the route `template`.
+    pub fn add(&mut self, template: &str, handler: Box<dyn Handler>) -> Result<()> {
+        if self.routes.iter().any(|(t, _)| t == template) {
+            return err!(Error::Value(format!("duplicate route = {template}")));
+        }
+
+        self.routes.push((template.to_string(), handler));
+        Ok(())
+    }
+
+    /// The route templates, in the order they are tried.
+    pub fn routes(&self) -> Vec<String> {
+        self.routes.iter().map(|(template, _)| template.clone()).collect()
+    }
+
+    /// Dispatches `message` to the first handler whose route template matches its route.
+    pub fn dispatch(&mut self, message: &Message) -> Result<()> {
+        let serder = match &message.body {
+            Body::Serder(serder) => serder,
+            Body::Creder(_) => {
+                return err!(Error::Validation("unexpected ACDC for routing".to_string()))
+            }
+        };
+        let route = route(serder)?;
+
+        for (template, handler) in &mut self.routes {
+            if let Some(params) = matches(template, &route) {
+                return handler.handle(message, &params);
+            }
+        }
+
+        err!(Error::Validation(format!("unknown route = {route}")))
+    }
+}
+
+/// Reply (rpy) verifier. Verifies the signatures on replies and only accepts a reply from a
+/// signer on a route when its datetime is later than that of the last reply accepted from the
+/// same signer on the same route, so that stale or replayed replies cannot roll back what an
+/// earlier reply established.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Revery {
+    accepted: IndexMap<(String, String), Dater>,
+}
+
+impl Revery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verifies the reply `message` and its signatures, returning the prefixes of its signers.
+    ///
+    /// Transferable signatures are checked against the keys and threshold of the establishment
+    /// event in `validators` they are sealed to, which must be the latest establishment event of
+    /// the signer in `validators`. The reply is rejected, and nothing is recorded,
+    /// when any signature is invalid or the reply is not later than the last reply accepted from
+    /// any of its signers on its route.
+    pub fn verify(&mut self, message: &Message, validators: &[Serder]) -> Result<Vec<String>> {
+        let rpy = match &message.body {
+            Body::Serder(serder) => serder,
+            Body::Creder(_) => {
+                return err!(Error::Validation("reply is not a KERI message".to_string()))
+            }
+        };
+
+        let ked = rpy.ked();
+        let label = Ids::t;
+        let ilk = ked[label].to_string()?;
+        if ilk != Ilkage::rpy {
+            return err!(Error::Validation(format!("unexpected ilk = {ilk} for reply")));
+        }
+
+        let said = rpy.said()?;
+        if !Saider::new_with_qb64(&said)?.verify(&ked, Some(true), Some(true), None, None, None)? {
+            return err!(Error::Validation(format!("invalid reply said = {said}")));
+        }
+
+        let route = route(rpy)?;
+        let label = Ids::dt;
+        let dater = Dater::new_with_dts(&ked[label].to_string()?, None)?;
+        let datetime = dater.datetime()?;
+
+        let ser = rpy.raw();
+        let mut signers: Vec<String> = vec![];
+        for group in &message.attachments {
+            match group {
+                Group::NonTransReceiptCouples(couples) => {
+                    for (prefixer, cigar) in couples {
+                        let pre = prefixer.qb64()?;
+                        if prefixer.transferable() {
+                            return err!(Error::Validation(format!(
+                                "transferable signer without seal: pre = {pre}"
+                            )));
+                        }
+
+                        let verfer = Verfer::new_with_qb64b(&prefixer.qb64b()?)?;
+                        if !verfer.verify(&cigar.raw(), &ser)? {
+                            return err!(Error::Validation(format!(
+                                "invalid reply signature: pre = {pre}"
+                            )));
+                        }
+                        if !signers.contains(&pre) {
+                            signers.push(pre);
+                        }
+                    }
+                }
+                Group::TransIdxSigGroups(groups) => {
+                    for (prefixer, seqner, saider, sigers) in groups {
+                        verify_trans(&ser, validators, prefixer, seqner, saider, sigers)?;
+                        let pre = prefixer.qb64()?;
+                        if !signers.contains(&pre) {
+                            signers.push(pre);
+                        }
+                    }
+                }
+                _ => {
+                    return err!(Error::Validation(format!(
+                        "unexpected attachment in reply: group = {group:?}"
+                    )))
+                }
+            }
+        }
+
+        if signers.is_empty() {
+            return err!(Error::Validation(format!("unsigned reply said = {said}")));
+        }
+
+        for pre in &signers {
+            if let Some(accepted) = self.accepted.get(&(pre.clone(), route.clone())) {
+                if datetime <= accepted.datetime()? {
+                    return err!(Error::Validation(format!(
+                        "stale reply on route = {route}: pre = {pre}, dt = {dt}, accepted = {accepted}",
+                        dt = dater.dts()?,
+                        accepted = accepted.dts()?
+                    )));
+                }
+            }
+        }
+
+        for pre in &signers {
+            self.accepted.insert((pre.clone(), route.clone()), dater.clone());
+        }
+
+        Ok(signers)
+    }
+
+    /// The datetime of the last reply accepted from `pre` on `route`.
+    pub fn accepted(&self, pre: &str, route: &str) -> Option<Dater> {
+        self.accepted.get(&(pre.to_string(), route.to_string())).cloned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use indexmap::IndexMap;
+
+    use super::{Handler, Revery, Router};
+    use crate::{
+        core::{
+            attachments::Group,
+            diger::Diger,
+            eventing::{incept, query, reply, rotate},
+            matter::{tables as matter, Matter},
+            parser::{Body, Message},
+            prefixer::Prefixer,
+            sadder::Sadder,
+            saider::Saider,
+            seqner::Seqner,
+            serder::Serder,
+            signer::Signer,
+        },
+        error::{err, Error, Result},
+    };
+
+    const DTS: &[&str] = &[
+        "2023-04-30T00:34:11.853572+00:00",
+        "2023-04-30T00:35:11.853572+00:00",
+        "2023-04-30T00:36:11.853572+00:00",
+    ];
+
+    type Log = Rc<RefCell<Vec<(String, IndexMap<String, String>)>>>;
+
+    struct Recorder {
+        name: String,
+        log: Log,
+    }
+
+    impl Handler for Recorder {
+        fn handle(&mut self, message: &Message, params: &IndexMap<String, String>) -> Result<()> {
+            if let Body::Serder(serder) = &message.body {
+                if serder.ked()["r"].to_string()? == "/ksn/fail" {
+                    return err!(Error::Validation("handler failure".to_string()));
+                }
+            }
+            self.log.borrow_mut().push((self.name.clone(), params.clone()));
+            Ok(())
+        }
+    }
+
+    fn message(serder: &Serder, attachments: Vec<Group>) -> Message {
+        Message { body: Body::Serder(serder.clone()), attachments }
+    }
+
+    fn witnessed(signer: &Signer, rpy: &Serder) -> Message {
+        let couple = (
+            Prefixer::new_with_qb64b(&signer.verfer().qb64b().unwrap()).unwrap(),
+            signer.sign_unindexed(&rpy.raw()).unwrap(),
+        );
+        message(rpy, vec![Group::NonTransReceiptCouples(vec![couple])])
+    }
+
+    #[test]
+    fn router() {
+        let log: Log = Rc::new(RefCell::new(vec![]));
+        let recorder = |name: &str| Box::new(Recorder { name: name.to_string(), log: log.clone() });
+
+        let mut router = Router::new();
+        router.add("/ksn/{aid}", recorder("ksn")).unwrap();
+        router.add("/end/role/{action}", recorder("role")).unwrap();
+        router.add("/end/role/add", recorder("unreachable")).unwrap();
+        router.add("logs", recorder("logs")).unwrap();
+        assert!(router.add("logs", recorder("logs")).is_err());
+        assert_eq!(
+            router.routes(),
+            vec!["/ksn/{aid}", "/end/role/{action}", "/end/role/add", "logs"]
+        );
+
+        let aid = "BEy_EvE8OUMqj0AgCJ3wOCOrIVHVtwubYAysPyaAv9VI";
+        let rpy = reply(&format!("/ksn/{aid}"), None, None, None, None).unwrap();
+        router.dispatch(&message(&rpy, vec![])).unwrap();
+        let rpy = reply("/end/role/add", None, None, None, None).unwrap();
+        router.dispatch(&message(&rpy, vec![])).unwrap();
+        let qry = query("logs", None, Some(&dat!({ "i": aid })), None, None, None).unwrap();
+        router.dispatch(&message(&qry, vec![])).unwrap();
+
+        let params = |pairs: &[(&str, &str)]| {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<IndexMap<_, _>>()
+        };
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                ("ksn".to_string(), params(&[("aid", aid)])),
+                ("role".to_string(), params(&[("action", "add")])),
+                ("logs".to_string(), params(&[])),
+            ]
+        );
+
+        // unknown routes, including partial matches and empty parameters
+        for route in ["/ksn", "/ksn/", "/ksn/a/b", "/loc/scheme", "/logs"] {
+            let rpy = reply(route, None, None, None, None).unwrap();
+            assert!(router.dispatch(&message(&rpy, vec![])).is_err());
+        }
+        // unrouted messages
+        let icp =
+            incept(&[aid], None, None, None, None, None, None, None, None, None, None, None, None)
+                .unwrap();
+        assert!(router.dispatch(&message(&icp, vec![])).is_err());
+        // handler failures are returned
+        let rpy = reply("/ksn/fail", None, None, None, None).unwrap();
+        assert!(router.dispatch(&message(&rpy, vec![])).is_err());
+        assert_eq!(log.borrow().len(), 3);
+    }
+
+    #[test]
+    fn monotonic_replies() {
+        let witness = Signer::new_with_raw(&[1; 32], Some(false), None).unwrap();
+        let other = Signer::new_with_raw(&[2; 32], Some(false), None).unwrap();
+        let wit = witness.verfer().qb64().unwrap();
+        let data = dat!({ "cid": &wit, "role": "witness", "eid": &wit });
+        let rpy = |route: &str, dt: &str| reply(route, Some(&data), Some(dt), None, None).unwrap();
+
+        let mut revery = Revery::new();
+        let first = witnessed(&witness, &rpy("/end/role/add", DTS[1]));
+        assert_eq!(revery.verify(&first, &[]).unwrap(), vec![wit.clone()]);
+        assert_eq!(revery.accepted(&wit, "/end/role/add").unwrap().dts().unwrap(), DTS[1]);
+        assert!(revery.accepted(&wit, "/loc/scheme").is_none());
+
+        // replayed, or earlier than the last accepted reply
+        assert!(revery.verify(&first, &[]).is_err());
+        assert!(revery.verify(&witnessed(&witness, &rpy("/end/role/add", DTS[0])), &[]).is_err());
+
+        // later replies, replies on other routes and replies from other signers are accepted
+        revery.verify(&witnessed(&witness, &rpy("/end/role/add", DTS[2])), &[]).unwrap();
+        revery.verify(&witnessed(&witness, &rpy("/loc/scheme", DTS[0])), &[]).unwrap();
+        revery.verify(&witnessed(&other, &rpy("/end/role/add", DTS[0])), &[]).unwrap();
+        assert_eq!(revery.accepted(&wit, "/end/role/add").unwrap().dts().unwrap(), DTS[2]);
+
+        // a reply stale for any of its signers is rejected for all of them
+        let stale = rpy("/loc/scheme", DTS[0]);
+        let mut both = witnessed(&witness, &stale);
+        both.attachments.push(
+            witnessed(&Signer::new_with_raw(&[3; 32], Some(false), None).unwrap(), &stale)
+                .attachments[0]
+                .clone(),
+        );
+        let before = revery.clone();
+        assert!(revery.verify(&both, &[]).is_err());
+        assert_eq!(revery, before);
+    }
+
+    #[test]
+    fn reply_signatures() {
+        let signer = Signer::new_with_raw(&[4; 32], Some(true), None).unwrap();
+        let icp = incept(
+            &[&signer.verfer().qb64().unwrap()],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .unwrap();
+        let pre = icp.pre().unwrap();
+        let rpy = reply("/end/role/add", None, Some(DTS[0]), None, None).unwrap();
+        let validators = [icp.clone()];
+
+        let sealed = |signer: &Signer, sn: u128| {
+            Group::TransIdxSigGroups(vec![(
+                Prefixer::new_with_qb64(&pre).unwrap(),
+                Seqner::new_with_sn(sn).unwrap(),
+                Saider::new_with_qb64(&icp.said().unwrap()).unwrap(),
+                vec![signer.sign_indexed(&rpy.raw(), false, 0, None).unwrap()],
+            )])
+        };
+
+        let mut revery = Revery::new();
+        let controller = message(&rpy, vec![sealed(&signer, 0)]);
+        // unknown establishment event
+        assert!(revery.verify(&controller, &[]).is_err());
+        assert!(revery.verify(&message(&rpy, vec![sealed(&signer, 1)]), &validators).is_err());
+        // signed with another key
+        let forger = Signer::new_with_raw(&[5; 32], Some(true), None).unwrap();
+        assert!(revery.verify(&message(&rpy, vec![sealed(&forger, 0)]), &validators).is_err());
+        // unsigned, or signed as a controller
+        assert!(revery.verify(&message(&rpy, vec![]), &validators).is_err());
+        let siger = signer.sign_indexed(&rpy.raw(), false, 0, None).unwrap();
+        assert!(revery
+            .verify(&message(&rpy, vec![Group::ControllerIdxSigs(vec![siger])]), &validators)
+            .is_err());
+        // transferable signers must be sealed
+        let couple = (
+            Prefixer::new_with_qb64b(&signer.verfer().qb64b().unwrap()).unwrap(),
+            signer.sign_unindexed(&rpy.raw()).unwrap(),
+        );
+        let unsealed = message(&rpy, vec![Group::NonTransReceiptCouples(vec![couple])]);
+        assert!(revery.verify(&unsealed, &validators).is_err());
+        assert!(revery.accepted(&pre, "/end/role/add").is_none());
+
+        assert_eq!(revery.verify(&controller, &validators).unwrap(), vec![pre.clone()]);
+
+        // not a reply, or a tampered reply
+        let witness = Signer::new_with_raw(&[1; 32], Some(false), None).unwrap();
+        let qry = query("logs", None, None, Some(DTS[1]), None, None).unwrap();
+        assert!(revery.verify(&witnessed(&witness, &qry), &[]).is_err());
+        let mut ked = reply("/end/role/add", None, Some(DTS[1]), None, None).unwrap().ked();
+        ked["r"] = dat!("/loc/scheme");
+        let tampered = Serder::new_with_ked(&ked, None, None).unwrap();
+        assert!(revery.verify(&witnessed(&witness, &tampered), &[]).is_err());
+    }
+
+    #[test]
+    fn stale_keys() {
+        let signer = Signer::new_with_raw(&[6; 32], Some(true), None).unwrap();
+        let next = Signer::new_with_raw(&[7; 32], Some(true), None).unwrap();
+        let ndig = Diger::new_with_ser(&next.verfer().qb64b().unwrap(), None).unwrap();
+        let icp = incept(
+            &[&signer.verfer().qb64().unwrap()],
+            None,
+            Some(&[&ndig.qb64().unwrap()]),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .unwrap();
+        let pre = icp.pre().unwrap();
+        let rot = rotate(
+            &pre,
+            &[&next.verfer().qb64().unwrap()],
+            &icp.said().unwrap(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let rpy = reply("/end/role/add", None, Some(DTS[0]), None, None).unwrap();
+
+        let sealed = |signer: &Signer, est: &Serder| {
+            let group = Group::TransIdxSigGroups(vec![(
+                Prefixer::new_with_qb64(&pre).unwrap(),
+                Seqner::new_with_sn(est.sn().unwrap()).unwrap(),
+                Saider::new_with_qb64(&est.said().unwrap()).unwrap(),
+                vec![signer.sign_indexed(&rpy.raw(), false, 0, None).unwrap()],
+            )]);
+            message(&rpy, vec![group])
+        };
+
+        // keys that were rotated out no longer sign replies, in any order of validators
+        let mut revery = Revery::new();
+        let stale = sealed(&signer, &icp);
+        assert!(revery.verify(&stale, std::slice::from_ref(&icp)).is_ok());
+        let mut revery = Revery::new();
+        assert!(revery.verify(&stale, &[icp.clone(), rot.clone()]).is_err());
+        assert!(revery.verify(&stale, &[rot.clone(), icp.clone()]).is_err());
+        assert!(revery.accepted(&pre, "/end/role/add").is_none());
+
+        let current = sealed(&next, &rot);
+        assert_eq!(revery.verify(&current, &[icp, rot]).unwrap(), vec![pre]);
+    }
+}
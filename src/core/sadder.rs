@@ -1,5 +1,5 @@
 use crate::core::{
-    common::{loads, sizeify, sniff, Ids, Version, CURRENT_VERSION},
+    common::{loads, sizeify, sniff, supported, Ids, Version, CURRENT_VERSION},
     matter::{tables as matter, Matter},
     saider::Saider,
 };
@@ -27,7 +27,7 @@ struct InhaleResult {
 fn inhale(raw: &[u8]) -> Result<InhaleResult> {
     let result = sniff(raw)?;
 
-    if result.version != *CURRENT_VERSION {
+    if !supported(&result.version) {
         return err!(Error::Value(format!(
             "unsupported version = {:?}, current version = {:?}",
             result.version, CURRENT_VERSION
@@ -220,7 +220,7 @@ mod test {
         sadder::Sadder,
         saider::Saider,
     };
-    use crate::data::{dat, Value};
+    use crate::data::Value;
 
     #[derive(Debug, Clone, PartialEq)]
     struct TestSadder {
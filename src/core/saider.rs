@@ -1,7 +1,7 @@
 use crate::core::common::{deversify, dumps, sizeify, Ids, Serialage, DUMMY};
 use crate::core::matter::{tables as matter, Matter};
 use crate::crypto::hash;
-use crate::data::{dat, Value};
+use crate::data::Value;
 use crate::error::{err, Error, Result};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -88,6 +88,36 @@ fn derive(
     Ok((digest, sad))
 }
 
+// saidifies the blocks nested in `value` that have a string `label` field, innermost first
+fn saidify_blocks(
+    value: &Value,
+    code: Option<&str>,
+    kind: Option<&str>,
+    label: &str,
+) -> Result<Value> {
+    let block = |value: &Value| -> Result<Value> {
+        match value {
+            Value::Object(map) if map.get(label).is_some_and(|v| v.to_string().is_ok()) => {
+                Ok(Saider::saidify_nested(value, code, kind, Some(label))?.1)
+            }
+            Value::Object(_) | Value::Array(_) => saidify_blocks(value, code, kind, label),
+            _ => Ok(value.clone()),
+        }
+    };
+
+    Ok(match value {
+        Value::Object(map) => {
+            let mut result = map.clone();
+            for (key, value) in map {
+                result[key] = block(value)?;
+            }
+            Value::Object(result)
+        }
+        Value::Array(values) => Value::Array(values.iter().map(block).collect::<Result<_>>()?),
+        _ => value.clone(),
+    })
+}
+
 impl Saider {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -187,6 +217,22 @@ impl Saider {
         Ok((saider, sad))
     }
 
+    /// Saidifies `sad` and every block nested in it, at any depth, that has a `label` field.
+    /// Inner blocks are saidified first so that each said commits to the saids of the blocks it
+    /// holds. Nested blocks have no version string and are serialized as `kind`, JSON by
+    /// default. Meant for the edge and rule sections of an ACDC, whose nested edges, rules and
+    /// groups carry saids of their own; attribute sections are saidified at the top level only.
+    pub fn saidify_nested(
+        sad: &Value,
+        code: Option<&str>,
+        kind: Option<&str>,
+        label: Option<&str>,
+    ) -> Result<(Saider, Value)> {
+        let label = label.unwrap_or(Ids::d);
+        let sad = saidify_blocks(sad, code, kind, label)?;
+        Self::saidify(&sad, code, kind, Some(label), None)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn verify(
         &self,
@@ -299,7 +345,6 @@ mod test {
     use crate::core::common::{versify, Identage, Ids, Serialage, Version};
     use crate::core::matter::{tables as matter, Matter};
     use crate::core::saider::Saider;
-    use crate::data::dat;
     use rstest::rstest;
 
     #[test]
@@ -618,4 +663,43 @@ mod test {
             .unwrap());
         assert!(Saider::saidify(&dat!({}), None, None, None, None).is_err());
     }
+
+    #[test]
+    fn saidify_nested() {
+        let sad = dat!({
+            "d": "",
+            "acceptedBlock": {
+                "d": "",
+                "n": "EE_Wrv2OHqIOptEni3mE3Ckc4C6jO1RvgtxdpDZBiuB0",
+                "s": "EDiWb-53cI8FBPOpF69LrLCSElNjG-BAChHp2-OsLmbC"
+            }
+        });
+
+        // matches the edge section of a credential issued by keripy
+        let (saider, sad) = Saider::saidify_nested(&sad, None, None, None).unwrap();
+        assert_eq!(saider.qb64().unwrap(), "ECuynR9pRY6A6dWRlc2DTSF7AWY2a-w-6qhx7vd-pWT-");
+        assert_eq!(
+            sad["acceptedBlock"]["d"].to_string().unwrap(),
+            "EOvQJIx58cCC-xB5LIWeApUH80Jxo8WxGNsLb-1HKLcy"
+        );
+        assert!(saider.verify(&sad, Some(true), None, None, None, None).unwrap());
+
+        // blocks nested in arrays and in blocks without a said, innermost first
+        let sad = dat!({
+            "d": "",
+            "l": [{"d": "", "x": {"d": "", "y": 1}}, "z"],
+            "m": {"n": {"d": "", "y": 2}}
+        });
+        let (saider, sad) = Saider::saidify_nested(&sad, None, None, None).unwrap();
+        let (inner, _) = Saider::saidify(&dat!({"d": "", "y": 1}), None, None, None, None).unwrap();
+        assert_eq!(sad["l"][0]["x"]["d"].to_string().unwrap(), inner.qb64().unwrap());
+        let (block, _) = Saider::saidify(&sad["l"][0], None, None, None, None).unwrap();
+        assert_eq!(sad["l"][0]["d"].to_string().unwrap(), block.qb64().unwrap());
+        assert_eq!(sad["l"][1], dat!("z"));
+        let (block, _) = Saider::saidify(&sad["m"]["n"], None, None, None, None).unwrap();
+        assert_eq!(sad["m"]["n"]["d"].to_string().unwrap(), block.qb64().unwrap());
+        assert!(saider.verify(&sad, Some(true), None, None, None, None).unwrap());
+
+        assert!(Saider::saidify_nested(&dat!({"x": {"d": ""}}), None, None, None).is_err());
+    }
 }
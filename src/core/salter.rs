@@ -187,7 +187,7 @@ impl Matter for Salter {
 #[cfg(test)]
 mod test {
     use crate::core::{
-        common::{versify, Ilkage, Serialage, Tierage, CURRENT_VERSION},
+        common::Tierage,
         matter::{tables as matter, Matter},
         salter::Salter,
         signer::Signer,
@@ -253,14 +253,14 @@ mod test {
     use crate::{
         core::{
             cigar::Cigar,
-            common::Version,
             counter::{tables as counter, Counter},
             diger::Diger,
+            eventing::{incept, receipt},
             indexer::Indexer,
-            number::Number,
             sadder::Sadder,
+            seal::SealEvent,
             seqner::Seqner,
-            serder::{test::incept, Serder},
+            serder::Serder,
             siger::Siger,
         },
         error::{err, Error, Result},
@@ -272,28 +272,6 @@ mod test {
         next: Vec<Signer>,
     }
 
-    #[derive(Debug, Clone, PartialEq)]
-    pub struct Seal {
-        i: String,
-        s: String,
-        d: String,
-        last: bool,
-    }
-
-    impl Seal {
-        pub fn i(&self) -> String {
-            self.i.clone()
-        }
-
-        pub fn s(&self) -> Result<String> {
-            Seqner::new_with_snh(&self.s)?.qb64()
-        }
-
-        pub fn d(&self) -> String {
-            self.d.clone()
-        }
-    }
-
     impl Vault {
         fn default() -> Result<Self> {
             // Tierage::min is not exposed externally, this is for testing. One needs to use 'temp'
@@ -369,7 +347,7 @@ mod test {
     fn messagize(
         serder: &Serder,
         sigers: Option<&[Siger]>,
-        seal: Option<&Seal>,
+        seal: Option<(&SealEvent, bool)>, // the bool selects the last establishment event
         wigers: Option<&[Siger]>,
         cigars: Option<&[Cigar]>,
     ) -> Result<String> {
@@ -381,8 +359,8 @@ mod test {
         }
 
         if let Some(sigers) = sigers {
-            if let Some(seal) = seal {
-                if seal.last {
+            if let Some((seal, last)) = seal {
+                if last {
                     atc += &Counter::new_with_code_and_count(
                         counter::Codex::TransLastIdxSigGroups,
                         1,
@@ -393,7 +371,7 @@ mod test {
                     atc += &Counter::new_with_code_and_count(counter::Codex::TransIdxSigGroups, 1)?
                         .qb64()?;
                     atc += &seal.i();
-                    atc += &seal.s()?;
+                    atc += &Seqner::new_with_sn(seal.s())?.qb64()?;
                     atc += &seal.d();
                 }
             }
@@ -424,30 +402,4 @@ mod test {
 
         Ok(message + &atc)
     }
-
-    fn receipt(
-        pre: &str,
-        sn: u128,
-        said: &str,
-        version: Option<&Version>,
-        kind: Option<&str>,
-    ) -> Result<Serder> {
-        let version = version.unwrap_or(CURRENT_VERSION);
-        let kind = kind.unwrap_or(Serialage::JSON);
-
-        let vs = versify(None, Some(version), Some(kind), Some(0))?;
-        let ilk = Ilkage::rct;
-
-        let sner = Number::new_with_num(sn)?;
-
-        let ked = dat!({
-            "v": &vs,
-            "t": ilk,
-            "d": said,
-            "i": pre,
-            "s": &sner.numh()?
-        });
-
-        Serder::new_with_ked(&ked, None, None)
-    }
 }
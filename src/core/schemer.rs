@@ -0,0 +1,810 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+use regex::Regex;
+
+use crate::{
+    core::{
+        common::{dumps, loads, Ids, Serialage},
+        creder::Creder,
+        matter::Matter,
+        pather::Pather,
+        sadder::Sadder,
+        saider::Saider,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+// the credential sections validated against the schema properties of the same label
+const SECTIONS: &[&str] = &[Ids::a, Ids::A, Ids::e, Ids::r];
+
+// JSON Schema assertion and applicator keywords outside the supported subset, which are
+// reported rather than ignored so that a value is never accepted against constraints that
+// were not checked
+const UNSUPPORTED: &[&str] = &[
+    "$dynamicRef",
+    "$recursiveRef",
+    "additionalItems",
+    "contains",
+    "dependencies",
+    "dependentRequired",
+    "dependentSchemas",
+    "else",
+    "if",
+    "maxContains",
+    "maxProperties",
+    "minContains",
+    "minProperties",
+    "multipleOf",
+    "patternProperties",
+    "prefixItems",
+    "propertyNames",
+    "then",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+// the string formats that are checked
+const FORMATS: &[&str] = &["date-time"];
+
+/// A field of a value that does not conform to a schema, at `path` from the root of the value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    path: Vec<String>,
+    message: String,
+}
+
+impl Violation {
+    fn new(path: &[String], message: &str) -> Self {
+        Violation { path: path.to_vec(), message: message.to_string() }
+    }
+
+    /// The path to the offending field, as with `Pather::path`.
+    pub fn path(&self) -> Value {
+        Value::Array(self.path.iter().map(|label| dat!(label.as_str())).collect())
+    }
+
+    /// The path to the offending field as a pather, when its labels are base64.
+    pub fn pather(&self) -> Result<Pather> {
+        Pather::new_with_path(&self.path())
+    }
+
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "-{path}: {message}", path = self.path.join("-"), message = self.message)
+    }
+}
+
+fn is_type(value: &Value, typ: &str) -> bool {
+    match (typ, value) {
+        ("null", Value::Null)
+        | ("boolean", Value::Boolean(_))
+        | ("number", Value::Number(_))
+        | ("string", Value::String(_))
+        | ("array", Value::Array(_))
+        | ("object", Value::Object(_)) => true,
+        ("integer", _) => {
+            value.to_i64().is_ok() || value.to_f64().map(|f| f.fract() == 0.0).unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value.to_i64() {
+        Ok(i) => Some(i as f64),
+        Err(_) => value.to_f64().ok(),
+    }
+}
+
+fn length(value: &Value) -> Option<usize> {
+    match value {
+        Value::Number(_) => value.to_i64().ok().and_then(|i| usize::try_from(i).ok()),
+        _ => None,
+    }
+}
+
+// the violations of `value` against the subschemas of a combinator
+fn alternatives(schemas: &[Value], value: &Value, path: &mut Vec<String>) -> Vec<Vec<Violation>> {
+    schemas
+        .iter()
+        .map(|schema| {
+            let mut violations = vec![];
+            check(schema, value, path, &mut violations);
+            violations
+        })
+        .collect()
+}
+
+// the subschemas of the combinator `keyword`, reporting a combinator that is not a list
+fn subschemas<'a>(
+    schema: &'a IndexMap<String, Value>,
+    keyword: &str,
+    path: &[String],
+    violations: &mut Vec<Violation>,
+) -> Option<&'a [Value]> {
+    match schema.get(keyword) {
+        Some(Value::Array(schemas)) => Some(schemas),
+        Some(_) => {
+            violations.push(Violation::new(path, &format!("invalid schema keyword {keyword}")));
+            None
+        }
+        None => None,
+    }
+}
+
+fn is_format(string: &str, format: &str) -> bool {
+    match format {
+        "date-time" => chrono::DateTime::parse_from_rfc3339(string).is_ok(),
+        _ => false,
+    }
+}
+
+// reports a value matching none of the alternatives of a combinator by the violations of the
+// only alternative that failed below `path`, if there is one, for the sake of precise paths
+fn mismatch(
+    results: Vec<Vec<Violation>>,
+    path: &[String],
+    message: &str,
+    violations: &mut Vec<Violation>,
+) {
+    let mut deeper: Vec<_> = results
+        .into_iter()
+        .filter(|result| result.iter().all(|violation| violation.path.len() > path.len()))
+        .collect();
+
+    match deeper.len() {
+        1 => violations.append(&mut deeper[0]),
+        _ => violations.push(Violation::new(path, message)),
+    }
+}
+
+// collects the violations of `value`, at `path`, against `schema`; keywords outside the
+// supported subset of JSON Schema and schemas that are neither objects nor booleans are
+// reported as violations
+fn check(schema: &Value, value: &Value, path: &mut Vec<String>, violations: &mut Vec<Violation>) {
+    let schema = match schema {
+        Value::Boolean(true) => return,
+        Value::Boolean(false) => return violations.push(Violation::new(path, "not allowed")),
+        Value::Object(schema) => schema,
+        _ => return violations.push(Violation::new(path, "invalid schema")),
+    };
+
+    if schema.contains_key("$ref") {
+        violations.push(Violation::new(path, "unsupported schema reference"));
+    }
+    for keyword in UNSUPPORTED.iter().filter(|keyword| schema.contains_key(**keyword)) {
+        violations.push(Violation::new(path, &format!("unsupported schema keyword {keyword}")));
+    }
+
+    if let Some(typ) = schema.get("type") {
+        let types = match typ {
+            Value::Array(types) => types.iter().filter_map(|t| t.to_string().ok()).collect(),
+            _ => typ.to_string().into_iter().collect::<Vec<_>>(),
+        };
+        if !types.iter().any(|t| is_type(value, t)) {
+            let message = format!("expected type {types}", types = types.join(" or "));
+            return violations.push(Violation::new(path, &message));
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if value != expected {
+            violations.push(Violation::new(path, &format!("expected constant {expected}")));
+        }
+    }
+
+    if let Some(Value::Array(values)) = schema.get("enum") {
+        if !values.contains(value) {
+            violations.push(Violation::new(path, "not one of the enumerated values"));
+        }
+    }
+
+    match value {
+        Value::String(string) => {
+            let size = string.chars().count();
+            if schema.get("minLength").and_then(length).is_some_and(|min| size < min) {
+                violations.push(Violation::new(path, "shorter than the minimum length"));
+            }
+            if schema.get("maxLength").and_then(length).is_some_and(|max| size > max) {
+                violations.push(Violation::new(path, "longer than the maximum length"));
+            }
+            if let Some(pattern) = schema.get("pattern").and_then(|p| p.to_string().ok()) {
+                match Regex::new(&pattern) {
+                    Ok(regex) if regex.is_match(string) => {}
+                    Ok(_) => violations
+                        .push(Violation::new(path, &format!("does not match pattern {pattern}"))),
+                    Err(_) => violations
+                        .push(Violation::new(path, &format!("invalid schema pattern {pattern}"))),
+                }
+            }
+            if let Some(format) = schema.get("format").and_then(|f| f.to_string().ok()) {
+                if !FORMATS.contains(&format.as_str()) {
+                    violations
+                        .push(Violation::new(path, &format!("unsupported schema format {format}")));
+                } else if !is_format(string, &format) {
+                    violations.push(Violation::new(path, &format!("not a valid {format}")));
+                }
+            }
+        }
+        Value::Number(_) => {
+            let n = number(value).unwrap_or_default();
+            let bound = |keyword: &str| schema.get(keyword).and_then(number);
+            if bound("minimum").is_some_and(|min| n < min)
+                || bound("exclusiveMinimum").is_some_and(|min| n <= min)
+            {
+                violations.push(Violation::new(path, "less than the minimum"));
+            }
+            if bound("maximum").is_some_and(|max| n > max)
+                || bound("exclusiveMaximum").is_some_and(|max| n >= max)
+            {
+                violations.push(Violation::new(path, "greater than the maximum"));
+            }
+        }
+        Value::Array(values) => {
+            if schema.get("minItems").and_then(length).is_some_and(|min| values.len() < min) {
+                violations.push(Violation::new(path, "fewer than the minimum items"));
+            }
+            if schema.get("maxItems").and_then(length).is_some_and(|max| values.len() > max) {
+                violations.push(Violation::new(path, "more than the maximum items"));
+            }
+            if schema.get("uniqueItems") == Some(&Value::Boolean(true))
+                && values.iter().enumerate().any(|(i, value)| values[..i].contains(value))
+            {
+                violations.push(Violation::new(path, "items are not unique"));
+            }
+            if let Some(items) = schema.get("items") {
+                for (index, value) in values.iter().enumerate() {
+                    path.push(index.to_string());
+                    check(items, value, path, violations);
+                    path.pop();
+                }
+            }
+        }
+        Value::Object(map) => check_object(schema, map, path, violations),
+        _ => {}
+    }
+
+    if let Some(schemas) = subschemas(schema, "allOf", path, violations) {
+        for schema in schemas {
+            check(schema, value, path, violations);
+        }
+    }
+
+    if let Some(schemas) = subschemas(schema, "anyOf", path, violations) {
+        let results = alternatives(schemas, value, path);
+        if !results.is_empty() && !results.iter().any(|result| result.is_empty()) {
+            mismatch(results, path, "does not match any of the schemas in anyOf", violations);
+        }
+    }
+
+    if let Some(schemas) = subschemas(schema, "oneOf", path, violations) {
+        let results = alternatives(schemas, value, path);
+        let matches = results.iter().filter(|result| result.is_empty()).count();
+        if matches == 0 && !results.is_empty() {
+            mismatch(results, path, "does not match any of the schemas in oneOf", violations);
+        } else if matches > 1 {
+            let message = format!("matches {matches} of the schemas in oneOf, expected one");
+            violations.push(Violation::new(path, &message));
+        }
+    }
+
+    if let Some(schema) = schema.get("not") {
+        let mut results = vec![];
+        check(schema, value, path, &mut results);
+        if results.is_empty() {
+            violations.push(Violation::new(path, "matches the schema in not"));
+        }
+    }
+}
+
+fn check_object(
+    schema: &IndexMap<String, Value>,
+    map: &IndexMap<String, Value>,
+    path: &mut Vec<String>,
+    violations: &mut Vec<Violation>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for label in required.iter().filter_map(|label| label.to_string().ok()) {
+            if !map.contains_key(&label) {
+                path.push(label);
+                violations.push(Violation::new(path, "missing required field"));
+                path.pop();
+            }
+        }
+    }
+
+    let properties = match schema.get("properties") {
+        Some(Value::Object(properties)) => properties.clone(),
+        _ => IndexMap::new(),
+    };
+    for (label, value) in map {
+        path.push(label.clone());
+        match (properties.get(label), schema.get("additionalProperties")) {
+            (Some(property), _) => check(property, value, path, violations),
+            (None, Some(additional)) => match additional {
+                Value::Boolean(false) => {
+                    violations.push(Violation::new(path, "unexpected field"));
+                }
+                _ => check(additional, value, path, violations),
+            },
+            (None, None) => {}
+        }
+        path.pop();
+    }
+}
+
+/// A JSON schema, self-addressed by the said in its `$id` field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schemer {
+    raw: Vec<u8>,
+    sed: Value,
+    saider: Saider,
+}
+
+impl Schemer {
+    /// Loads a schema from `raw` JSON, or from `sed`. The `$id` of a schema from `sed` is
+    /// computed with digest `code` when empty; otherwise it must be the said of the schema.
+    pub fn new(raw: Option<&[u8]>, sed: Option<&Value>, code: Option<&str>) -> Result<Self> {
+        let label = Ids::dollar;
+        let kind = Serialage::JSON;
+
+        let sed = if let Some(raw) = raw {
+            loads(raw, None, Some(kind))?
+        } else if let Some(sed) = sed {
+            let map = sed.to_map()?;
+            match map.get(label) {
+                Some(Value::String(said)) if said.is_empty() => {
+                    Saider::saidify(sed, code, Some(kind), Some(label), None)?.1
+                }
+                _ => sed.clone(),
+            }
+        } else {
+            return err!(Error::EmptyMaterial("missing schema".to_string()));
+        };
+
+        let map = sed.to_map()?;
+        let said = match map.get(label) {
+            Some(said) => said.to_string()?,
+            None => return err!(Error::Value(format!("missing schema said field = {label}"))),
+        };
+        let saider = Saider::new_with_qb64(&said)?;
+        if !saider.verify(&sed, Some(true), Some(false), Some(kind), Some(label), None)? {
+            return err!(Error::Validation(format!("invalid schema said = {said}")));
+        }
+
+        let raw = match raw {
+            Some(raw) => raw.to_vec(),
+            None => dumps(&sed, Some(kind))?,
+        };
+
+        Ok(Schemer { raw, sed, saider })
+    }
+
+    pub fn new_with_raw(raw: &[u8]) -> Result<Self> {
+        Self::new(Some(raw), None, None)
+    }
+
+    pub fn new_with_sed(sed: &Value, code: Option<&str>) -> Result<Self> {
+        Self::new(None, Some(sed), code)
+    }
+
+    pub fn raw(&self) -> Vec<u8> {
+        self.raw.clone()
+    }
+
+    pub fn sed(&self) -> Value {
+        self.sed.clone()
+    }
+
+    pub fn saider(&self) -> Saider {
+        self.saider.clone()
+    }
+
+    pub fn said(&self) -> Result<String> {
+        self.saider.qb64()
+    }
+
+    /// The violations of `value` against the whole schema.
+    pub fn evaluate(&self, value: &Value) -> Vec<Violation> {
+        let mut violations = vec![];
+        check(&self.sed, value, &mut vec![], &mut violations);
+        violations
+    }
+
+    /// The violations of the attribute, edge and rule sections of `creder` against the
+    /// schema properties of the same label, with paths from the root of the credential.
+    pub fn violations(&self, creder: &Creder) -> Result<Vec<Violation>> {
+        let schema = creder.schema()?;
+        if schema != self.said()? {
+            return err!(Error::Validation(format!(
+                "credential schema = {schema} does not match schema = {said}",
+                said = self.said()?
+            )));
+        }
+
+        let ked = creder.ked().to_map()?;
+        let sed = self.sed.to_map()?;
+        let properties = match sed.get("properties") {
+            Some(Value::Object(properties)) => properties.clone(),
+            _ => IndexMap::new(),
+        };
+        let required = match sed.get("required") {
+            Some(Value::Array(required)) => required.clone(),
+            _ => vec![],
+        };
+
+        let mut violations = vec![];
+        for label in SECTIONS {
+            let mut path = vec![label.to_string()];
+            match (ked.get(*label), properties.get(*label)) {
+                (Some(section), Some(schema)) => check(schema, section, &mut path, &mut violations),
+                (Some(_), None) => {
+                    if sed.get("additionalProperties") == Some(&Value::Boolean(false)) {
+                        violations.push(Violation::new(&path, "unexpected section"));
+                    }
+                }
+                (None, _) => {
+                    if required.contains(&dat!(*label)) {
+                        violations.push(Violation::new(&path, "missing required section"));
+                    }
+                }
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Validates `creder` against the schema, failing on the first violation.
+    pub fn validate(&self, creder: &Creder) -> Result<()> {
+        match self.violations(creder)?.first() {
+            Some(violation) => {
+                err!(Error::Validation(format!("invalid credential field {violation}")))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Schemas keyed by said, against which credentials are validated.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaRegistry {
+    schemers: IndexMap<String, Schemer>,
+}
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `schemer`, returning its said.
+    pub fn add(&mut self, schemer: Schemer) -> Result<String> {
+        let said = schemer.said()?;
+        self.schemers.insert(said.clone(), schemer);
+        Ok(said)
+    }
+
+    pub fn get(&self, said: &str) -> Option<&Schemer> {
+        self.schemers.get(said)
+    }
+
+    pub fn contains(&self, said: &str) -> bool {
+        self.schemers.contains_key(said)
+    }
+
+    pub fn saids(&self) -> Vec<String> {
+        self.schemers.keys().cloned().collect()
+    }
+
+    /// Validates `creder` against the schema named by its `s` field, which must be registered.
+    pub fn validate(&self, creder: &Creder) -> Result<()> {
+        let schema = creder.schema()?;
+        match self.get(&schema) {
+            Some(schemer) => schemer.validate(creder),
+            None => err!(Error::Validation(format!("unknown schema = {schema}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SchemaRegistry, Schemer};
+    use crate::core::{
+        common::Ids, creder::Creder, matter::Matter, proving::credential, sadder::Sadder,
+        saider::Saider,
+    };
+
+    const ISSUER: &str = "ENayINhHQnx6525EpcTmkvo6ZixiJyiskwkVNbMPohYa";
+    const DT: &str = "2023-04-30T00:34:11.853572+00:00";
+
+    fn sed() -> crate::data::Value {
+        dat!({
+            "$id": "",
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Legal Entity Credential",
+            "type": "object",
+            "properties": {
+                "v": { "type": "string" },
+                "d": { "type": "string" },
+                "i": { "type": "string" },
+                "s": { "type": "string" },
+                "a": {
+                    "oneOf": [
+                        { "type": "string" },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "d": { "type": "string" },
+                                "dt": { "type": "string", "format": "date-time" },
+                                "LEI": { "type": "string", "pattern": "^[A-Z0-9]{20}$" },
+                                "score": { "type": "integer", "minimum": 0, "maximum": 100 },
+                                "roles": {
+                                    "type": "array",
+                                    "items": { "enum": ["admin", "member"] },
+                                    "uniqueItems": true
+                                }
+                            },
+                            "additionalProperties": false,
+                            "required": ["d", "dt", "LEI"]
+                        }
+                    ]
+                },
+                "e": {
+                    "oneOf": [
+                        { "type": "string" },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "d": { "type": "string" },
+                                "qvi": {
+                                    "type": "object",
+                                    "properties": {
+                                        "n": { "type": "string", "minLength": 44 },
+                                        "s": { "const": "EDiWb-53cI8FBPOpF69LrLCSElNjG-BAChHp2-OsLmbC" }
+                                    },
+                                    "required": ["n", "s"]
+                                }
+                            },
+                            "required": ["d", "qvi"]
+                        }
+                    ]
+                }
+            },
+            "additionalProperties": false,
+            "required": ["v", "d", "i", "s", "a", "e"]
+        })
+    }
+
+    fn issue(said: &str, data: &crate::data::Value, edges: Option<&crate::data::Value>) -> Creder {
+        let (creder, _) =
+            credential(ISSUER, said, data, None, None, edges, None, None, None, None, None, None)
+                .unwrap();
+        creder
+    }
+
+    fn edges(s: &str) -> crate::data::Value {
+        dat!({ "qvi": { "d": "", "n": "EE_Wrv2OHqIOptEni3mE3Ckc4C6jO1RvgtxdpDZBiuB0", "s": s } })
+    }
+
+    #[test]
+    fn schemer() {
+        let schemer = Schemer::new_with_sed(&sed(), None).unwrap();
+        let said = schemer.said().unwrap();
+        let (saider, _) = Saider::saidify(&sed(), None, None, Some(Ids::dollar), None).unwrap();
+        assert_eq!(said, saider.qb64().unwrap());
+        assert_eq!(schemer.sed()[Ids::dollar], dat!(&said));
+        assert_eq!(schemer.saider(), saider);
+
+        let loaded = Schemer::new_with_raw(&schemer.raw()).unwrap();
+        assert_eq!(loaded, schemer);
+        assert_eq!(Schemer::new_with_sed(&schemer.sed(), None).unwrap(), schemer);
+
+        // a schema must be self-addressing
+        let mut sed = schemer.sed();
+        sed["title"] = dat!("Tampered");
+        assert!(Schemer::new_with_sed(&sed, None).is_err());
+        assert!(Schemer::new_with_raw(sed.to_json().unwrap().as_bytes()).is_err());
+        assert!(Schemer::new_with_sed(&dat!({ "type": "object" }), None).is_err());
+        assert!(Schemer::new(None, None, None).is_err());
+        assert!(Schemer::new_with_raw(b"not json").is_err());
+
+        let data = dat!({ "dt": DT, "LEI": "254900OPPU84GM83MG36", "roles": ["admin"] });
+        let creder =
+            issue(&said, &data, Some(&edges("EDiWb-53cI8FBPOpF69LrLCSElNjG-BAChHp2-OsLmbC")));
+        assert!(schemer.violations(&creder).unwrap().is_empty());
+        schemer.validate(&creder).unwrap();
+        schemer.validate(&creder.compact().unwrap()).unwrap();
+        assert!(schemer.evaluate(&creder.ked()).is_empty());
+    }
+
+    #[test]
+    fn violations() {
+        let schemer = Schemer::new_with_sed(&sed(), None).unwrap();
+        let said = schemer.said().unwrap();
+
+        let data = dat!({
+            "dt": 1,
+            "LEI": "254900oppu84gm83mg36",
+            "score": 101,
+            "roles": ["admin", "owner", "admin"],
+            "extra": true
+        });
+        let creder = issue(&said, &data, Some(&edges(&said)));
+        let violations = schemer.violations(&creder).unwrap();
+        let found = violations.iter().map(|violation| violation.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                "-a-dt: expected type string",
+                "-a-LEI: does not match pattern ^[A-Z0-9]{20}$",
+                "-a-score: greater than the maximum",
+                "-a-roles: items are not unique",
+                "-a-roles-1: not one of the enumerated values",
+                "-a-extra: unexpected field",
+                "-e-qvi-s: expected constant \"EDiWb-53cI8FBPOpF69LrLCSElNjG-BAChHp2-OsLmbC\"",
+            ]
+        );
+
+        // paths are pather paths into the credential
+        let pather = violations[1].pather().unwrap();
+        assert_eq!(pather.path().unwrap(), violations[1].path());
+        assert_eq!(pather.resolve(&creder.ked()).unwrap(), dat!("254900oppu84gm83mg36"));
+        assert_eq!(violations[4].pather().unwrap().resolve(&creder.ked()).unwrap(), dat!("owner"));
+        assert_eq!(violations[1].message(), "does not match pattern ^[A-Z0-9]{20}$");
+
+        let err = schemer.validate(&creder).unwrap_err().to_string();
+        assert_eq!(err, "invalid credential field -a-dt: expected type string");
+
+        // missing sections and fields, and a section of the wrong type
+        let creder = issue(&said, &dat!({ "dt": DT }), None);
+        let found = schemer
+            .violations(&creder)
+            .unwrap()
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec!["-a-LEI: missing required field", "-e: missing required section"]);
+
+        let mut ked = creder.ked();
+        ked[Ids::e] = dat!(["not", "a", "map"]);
+        let (_, ked) = Saider::saidify(&ked, None, None, None, None).unwrap();
+        let creder = Creder::new_with_ked(&ked, None, None).unwrap();
+        let violations = schemer.violations(&creder).unwrap();
+        assert_eq!(violations[1].to_string(), "-e: does not match any of the schemas in oneOf");
+
+        // the credential must name the schema
+        let other = Schemer::new_with_sed(&dat!({ "$id": "", "type": "object" }), None).unwrap();
+        assert!(other.violations(&creder).is_err());
+        assert!(other.evaluate(&dat!([])).len() == 1);
+    }
+
+    #[test]
+    fn keywords() {
+        let schemer = Schemer::new_with_sed(
+            &dat!({
+                "$id": "",
+                "type": "object",
+                "properties": {
+                    "n": { "type": ["integer", "null"], "exclusiveMinimum": 0 },
+                    "f": { "type": "number", "maximum": 1.5 },
+                    "l": { "type": "array", "minItems": 1, "maxItems": 2 },
+                    "s": { "type": "string", "minLength": 2, "maxLength": 3 },
+                    "o": { "oneOf": [{ "type": "string" }, { "minLength": 1 }] },
+                    "x": { "anyOf": [{ "type": "string" }, { "type": "boolean" }] },
+                    "y": { "allOf": [{ "type": "string" }, { "maxLength": 1 }] },
+                    "z": { "not": { "type": "string" } },
+                    "r": { "$ref": "#/definitions/r" },
+                    "b": false
+                },
+                "additionalProperties": { "type": "string" }
+            }),
+            None,
+        )
+        .unwrap();
+
+        let valid = dat!({
+            "n": null, "f": 1.5, "l": [1], "s": "ab", "o": 1, "x": true, "y": "a", "z": 1, "q": "q"
+        });
+        assert!(schemer.evaluate(&valid).is_empty());
+
+        let invalid = dat!({
+            "n": 0, "f": 1.6, "l": [], "s": "abcd", "o": "a", "x": 1, "y": "ab", "z": "z",
+            "r": 1, "b": 1, "q": 1
+        });
+        let found = schemer
+            .evaluate(&invalid)
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                "-n: less than the minimum",
+                "-f: greater than the maximum",
+                "-l: fewer than the minimum items",
+                "-s: longer than the maximum length",
+                "-o: matches 2 of the schemas in oneOf, expected one",
+                "-x: does not match any of the schemas in anyOf",
+                "-y: longer than the maximum length",
+                "-z: matches the schema in not",
+                "-r: unsupported schema reference",
+                "-b: not allowed",
+                "-q: expected type string",
+            ]
+        );
+
+        // keywords outside the supported subset and malformed schemas fail closed
+        let schemer = Schemer::new_with_sed(
+            &dat!({
+                "$id": "",
+                "properties": {
+                    "d": { "type": "string", "format": "date-time" },
+                    "e": { "type": "string", "format": "email" },
+                    "m": { "type": "integer", "multipleOf": 2 },
+                    "c": { "if": { "type": "string" }, "then": { "minLength": 2 } },
+                    "p": { "patternProperties": { "^x": { "type": "string" } } },
+                    "a": { "contains": { "const": 1 } },
+                    "i": { "items": [{ "type": "string" }] },
+                    "o": { "anyOf": { "type": "string" } },
+                    "n": 1
+                },
+                "dependentRequired": { "d": ["e"] }
+            }),
+            None,
+        )
+        .unwrap();
+        assert_eq!(schemer.evaluate(&dat!({ "d": DT })).len(), 1);
+
+        let found = schemer
+            .evaluate(&dat!({
+                "d": "yesterday", "e": "a@b.c", "m": 4, "c": "c", "p": {}, "a": [1], "i": ["i"],
+                "o": "o", "n": null
+            }))
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                "-: unsupported schema keyword dependentRequired",
+                "-d: not a valid date-time",
+                "-e: unsupported schema format email",
+                "-m: unsupported schema keyword multipleOf",
+                "-c: unsupported schema keyword if",
+                "-c: unsupported schema keyword then",
+                "-p: unsupported schema keyword patternProperties",
+                "-a: unsupported schema keyword contains",
+                "-i-0: invalid schema",
+                "-o: invalid schema keyword anyOf",
+                "-n: invalid schema",
+            ]
+        );
+    }
+
+    #[test]
+    fn registry() {
+        let schemer = Schemer::new_with_sed(&sed(), None).unwrap();
+        let said = schemer.said().unwrap();
+        let mut registry = SchemaRegistry::new();
+        assert!(!registry.contains(&said));
+        assert_eq!(registry.add(schemer.clone()).unwrap(), said);
+        assert!(registry.contains(&said));
+        assert_eq!(registry.get(&said), Some(&schemer));
+        assert_eq!(registry.saids(), vec![said.clone()]);
+
+        let data = dat!({ "dt": DT, "LEI": "254900OPPU84GM83MG36" });
+        let edges = edges("EDiWb-53cI8FBPOpF69LrLCSElNjG-BAChHp2-OsLmbC");
+        registry.validate(&issue(&said, &data, Some(&edges))).unwrap();
+        assert!(registry.validate(&issue(&said, &dat!({ "dt": DT }), Some(&edges))).is_err());
+
+        let unknown = "EE5uDJTq5cc6AEdqbyMpvARUjsK_chNdInf3xyRoCBcT";
+        let err = registry.validate(&issue(unknown, &data, None)).unwrap_err().to_string();
+        assert_eq!(err, format!("unknown schema = {unknown}"));
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{
+    core::{
+        common::{Ids, Ilkage},
+        diger::Diger,
+        prefixer::Prefixer,
+        sadder::Sadder,
+        seqner::Seqner,
+        serder::Serder,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+// the values of a seal's fields, which must be exactly `labels` in order
+fn fields(value: &Value, labels: &[&str]) -> Result<Vec<String>> {
+    let map = value.to_map()?;
+    if !map.keys().eq(labels.iter()) {
+        return err!(Error::Value(format!(
+            "invalid seal fields = {fields:?}, expected {labels:?}",
+            fields = map.keys().collect::<Vec<_>>()
+        )));
+    }
+
+    map.values().map(|value| value.to_string()).collect()
+}
+
+fn validate_pre(pre: &str) -> Result<()> {
+    Prefixer::new_with_qb64(pre)?;
+    Ok(())
+}
+
+fn validate_dig(dig: &str) -> Result<()> {
+    Diger::new_with_qb64(dig)?;
+    Ok(())
+}
+
+fn sn(snh: &str) -> Result<u128> {
+    Seqner::new_with_snh(snh)?.sn()
+}
+
+/// Seal of the digest `d` of arbitrary data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SealDigest {
+    d: String,
+}
+
+impl SealDigest {
+    pub fn new(d: &str) -> Result<Self> {
+        validate_dig(d)?;
+        Ok(SealDigest { d: d.to_string() })
+    }
+
+    pub fn new_with_value(value: &Value) -> Result<Self> {
+        let fields = fields(value, &[Ids::d])?;
+        Self::new(&fields[0])
+    }
+
+    pub fn d(&self) -> String {
+        self.d.clone()
+    }
+
+    pub fn value(&self) -> Value {
+        dat!({ "d": &self.d })
+    }
+}
+
+/// Seal of the root digest `rd` of a merkle tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SealRoot {
+    rd: String,
+}
+
+impl SealRoot {
+    pub fn new(rd: &str) -> Result<Self> {
+        validate_dig(rd)?;
+        Ok(SealRoot { rd: rd.to_string() })
+    }
+
+    pub fn new_with_value(value: &Value) -> Result<Self> {
+        let fields = fields(value, &[Ids::rd])?;
+        Self::new(&fields[0])
+    }
+
+    pub fn rd(&self) -> String {
+        self.rd.clone()
+    }
+
+    pub fn value(&self) -> Value {
+        dat!({ "rd": &self.rd })
+    }
+}
+
+/// Seal of the key event of identifier `i` with sequence number `s` and said `d`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SealEvent {
+    i: String,
+    s: u128,
+    d: String,
+}
+
+impl SealEvent {
+    pub fn new(i: &str, s: u128, d: &str) -> Result<Self> {
+        validate_pre(i)?;
+        validate_dig(d)?;
+        Ok(SealEvent { i: i.to_string(), s, d: d.to_string() })
+    }
+
+    pub fn new_with_value(value: &Value) -> Result<Self> {
+        let fields = fields(value, &[Ids::i, Ids::s, Ids::d])?;
+        Self::new(&fields[0], sn(&fields[1])?, &fields[2])
+    }
+
+    /// Seals the key event in `serder`.
+    pub fn new_with_serder(serder: &Serder) -> Result<Self> {
+        Self::new(&serder.pre()?, serder.sn()?, &serder.said()?)
+    }
+
+    pub fn i(&self) -> String {
+        self.i.clone()
+    }
+
+    pub fn s(&self) -> u128 {
+        self.s
+    }
+
+    pub fn d(&self) -> String {
+        self.d.clone()
+    }
+
+    pub fn value(&self) -> Value {
+        dat!({ "i": &self.i, "s": &format!("{:x}", self.s), "d": &self.d })
+    }
+}
+
+/// Seal of the location of a key event: identifier `i`, sequence number `s`, ilk `t` and the
+/// said `p` of the prior event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SealLocation {
+    i: String,
+    s: u128,
+    t: String,
+    p: String,
+}
+
+impl SealLocation {
+    pub fn new(i: &str, s: u128, t: &str, p: &str) -> Result<Self> {
+        const ILKS: &[&str] = &[Ilkage::icp, Ilkage::rot, Ilkage::ixn, Ilkage::dip, Ilkage::drt];
+        validate_pre(i)?;
+        if !ILKS.contains(&t) {
+            return err!(Error::Value(format!("invalid ilk = {t} for location seal")));
+        }
+        validate_dig(p)?;
+        Ok(SealLocation { i: i.to_string(), s, t: t.to_string(), p: p.to_string() })
+    }
+
+    pub fn new_with_value(value: &Value) -> Result<Self> {
+        let fields = fields(value, &[Ids::i, Ids::s, Ids::t, Ids::p])?;
+        Self::new(&fields[0], sn(&fields[1])?, &fields[2], &fields[3])
+    }
+
+    /// Seals the location of the key event in `serder`, which must have a prior event.
+    pub fn new_with_serder(serder: &Serder) -> Result<Self> {
+        let ked = serder.ked();
+        let label = Ids::t;
+        let ilk = ked[label].to_string()?;
+        let label = Ids::p;
+        if !ked.to_map()?.contains_key(label) {
+            return err!(Error::Value(format!("missing prior event for ilk = {ilk}")));
+        }
+        let prior = ked[label].to_string()?;
+        Self::new(&serder.pre()?, serder.sn()?, &ilk, &prior)
+    }
+
+    pub fn i(&self) -> String {
+        self.i.clone()
+    }
+
+    pub fn s(&self) -> u128 {
+        self.s
+    }
+
+    pub fn t(&self) -> String {
+        self.t.clone()
+    }
+
+    pub fn p(&self) -> String {
+        self.p.clone()
+    }
+
+    pub fn value(&self) -> Value {
+        dat!({ "i": &self.i, "s": &format!("{:x}", self.s), "t": &self.t, "p": &self.p })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SealDigest, SealEvent, SealLocation, SealRoot};
+    use crate::core::{
+        eventing::{incept, interact},
+        matter::{tables as matter, Matter},
+        sadder::Sadder,
+        signer::Signer,
+    };
+
+    const DIG: &str = "ELC5L3iBVD77d_MYbYGGCUQgqQBju1o4x1Ud-z2sL-ux";
+    const PRE: &str = "EBfdlu8R27Fbx-ehrqwImnK-8Cm79sqbAQ4MmvEAYqao";
+
+    #[test]
+    fn seals() {
+        let seal = SealDigest::new(DIG).unwrap();
+        assert_eq!(seal.value(), dat!({ "d": DIG }));
+        assert_eq!(SealDigest::new_with_value(&seal.value()).unwrap(), seal);
+        assert_eq!(seal.d(), DIG);
+
+        let seal = SealRoot::new(DIG).unwrap();
+        assert_eq!(seal.value(), dat!({ "rd": DIG }));
+        assert_eq!(SealRoot::new_with_value(&seal.value()).unwrap(), seal);
+        assert_eq!(seal.rd(), DIG);
+
+        let seal = SealEvent::new(PRE, 26, DIG).unwrap();
+        assert_eq!(seal.value(), dat!({ "i": PRE, "s": "1a", "d": DIG }));
+        assert_eq!(SealEvent::new_with_value(&seal.value()).unwrap(), seal);
+        assert_eq!((seal.i(), seal.s(), seal.d()), (PRE.to_string(), 26, DIG.to_string()));
+
+        let seal = SealLocation::new(PRE, 1, "ixn", DIG).unwrap();
+        assert_eq!(seal.value(), dat!({ "i": PRE, "s": "1", "t": "ixn", "p": DIG }));
+        assert_eq!(SealLocation::new_with_value(&seal.value()).unwrap(), seal);
+        assert_eq!(seal.t(), "ixn");
+        assert_eq!(seal.p(), DIG);
+    }
+
+    #[test]
+    fn seals_with_serder() {
+        let signer = Signer::new_with_raw(&[0u8; 32], Some(true), None).unwrap();
+        let icp = incept(
+            &[&signer.verfer().qb64().unwrap()],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .unwrap();
+        let ixn =
+            interact(&icp.pre().unwrap(), &icp.said().unwrap(), Some(1), None, None, None).unwrap();
+
+        let seal = SealEvent::new_with_serder(&ixn).unwrap();
+        assert_eq!(seal, SealEvent::new(&ixn.pre().unwrap(), 1, &ixn.said().unwrap()).unwrap());
+
+        let seal = SealLocation::new_with_serder(&ixn).unwrap();
+        assert_eq!(seal.t(), "ixn");
+        assert_eq!(seal.p(), icp.said().unwrap());
+        assert!(SealLocation::new_with_serder(&icp).is_err());
+    }
+
+    #[test]
+    fn seals_unhappy_paths() {
+        assert!(SealDigest::new("not a digest").is_err());
+        assert!(SealRoot::new("").is_err());
+        assert!(SealEvent::new("", 0, DIG).is_err());
+        assert!(SealEvent::new(PRE, 0, PRE[1..].as_ref()).is_err());
+        assert!(SealLocation::new(PRE, 0, "xyz", DIG).is_err());
+
+        // fields must match exactly and in order
+        assert!(SealDigest::new_with_value(&dat!({ "rd": DIG })).is_err());
+        assert!(SealDigest::new_with_value(&dat!([DIG])).is_err());
+        assert!(SealEvent::new_with_value(&dat!({ "i": PRE, "d": DIG, "s": "0" })).is_err());
+        assert!(
+            SealEvent::new_with_value(&dat!({ "i": PRE, "s": "0", "d": DIG, "x": "" })).is_err()
+        );
+        assert!(SealEvent::new_with_value(&dat!({ "i": PRE, "s": "zz", "d": DIG })).is_err());
+        assert!(SealEvent::new_with_value(&dat!({ "i": PRE, "s": 0, "d": DIG })).is_err());
+    }
+}
@@ -4,12 +4,13 @@ use crate::{
         diger::Diger,
         matter::tables as matter,
         number::Number,
+        prefixer::Prefixer,
         sadder::Sadder,
         saider::Saider,
         tholder::Tholder,
         verfer::Verfer,
     },
-    data::{dat, Value},
+    data::Value,
     error::{err, Error, Result},
 };
 
@@ -189,6 +190,20 @@ impl Serder {
         Ok(ILKS.contains(&ilk.as_str()))
     }
 
+    /// Whether the said, and a self-addressing prefix an inception incepts, match the content.
+    pub fn verify(&self) -> Result<bool> {
+        const ILKS: &[&str] = &[Ilkage::icp, Ilkage::dip, Ilkage::vcp];
+
+        let said = self.said()?;
+        let label = Ids::t;
+        let ilk = self.ked[label].to_string()?;
+        if ILKS.contains(&ilk.as_str()) && self.pre()? == said {
+            Prefixer::new_with_qb64(&said)?.verify(&self.ked, Some(true))
+        } else {
+            self.saider.verify(&self.ked, Some(true), Some(true), Some(&self.kind), None, None)
+        }
+    }
+
     // pretty implemented in Sadder (this was overridden for some reason in KERIpy)
 }
 
@@ -275,23 +290,19 @@ impl Sadder for Serder {
 
 #[cfg(test)]
 pub(crate) mod test {
-    use crate::{
-        core::{
-            common::{
-                sniff, versify, Identage, Ids, Ilkage, Serialage, Version, CURRENT_VERSION,
-                MINIMUM_SNIFF_SIZE, VERSION_FULL_SIZE,
-            },
-            matter::{tables as matter, Matter},
-            number::Number,
-            prefixer::Prefixer,
-            sadder::Sadder,
-            saider::Saider,
-            serder::Serder,
-            tholder::Tholder,
+    use crate::core::{
+        common::{
+            sniff, versify, Identage, Ids, Serialage, CURRENT_VERSION, MINIMUM_SNIFF_SIZE,
+            VERSION_FULL_SIZE, VRSN_2_0,
         },
-        data::Value,
-        error::{err, Error, Result},
+        eventing::incept,
+        matter::{tables as matter, Matter},
+        number::Number,
+        sadder::Sadder,
+        saider::Saider,
+        serder::Serder,
     };
+    use rstest::rstest;
 
     #[test]
     fn convenience() {
@@ -461,6 +472,74 @@ pub(crate) mod test {
         println!("{p}", p = srdr.pretty(None).unwrap());
     }
 
+    #[rstest]
+    fn binary_kinds(#[values(Serialage::MGPK, Serialage::CBOR)] kind: &str) {
+        let pre = "BEy_EvE8OUMqj0AgCJ3wOCOrIVHVtwubYAysPyaAv9VI";
+        let srdr = incept(
+            &[pre],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(kind),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(srdr.kind(), kind);
+        assert_eq!(srdr.size() as usize, srdr.raw().len());
+        assert_eq!(sniff(&srdr.raw()).unwrap().size as usize, srdr.raw().len());
+
+        let raw = srdr.raw();
+        assert_ne!(raw[0], b'{');
+        let (saider, _) = Saider::saidify(&srdr.ked(), None, None, None, None).unwrap();
+        assert_eq!(saider.qb64().unwrap(), srdr.said().unwrap());
+
+        let parsed = Serder::new_with_raw(&[raw.as_slice(), b"-AAB"].concat()).unwrap();
+        assert_eq!(parsed, srdr);
+
+        // reserializing as json back-patches the version string
+        let mut json = srdr.clone();
+        json.populate_from_kind_and_self(Serialage::JSON).unwrap();
+        assert_eq!(json.kind(), Serialage::JSON);
+        assert_eq!(json.raw()[0], b'{');
+        assert_eq!(json.ked()["v"], dat!(&format!("KERI10JSON{:06x}_", json.raw().len())));
+    }
+
+    #[test]
+    fn version_2() {
+        let pre = "BEy_EvE8OUMqj0AgCJ3wOCOrIVHVtwubYAysPyaAv9VI";
+        let srdr = incept(
+            &[pre],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(VRSN_2_0),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(srdr.version(), *VRSN_2_0);
+        assert_eq!(srdr.ked()["v"], dat!("KERICAACAAJSONAAD_."));
+        assert_eq!(srdr.raw().len(), 255);
+
+        let parsed = Serder::new_with_raw(&srdr.raw()).unwrap();
+        assert_eq!(parsed, srdr);
+        let (saider, _) = Saider::saidify(&srdr.ked(), None, None, None, None).unwrap();
+        assert_eq!(saider.qb64().unwrap(), srdr.said().unwrap());
+    }
+
     #[test]
     fn creation() {
         let ked = dat!({
@@ -567,231 +646,4 @@ pub(crate) mod test {
         let (_, ked) = Saider::saidify(&ked, None, None, None, None).unwrap();
         assert!(Serder::new(None, None, None, Some(&ked), None).is_err());
     }
-
-    // what follows is a simple inception function. it is used above to verify serder functionality.
-
-    // this function uses convenience methods unlike most test code. it is likely that it will
-    // be extracted and used elsewhere - and convenience methods make sense outside the tests.
-    #[allow(clippy::too_many_arguments)]
-    pub(crate) fn incept(
-        keys: &[&str],          // current keys qb64
-        sith: Option<&Value>,   // current signing threshold
-        ndigs: Option<&[&str]>, // next keys qb64
-        nsith: Option<&Value>,  // next signing threshold
-        toad: Option<u128>,     // witness threshold number
-        wits: Option<&[&str]>,  // witness identifier prefixes qb64
-        cnfg: Option<&[&str]>,  // configuration traits from traiter::Codex
-        data: Option<&[Value]>, // seal dicts
-        version: Option<&Version>,
-        kind: Option<&str>,
-        code: Option<&str>,
-        intive: Option<bool>, // sith, nsith and toad are ints, not hex when numeric
-        delpre: Option<&str>, // delegator identifier prefix
-    ) -> Result<Serder> {
-        let version = version.unwrap_or(CURRENT_VERSION);
-        let kind = kind.unwrap_or(Serialage::JSON);
-        let intive = intive.unwrap_or(false);
-
-        let vs = &versify(None, Some(version), Some(kind), Some(0))?;
-        let ilk = if delpre.is_none() { Ilkage::icp } else { Ilkage::dip };
-        let sner = Number::new_with_num(0)?;
-
-        let sith = if let Some(sith) = sith {
-            sith.clone()
-        } else {
-            let mut s: i64 = (keys.len() as i64 + 1) / 2;
-            s = if s > 1 { s } else { 1 };
-            dat!(s)
-        };
-
-        let tholder = Tholder::new_with_sith(&sith)?;
-        if tholder.num()?.is_some() && tholder.num()?.unwrap() < 1 {
-            return err!(Error::Value(format!(
-                "invalid sith = {n} less than 1",
-                n = tholder.num()?.unwrap()
-            )));
-        }
-        if tholder.size() as usize > keys.len() {
-            return err!(Error::Value(format!(
-                "invalid sith size = {s} for keys = {keys:?}",
-                s = tholder.size()
-            )));
-        }
-
-        let ndigs = ndigs.unwrap_or(&[]);
-        let nsith = if let Some(nsith) = nsith {
-            nsith.clone()
-        } else {
-            let mut s: i64 = (ndigs.len() as i64 + 1) / 2;
-            s = if s > 0 { s } else { 0 };
-            dat!(s)
-        };
-
-        let ntholder = Tholder::new_with_sith(&nsith)?;
-        if ntholder.size() as usize > ndigs.len() {
-            return err!(Error::Value(format!(
-                "invalid nsith size = {s} for keys = {keys:?}",
-                s = ntholder.size()
-            )));
-        }
-
-        let wits = wits.unwrap_or(&[]);
-        let mut unique = wits.to_vec();
-        unique.dedup();
-        if wits.len() != unique.len() {
-            return err!(Error::Value(format!("invalid wits = {wits:?}, has duplicates")));
-        }
-
-        let toader = if let Some(toad) = toad {
-            Number::new_with_num(toad)?
-        } else if wits.is_empty() {
-            Number::new_with_num(0)?
-        } else {
-            let toad = ample(wits.len() as u128, None, None)?;
-            Number::new_with_num(toad)?
-        };
-
-        if !wits.is_empty() {
-            if toader.num()? < 1 || toader.num()? > wits.len() as u128 {
-                return err!(Error::Value(format!(
-                    "invalid toad = {n} for wits = {wits:?}",
-                    n = toader.num()?
-                )));
-            }
-        } else if toader.num()? != 0 {
-            return err!(Error::Value(format!(
-                "invalid toad = {n} for wits = {wits:?}",
-                n = toader.num()?
-            )));
-        }
-
-        let cnfg = cnfg.unwrap_or(&[]);
-        let data = data.unwrap_or(&[]);
-
-        let kt = if let Some(n) = tholder.num()? {
-            if intive && n < u32::MAX {
-                dat!(n)
-            } else {
-                tholder.sith()?
-            }
-        } else {
-            tholder.sith()?
-        };
-
-        let nt = if let Some(n) = ntholder.num()? {
-            if intive && n < u32::MAX {
-                dat!(n)
-            } else {
-                ntholder.sith()?
-            }
-        } else {
-            ntholder.sith()?
-        };
-
-        let toad = if intive && toader.num()? < u32::MAX as u128 {
-            dat!(toader.num()? as i64)
-        } else {
-            dat!(&toader.numh()?)
-        };
-
-        let keys: Vec<Value> = keys.iter().map(|key| dat!(*key)).collect();
-        let ndigs: Vec<Value> = ndigs.iter().map(|dig| dat!(*dig)).collect();
-        let wits: Vec<Value> = wits.iter().map(|wit| dat!(*wit)).collect();
-        let cnfg: Vec<Value> = cnfg.iter().map(|cfg| dat!(*cfg)).collect();
-
-        let mut ked = dat!({
-            "v": vs,
-            "t": ilk,
-            "d": "",
-            "i": "",
-            "s": &sner.numh()?,
-            "kt": kt,
-            "k": keys.as_slice(),
-            "nt": nt,
-            "n": ndigs.as_slice(),
-            "bt": toad,
-            "b": wits.as_slice(),
-            "c": cnfg.as_slice(),
-            "a": data
-        });
-
-        let code = if let Some(delpre) = delpre {
-            let label = Ids::di;
-            ked[label] = dat!(delpre);
-            Some(code.unwrap_or(matter::Codex::Blake3_256))
-        } else {
-            code
-        };
-
-        let prefixer = if delpre.is_none() && code.is_none() && keys.len() == 1 {
-            let prefixer = Prefixer::new_with_qb64(&keys[0].to_string()?)?;
-            if prefixer.digestive() {
-                return err!(Error::Value(format!(
-                    "invalid code, digestive = {c}, must be derived from ked",
-                    c = prefixer.code()
-                )));
-            }
-            prefixer
-        } else {
-            let prefixer = Prefixer::new_with_ked(&ked, None, code)?;
-            if delpre.is_some() && !prefixer.digestive() {
-                return err!(Error::Value(format!(
-                    "invalid derivation code = {c} for delegation, must be digestive",
-                    c = prefixer.code()
-                )));
-            }
-            prefixer
-        };
-
-        let label = Ids::i;
-        ked[label] = dat!(&prefixer.qb64()?);
-        let ked = if prefixer.digestive() {
-            let label = Ids::d;
-            ked[label] = dat!(&prefixer.qb64()?);
-            ked
-        } else {
-            let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
-            ked
-        };
-
-        Serder::new(None, None, None, Some(&ked), None)
-    }
-
-    fn ample(n: u128, f: Option<u128>, weak: Option<bool>) -> Result<u128> {
-        let weak = weak.unwrap_or(true);
-        let n = if n > 0 { n } else { 0 };
-        if let Some(f) = f {
-            let f = if f > 0 { f } else { 0 };
-            let m1 = (n + f + 2) / 2;
-            let m2 = if n - f > 0 { n - f } else { 0 };
-
-            if m2 < m1 && n > 0 {
-                return err!(Error::Value(format!("invalid f={f}, too big for n={n}")));
-            }
-
-            if weak {
-                match [n, m1, m2].iter().min() {
-                    Some(x) => Ok(*x),
-                    None => err!(Error::Value("unreachable".to_string())),
-                }
-            } else {
-                Ok(std::cmp::min(n, std::cmp::max(m1, m2)))
-            }
-        } else {
-            let f1 = std::cmp::max(1, std::cmp::max(0, n - 1) / 3);
-            let f2 = std::cmp::max(1, (std::cmp::max(0, n - 1) + 2) / 3);
-
-            if weak {
-                match [n, (n + f1 + 3) / 2, (n + f2 + 3) / 2].iter().min() {
-                    Some(x) => Ok(*x),
-                    None => err!(Error::Value("unreachable".to_string())),
-                }
-            } else {
-                match [0, n - f1, (n + f1 + 3) / 2].iter().max() {
-                    Some(x) => Ok(std::cmp::min(n, *x)),
-                    None => err!(Error::Value("unreachable".to_string())),
-                }
-            }
-        }
-    }
 }
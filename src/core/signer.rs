@@ -61,7 +61,7 @@ fn validate_code(code: &str) -> Result<()> {
         matter::Codex::Ed25519_Seed,
         matter::Codex::ECDSA_256k1_Seed,
         matter::Codex::ECDSA_256r1_Seed,
-        // matter::Codex::Ed448_Seed,
+        matter::Codex::Ed448_Seed,
     ];
 
     if !CODES.contains(&code) {
@@ -77,12 +77,14 @@ fn derive_verfer(code: &str, private_key: &[u8], transferable: bool) -> Result<V
             matter::Codex::Ed25519_Seed => matter::Codex::Ed25519,
             matter::Codex::ECDSA_256k1_Seed => matter::Codex::ECDSA_256k1,
             matter::Codex::ECDSA_256r1_Seed => matter::Codex::ECDSA_256r1,
+            matter::Codex::Ed448_Seed => matter::Codex::Ed448,
             _ => return err!(Error::UnexpectedCode(code.to_string())),
         },
         false => match code {
             matter::Codex::Ed25519_Seed => matter::Codex::Ed25519N,
             matter::Codex::ECDSA_256k1_Seed => matter::Codex::ECDSA_256k1N,
             matter::Codex::ECDSA_256r1_Seed => matter::Codex::ECDSA_256r1N,
+            matter::Codex::Ed448_Seed => matter::Codex::Ed448N,
             _ => return err!(Error::UnexpectedCode(code.to_string())),
         },
     };
@@ -148,6 +150,7 @@ impl Signer {
             matter::Codex::Ed25519_Seed => matter::Codex::Ed25519_Sig,
             matter::Codex::ECDSA_256k1_Seed => matter::Codex::ECDSA_256k1_Sig,
             matter::Codex::ECDSA_256r1_Seed => matter::Codex::ECDSA_256r1_Sig,
+            matter::Codex::Ed448_Seed => matter::Codex::Ed448_Sig,
             _ => return err!(Error::UnexpectedCode(self.code())),
         };
 
@@ -169,6 +172,7 @@ impl Signer {
                     matter::Codex::Ed25519_Seed => indexer::Codex::Ed25519_Crt,
                     matter::Codex::ECDSA_256k1_Seed => indexer::Codex::ECDSA_256k1_Crt,
                     matter::Codex::ECDSA_256r1_Seed => indexer::Codex::ECDSA_256r1_Crt,
+                    matter::Codex::Ed448_Seed => indexer::Codex::Ed448_Crt,
                     _ => return err!(Error::UnexpectedCode(self.code())),
                 }
             } else {
@@ -176,6 +180,7 @@ impl Signer {
                     matter::Codex::Ed25519_Seed => indexer::Codex::Ed25519_Big_Crt,
                     matter::Codex::ECDSA_256k1_Seed => indexer::Codex::ECDSA_256k1_Big_Crt,
                     matter::Codex::ECDSA_256r1_Seed => indexer::Codex::ECDSA_256r1_Big_Crt,
+                    matter::Codex::Ed448_Seed => indexer::Codex::Ed448_Big_Crt,
                     _ => return err!(Error::UnexpectedCode(self.code())),
                 }
             };
@@ -189,6 +194,7 @@ impl Signer {
                     matter::Codex::Ed25519_Seed => indexer::Codex::Ed25519,
                     matter::Codex::ECDSA_256k1_Seed => indexer::Codex::ECDSA_256k1,
                     matter::Codex::ECDSA_256r1_Seed => indexer::Codex::ECDSA_256r1,
+                    matter::Codex::Ed448_Seed => indexer::Codex::Ed448,
                     _ => return err!(Error::UnexpectedCode(self.code())),
                 }
             } else {
@@ -196,6 +202,7 @@ impl Signer {
                     matter::Codex::Ed25519_Seed => indexer::Codex::Ed25519_Big,
                     matter::Codex::ECDSA_256k1_Seed => indexer::Codex::ECDSA_256k1_Big,
                     matter::Codex::ECDSA_256r1_Seed => indexer::Codex::ECDSA_256r1_Big,
+                    matter::Codex::Ed448_Seed => indexer::Codex::Ed448_Big,
                     _ => return err!(Error::UnexpectedCode(self.code())),
                 }
             };
@@ -253,6 +260,7 @@ impl Matter for Signer {
 }
 
 #[cfg(test)]
+#[allow(clippy::too_many_arguments)]
 mod test {
     use crate::core::{
         cigar::Cigar,
@@ -410,7 +418,8 @@ mod test {
         #[values(
             matter::Codex::Ed25519_Seed,
             matter::Codex::ECDSA_256k1_Seed,
-            matter::Codex::ECDSA_256r1_Seed
+            matter::Codex::ECDSA_256r1_Seed,
+            matter::Codex::Ed448_Seed
         )]
         code: &str,
     ) {
@@ -485,6 +494,31 @@ mod test {
         assert!(!signer.verfer().verify(&cigar.raw(), bad_ser).unwrap());
     }
 
+    #[test]
+    fn sign_ed448_unindexed() {
+        let ser = b"abcdefghijklmnopqrstuvwxyz0123456789";
+        let bad_ser = b"abcdefghijklmnopqrstuvwxyz0123456789ABCDEFG";
+
+        let signer =
+            Signer::new(Some(true), Some(matter::Codex::Ed448_Seed), None, None, None, None)
+                .unwrap();
+        assert_eq!(signer.verfer().code(), matter::Codex::Ed448);
+
+        let cigar = signer.sign_unindexed(ser).unwrap();
+        assert_eq!(cigar.code(), matter::Codex::Ed448_Sig);
+        assert_eq!(cigar.raw().len(), matter::raw_size(matter::Codex::Ed448_Sig).unwrap() as usize);
+        assert!(signer.verfer().verify(&cigar.raw(), ser).unwrap());
+        assert!(!signer.verfer().verify(&cigar.raw(), bad_ser).unwrap());
+
+        let cigar = Cigar::new_with_qb64(&cigar.qb64().unwrap(), None).unwrap();
+        assert!(signer.verfer().verify(&cigar.raw(), ser).unwrap());
+
+        let signer =
+            Signer::new(Some(false), Some(matter::Codex::Ed448_Seed), None, None, None, None)
+                .unwrap();
+        assert_eq!(signer.verfer().code(), matter::Codex::Ed448N);
+    }
+
     #[rstest]
     #[case(false, 0, None, 0, indexer::Codex::Ed25519)]
     #[case(false, 1, None, 1, indexer::Codex::Ed25519)]
@@ -529,6 +563,34 @@ mod test {
         assert!(!signer.verfer().verify(&siger.raw(), bad_ser).unwrap());
     }
 
+    #[rstest]
+    #[case(false, 0, None, 0, indexer::Codex::Ed448)]
+    #[case(false, 1, Some(3), 3, indexer::Codex::Ed448_Big)]
+    #[case(false, 67, Some(67), 67, indexer::Codex::Ed448_Big)]
+    #[case(true, 4, None, 0, indexer::Codex::Ed448_Crt)]
+    #[case(true, 65, None, 0, indexer::Codex::Ed448_Big_Crt)]
+    fn sign_ed448_indexed(
+        #[case] only: bool,
+        #[case] index: u32,
+        #[case] input_ondex: Option<u32>,
+        #[case] output_ondex: u32,
+        #[case] siger_code: &str,
+    ) {
+        let ser = b"abcdefghijklmnopqrstuvwxyz0123456789";
+        let bad_ser = b"abcdefghijklmnopqrstuvwxyz0123456789ABCDEFG";
+
+        let signer =
+            Signer::new(Some(true), Some(matter::Codex::Ed448_Seed), None, None, None, None)
+                .unwrap();
+
+        let siger = signer.sign_indexed(ser, only, index, input_ondex).unwrap();
+        assert_eq!(siger.code(), siger_code);
+        assert_eq!(siger.index(), index);
+        assert_eq!(siger.ondex(), output_ondex);
+        assert!(signer.verfer().verify(&siger.raw(), ser).unwrap());
+        assert!(!signer.verfer().verify(&siger.raw(), bad_ser).unwrap());
+    }
+
     #[rstest]
     #[case(false, 0, None, 0, indexer::Codex::ECDSA_256k1)]
     #[case(false, 1, None, 1, indexer::Codex::ECDSA_256k1)]
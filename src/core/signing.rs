@@ -0,0 +1,346 @@
+use crate::{
+    core::{
+        attachments::Group,
+        bexter::Bext,
+        common::{dumps, Serialage},
+        kever::verify_trans,
+        matter::Matter,
+        pather::Pather,
+        prefixer::Prefixer,
+        saider::Saider,
+        seal::SealEvent,
+        seqner::Seqner,
+        serder::Serder,
+        signer::Signer,
+        verfer::Verfer,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+// the path from the root of the SAD
+fn full(pather: &Pather, root: Option<&Pather>) -> Result<Pather> {
+    match root {
+        Some(root) => pather.root(root),
+        None => Ok(pather.clone()),
+    }
+}
+
+/// The serialization of the sub-SAD of `sad` at `pather` that SAD path signatures are made
+/// over: a map or list is serialized as `kind`, JSON by default, and a string, such as the said
+/// of a compacted section, as its bytes.
+pub fn serialize(sad: &Value, pather: &Pather, kind: Option<&str>) -> Result<Vec<u8>> {
+    match pather.resolve(sad)? {
+        value @ (Value::Object(_) | Value::Array(_)) => dumps(&value, kind),
+        Value::String(string) => Ok(string.into_bytes()),
+        _ => err!(Error::Value(format!(
+            "invalid sub-SAD at path = {path}, must be a map, list or string",
+            path = pather.bext()?
+        ))),
+    }
+}
+
+/// Signs the sub-SADs of `sad` at each of `paths` with `signers`, returning a SadPathSig group,
+/// or a SadPathSigGroup when the paths are relative to `root`.
+///
+/// Transferable signers must be sealed to their establishment event by `seal`, and their
+/// signatures are attached as a TransIdxSigGroup indexed by their position in `signers`.
+/// Non-transferable signers are attached as NonTransReceiptCouples.
+pub fn sign(
+    sad: &Value,
+    kind: Option<&str>, // serialization of the sub-SADs
+    root: Option<&Pather>,
+    paths: &[Pather],
+    signers: &[Signer],
+    seal: Option<&SealEvent>, // signers' establishment event, when transferable
+) -> Result<Group> {
+    let kind = kind.unwrap_or(Serialage::JSON);
+    if paths.is_empty() || signers.is_empty() {
+        return err!(Error::Value("missing paths or signers".to_string()));
+    }
+
+    let prefixers = signers
+        .iter()
+        .map(|signer| Prefixer::new_with_qb64b(&signer.verfer().qb64b()?))
+        .collect::<Result<Vec<_>>>()?;
+    let transferable = prefixers[0].transferable();
+    if prefixers.iter().any(|prefixer| prefixer.transferable() != transferable) {
+        return err!(Error::Value("mixed transferable and non-transferable signers".to_string()));
+    }
+    if transferable && seal.is_none() {
+        return err!(Error::Value("missing seal for transferable signers".to_string()));
+    }
+
+    let mut sigs = vec![];
+    for pather in paths {
+        let ser = serialize(sad, &full(pather, root)?, Some(kind))?;
+        let group = match seal {
+            Some(seal) if transferable => {
+                let sigers = signers
+                    .iter()
+                    .enumerate()
+                    .map(|(index, signer)| signer.sign_indexed(&ser, false, index as u32, None))
+                    .collect::<Result<Vec<_>>>()?;
+                Group::TransIdxSigGroups(vec![(
+                    Prefixer::new_with_qb64(&seal.i())?,
+                    Seqner::new_with_sn(seal.s())?,
+                    Saider::new_with_qb64(&seal.d())?,
+                    sigers,
+                )])
+            }
+            _ => Group::NonTransReceiptCouples(
+                signers
+                    .iter()
+                    .zip(&prefixers)
+                    .map(|(signer, prefixer)| Ok((prefixer.clone(), signer.sign_unindexed(&ser)?)))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+        };
+        sigs.push((pather.clone(), group));
+    }
+
+    Ok(match root {
+        Some(root) => Group::SadPathSigGroup(root.clone(), sigs),
+        None => Group::SadPathSig(sigs),
+    })
+}
+
+/// Verifies a SadPathSig or SadPathSigGroup `group` attached to `sad`, returning the paths
+/// from the root of `sad` that it signs. Transferable signatures are checked against the keys
+/// and threshold of the establishment event in `validators` that they are sealed to, which must
+/// be the signer's latest establishment event there.
+pub fn verify(
+    sad: &Value,
+    kind: Option<&str>, // serialization of the sub-SADs
+    group: &Group,
+    validators: &[Serder],
+) -> Result<Vec<Pather>> {
+    let (root, sigs) = match group {
+        Group::SadPathSig(sigs) => (None, sigs),
+        Group::SadPathSigGroup(root, sigs) => (Some(root), sigs),
+        _ => return err!(Error::Validation(format!("unexpected group = {group:?}"))),
+    };
+
+    let mut paths = vec![];
+    for (pather, group) in sigs {
+        let pather = full(pather, root)?;
+        let path = pather.bext()?;
+        let ser = serialize(sad, &pather, kind)?;
+
+        match group {
+            Group::TransIdxSigGroups(groups) => {
+                for (prefixer, seqner, saider, sigers) in groups {
+                    verify_trans(&ser, validators, prefixer, seqner, saider, sigers)?;
+                }
+            }
+            Group::NonTransReceiptCouples(couples) => {
+                for (prefixer, cigar) in couples {
+                    let pre = prefixer.qb64()?;
+                    if prefixer.transferable() {
+                        return err!(Error::Validation(format!(
+                            "transferable signer without seal at path = {path}: pre = {pre}"
+                        )));
+                    }
+
+                    let verfer = Verfer::new_with_qb64b(&prefixer.qb64b()?)?;
+                    if !verfer.verify(&cigar.raw(), &ser)? {
+                        return err!(Error::Validation(format!(
+                            "invalid signature at path = {path}: pre = {pre}"
+                        )));
+                    }
+                }
+            }
+            _ => {
+                return err!(Error::Validation(format!(
+                    "unsupported signatures at path = {path}: group = {group:?}"
+                )))
+            }
+        }
+
+        paths.push(pather);
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{serialize, sign, verify};
+    use crate::core::{
+        attachments::{Attachments, Group},
+        bexter::Bext,
+        common::dumps,
+        eventing::rotate,
+        fixtures::identifier,
+        matter::Matter,
+        pather::Pather,
+        proving::credential,
+        sadder::Sadder,
+        seal::SealEvent,
+        signer::Signer,
+    };
+    use crate::data::Value;
+
+    const DT: &str = "2023-04-30T00:34:11.853572+00:00";
+    const SCHEMA: &str = "EE5uDJTq5cc6AEdqbyMpvARUjsK_chNdInf3xyRoCBcT";
+
+    fn pather(path: &[&str]) -> Pather {
+        Pather::new_with_path(&Value::Array(path.iter().map(|s| dat!(*s)).collect())).unwrap()
+    }
+
+    fn roundtrip(group: &Group) -> Group {
+        let attachments = Attachments::new_with_qb64b(&group.qb64b().unwrap()).unwrap();
+        let groups = attachments.groups();
+        assert_eq!(groups.len(), 1);
+        groups[0].clone()
+    }
+
+    #[test]
+    fn sad_path_sig_group() {
+        let (signer, icp) = identifier(1);
+        let (other, other_icp) = identifier(2);
+
+        let (acdc, _) = credential(
+            &icp.pre().unwrap(),
+            SCHEMA,
+            &dat!({ "dt": DT }),
+            Some(&other_icp.pre().unwrap()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let mut sad = dat!({
+            "t": "exn",
+            "e": {
+                "acdc": acdc.ked()
+            }
+        });
+
+        let root = pather(&["e"]);
+        let seal = SealEvent::new_with_serder(&icp).unwrap();
+        let group = sign(
+            &sad,
+            None,
+            Some(&root),
+            &[pather(&["acdc"])],
+            std::slice::from_ref(&signer),
+            Some(&seal),
+        )
+        .unwrap();
+        assert!(matches!(group, Group::SadPathSigGroup(..)));
+        assert!(group.qb64().unwrap().starts_with("-K"));
+
+        let group = roundtrip(&group);
+        let paths = verify(&sad, None, &group, std::slice::from_ref(&icp)).unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].bext().unwrap(), "-e-acdc");
+        assert_eq!(serialize(&sad, &paths[0], None).unwrap(), dumps(&acdc.ked(), None).unwrap());
+
+        // unknown establishment event
+        assert!(verify(&sad, None, &group, std::slice::from_ref(&other_icp)).is_err());
+        assert!(verify(&sad, None, &group, &[]).is_err());
+
+        // signed with keys that have since been rotated out
+        let rot = rotate(
+            &icp.pre().unwrap(),
+            &[&signer.verfer().qb64().unwrap()],
+            &icp.said().unwrap(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(verify(&sad, None, &group, &[icp.clone(), rot]).is_err());
+
+        // signed by the wrong keys
+        let seal = SealEvent::new_with_serder(&icp).unwrap();
+        let forged =
+            sign(&sad, None, Some(&root), &[pather(&["acdc"])], &[other], Some(&seal)).unwrap();
+        assert!(verify(&sad, None, &forged, std::slice::from_ref(&icp)).is_err());
+
+        // tampered sub-SAD
+        sad["e"]["acdc"]["a"]["dt"] = dat!("2024-04-30T00:34:11.853572+00:00");
+        assert!(verify(&sad, None, &group, &[icp]).is_err());
+    }
+
+    #[test]
+    fn sad_path_sig() {
+        let signer = Signer::new_with_raw(&[3; 32], Some(false), None).unwrap();
+        let (acdc, _) = credential(
+            "EIaGMMWJFPmtXznY1IIiKDIrg-vIyge6mBl2QV8dDjI3",
+            SCHEMA,
+            &dat!({ "dt": DT, "LEI": "254900OPPU84GM83MG36" }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let mut sad = acdc.ked();
+
+        let paths = [pather(&["a"]), pather(&["s"])];
+        let group = sign(&sad, None, None, &paths, &[signer], None).unwrap();
+        assert!(matches!(group, Group::SadPathSig(..)));
+        assert!(group.qb64().unwrap().starts_with("-J"));
+
+        let group = roundtrip(&group);
+        let signed = verify(&sad, None, &group, &[]).unwrap();
+        assert_eq!(
+            signed.iter().map(|pather| pather.bext().unwrap()).collect::<Vec<_>>(),
+            vec!["-a", "-s"]
+        );
+
+        sad["a"]["LEI"] = dat!("984500E5DEFDBQ1O9038");
+        assert!(verify(&sad, None, &group, &[]).is_err());
+    }
+
+    #[test]
+    fn signing_unhappy_paths() {
+        let (signer, icp) = identifier(1);
+        let nontrans = Signer::new_with_raw(&[3; 32], Some(false), None).unwrap();
+        let sad = dat!({ "d": "", "n": 1, "a": { "x": "y" } });
+        let paths = [pather(&["a"])];
+        let seal = SealEvent::new_with_serder(&icp).unwrap();
+
+        // nothing to sign
+        assert!(sign(&sad, None, None, &[], std::slice::from_ref(&nontrans), None).is_err());
+        assert!(sign(&sad, None, None, &paths, &[], None).is_err());
+        // transferable signers must be sealed
+        assert!(sign(&sad, None, None, &paths, std::slice::from_ref(&signer), None).is_err());
+        // mixed signers
+        assert!(sign(&sad, None, None, &paths, &[signer, nontrans.clone()], Some(&seal)).is_err());
+        // sub-SADs must be maps, lists or strings
+        assert!(sign(&sad, None, None, &[pather(&["n"])], std::slice::from_ref(&nontrans), None)
+            .is_err());
+        assert!(sign(&sad, None, None, &[pather(&["z"])], std::slice::from_ref(&nontrans), None)
+            .is_err());
+
+        // only sad path groups are verifiable
+        let siger = nontrans.sign_indexed(&[0u8], false, 0, None).unwrap();
+        assert!(verify(&sad, None, &Group::ControllerIdxSigs(vec![siger.clone()]), &[]).is_err());
+        let nested =
+            Group::SadPathSig(vec![(pather(&["a"]), Group::ControllerIdxSigs(vec![siger]))]);
+        assert!(verify(&sad, None, &nested, &[]).is_err());
+    }
+}
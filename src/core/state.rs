@@ -0,0 +1,780 @@
+use crate::{
+    core::{
+        attachments::Group,
+        common::{dumps, Ids, Ilkage, Serialage, Version, CURRENT_VERSION},
+        dater::Dater,
+        diger::Diger,
+        eventing::validate_toad,
+        kever::{verify_sigs, Kever},
+        matter::Matter,
+        number::Number,
+        prefixer::Prefixer,
+        sadder::Sadder,
+        saider::Saider,
+        serder::Serder,
+        tholder::Tholder,
+        verfer::Verfer,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+// the canonical field order of a key state notice
+const LABELS: &[&str] = &[
+    Ids::vn,
+    Ids::i,
+    Ids::s,
+    Ids::p,
+    Ids::d,
+    Ids::f,
+    Ids::dt,
+    Ids::et,
+    Ids::kt,
+    Ids::k,
+    Ids::nt,
+    Ids::n,
+    Ids::bt,
+    Ids::b,
+    Ids::c,
+    Ids::ee,
+    Ids::di,
+];
+const EST_LABELS: &[&str] = &[Ids::s, Ids::d, Ids::br, Ids::ba];
+const KSN_ROUTE: &str = "/ksn";
+
+fn now() -> Result<String> {
+    Dater::new(None, None, None, None, None, None)?.dts()
+}
+
+fn validate_labels(ked: &Value, labels: &[&str]) -> Result<()> {
+    let keys = ked.to_map()?.keys().cloned().collect::<Vec<_>>();
+    if keys != labels {
+        return err!(Error::Value(format!(
+            "invalid key state fields = {keys:?}, expected {labels:?}"
+        )));
+    }
+
+    Ok(())
+}
+
+fn strings(value: &Value) -> Result<Vec<String>> {
+    value.to_vec()?.iter().map(|v| v.to_string()).collect()
+}
+
+fn values(items: &[String]) -> Value {
+    Value::Array(items.iter().map(|item| dat!(item.as_str())).collect())
+}
+
+/// The latest establishment event of a key state, with the witnesses it cut and added.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateEst {
+    pub s: u128,
+    pub d: String,
+    pub br: Vec<String>,
+    pub ba: Vec<String>,
+}
+
+/// Key state notice (ksn) for a single identifier: a snapshot of its key state at its latest
+/// event, either derived from a validated key event log or received from another party, such as
+/// a witness, in a `/ksn` reply.
+///
+/// A received key state lets a client verify signatures made with the current keys without
+/// replaying the key event log, provided it trusts the signers of the reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyState {
+    version: Version,
+    prefixer: Prefixer,
+    sner: Number,
+    prior: String,
+    said: String,
+    fner: Number,
+    dt: String,
+    et: String,
+    tholder: Tholder,
+    verfers: Vec<Verfer>,
+    ntholder: Tholder,
+    digers: Vec<Diger>,
+    toader: Number,
+    wits: Vec<String>,
+    cnfg: Vec<String>,
+    est: StateEst,
+    delpre: Option<String>,
+}
+
+impl KeyState {
+    /// Builds the key state of `kever` as of `dt`, now by default, or parses a key state notice
+    /// `ked` in canonical field order.
+    pub fn new(kever: Option<&Kever>, dt: Option<&str>, ked: Option<&Value>) -> Result<Self> {
+        if let Some(kever) = kever {
+            let dt = match dt {
+                Some(dt) => Dater::new_with_dts(dt, None)?.dts()?,
+                None => now()?,
+            };
+            let serder = kever.serder();
+            let prior = if kever.sn()? == 0 {
+                "".to_string()
+            } else {
+                let label = Ids::p;
+                serder.ked()[label].to_string()?
+            };
+            let last_est = kever.last_est();
+            let et = if last_est.s == kever.sn()? {
+                kever.ilk()
+            } else if kever.delpre().is_some() {
+                if last_est.s == 0 {
+                    Ilkage::dip.to_string()
+                } else {
+                    Ilkage::drt.to_string()
+                }
+            } else if last_est.s == 0 {
+                Ilkage::icp.to_string()
+            } else {
+                Ilkage::rot.to_string()
+            };
+
+            Ok(KeyState {
+                version: CURRENT_VERSION.clone(),
+                prefixer: kever.prefixer(),
+                sner: kever.sner(),
+                prior,
+                said: serder.said()?,
+                // a log validated in order, without recovery, is first seen in order
+                fner: kever.sner(),
+                dt,
+                et,
+                tholder: kever.tholder(),
+                verfers: kever.verfers(),
+                ntholder: kever.ntholder(),
+                digers: kever.digers(),
+                toader: kever.toader(),
+                wits: kever.wits(),
+                cnfg: kever.cnfg(),
+                est: StateEst { s: last_est.s, d: last_est.d, br: kever.cuts(), ba: kever.adds() },
+                delpre: kever.delpre(),
+            })
+        } else if let Some(ked) = ked {
+            Self::parse(ked)
+        } else {
+            err!(Error::Value("missing kever or ked".to_string()))
+        }
+    }
+
+    pub fn new_with_kever(kever: &Kever, dt: Option<&str>) -> Result<Self> {
+        Self::new(Some(kever), dt, None)
+    }
+
+    pub fn new_with_ked(ked: &Value) -> Result<Self> {
+        Self::new(None, None, Some(ked))
+    }
+
+    /// Parses the key state carried by the `/ksn` reply `rpy`.
+    pub fn new_with_reply(rpy: &Serder) -> Result<Self> {
+        let ked = rpy.ked();
+        let label = Ids::t;
+        let ilk = ked[label].to_string()?;
+        if ilk != Ilkage::rpy {
+            return err!(Error::Validation(format!("expected reply, got ilk = {ilk}")));
+        }
+
+        let label = Ids::r;
+        let route = ked[label].to_string()?;
+        if route != KSN_ROUTE && !route.starts_with(&format!("{KSN_ROUTE}/")) {
+            return err!(Error::Validation(format!("unexpected reply route = {route}")));
+        }
+
+        let label = Ids::a;
+        Self::new_with_ked(&ked[label])
+    }
+
+    fn parse(ked: &Value) -> Result<Self> {
+        validate_labels(ked, LABELS)?;
+
+        let label = Ids::vn;
+        let vn = ked[label].to_vec()?;
+        let version = match vn.as_slice() {
+            [major, minor] => Version {
+                major: u8::try_from(major.to_i64()?)?,
+                minor: u8::try_from(minor.to_i64()?)?,
+            },
+            _ => return err!(Error::Value(format!("invalid key state version = {vn:?}"))),
+        };
+
+        let label = Ids::i;
+        let prefixer = Prefixer::new_with_qb64(&ked[label].to_string()?)?;
+        let label = Ids::s;
+        let sner = Number::new_with_numh(&ked[label].to_string()?)?;
+        let label = Ids::p;
+        let prior = ked[label].to_string()?;
+        if sner.num()? > 0 {
+            Saider::new_with_qb64(&prior)?;
+        }
+        let label = Ids::d;
+        let said = ked[label].to_string()?;
+        Saider::new_with_qb64(&said)?;
+        let label = Ids::f;
+        let fner = Number::new_with_numh(&ked[label].to_string()?)?;
+        let label = Ids::dt;
+        let dt = Dater::new_with_dts(&ked[label].to_string()?, None)?.dts()?;
+
+        let label = Ids::et;
+        let et = ked[label].to_string()?;
+        if ![Ilkage::icp, Ilkage::rot, Ilkage::dip, Ilkage::drt].contains(&et.as_str()) {
+            return err!(Error::Value(format!("invalid establishment ilk = {et}")));
+        }
+
+        let label = Ids::kt;
+        let tholder = Tholder::new_with_sith(&ked[label])?;
+        let label = Ids::k;
+        let verfers = strings(&ked[label])?
+            .iter()
+            .map(|key| Verfer::new_with_qb64(key))
+            .collect::<Result<Vec<_>>>()?;
+        let label = Ids::nt;
+        let ntholder = Tholder::new_with_sith(&ked[label])?;
+        let label = Ids::n;
+        let digers = strings(&ked[label])?
+            .iter()
+            .map(|dig| Diger::new_with_qb64(dig))
+            .collect::<Result<Vec<_>>>()?;
+
+        let label = Ids::bt;
+        let toader = Number::new_with_numh(&ked[label].to_string()?)?;
+        let label = Ids::b;
+        let wits = strings(&ked[label])?;
+        for (i, wit) in wits.iter().enumerate() {
+            if wits[..i].contains(wit) {
+                return err!(Error::Value(format!("invalid wits = {wits:?}, has duplicates")));
+            }
+        }
+        validate_toad(toader.num()?, &wits.iter().map(|wit| wit.as_str()).collect::<Vec<_>>())?;
+        let label = Ids::c;
+        let cnfg = strings(&ked[label])?;
+
+        let label = Ids::ee;
+        let ee = &ked[label];
+        validate_labels(ee, EST_LABELS)?;
+        let label = Ids::s;
+        let s = Number::new_with_numh(&ee[label].to_string()?)?.num()?;
+        if s > sner.num()? {
+            return err!(Error::Value(format!(
+                "invalid latest establishment sn = {s}, after sn = {sn}",
+                sn = sner.num()?
+            )));
+        }
+        let label = Ids::d;
+        let d = ee[label].to_string()?;
+        Saider::new_with_qb64(&d)?;
+        let label = Ids::br;
+        let br = strings(&ee[label])?;
+        let label = Ids::ba;
+        let ba = strings(&ee[label])?;
+
+        let label = Ids::di;
+        let di = ked[label].to_string()?;
+        let delegated = et == Ilkage::dip || et == Ilkage::drt;
+        let delpre = if di.is_empty() {
+            None
+        } else {
+            Prefixer::new_with_qb64(&di)?;
+            Some(di)
+        };
+        if delegated != delpre.is_some() {
+            return err!(Error::Value(format!(
+                "mismatched delegator = {di:?} for establishment ilk = {et}",
+                di = delpre
+            )));
+        }
+
+        Ok(KeyState {
+            version,
+            prefixer,
+            sner,
+            prior,
+            said,
+            fner,
+            dt,
+            et,
+            tholder,
+            verfers,
+            ntholder,
+            digers,
+            toader,
+            wits,
+            cnfg,
+            est: StateEst { s, d, br, ba },
+            delpre,
+        })
+    }
+
+    /// The key state notice, in canonical field order.
+    pub fn ked(&self) -> Result<Value> {
+        Ok(dat!({
+            "vn": [self.version.major as i64, self.version.minor as i64],
+            "i": &self.prefixer.qb64()?,
+            "s": &self.sner.numh()?,
+            "p": &self.prior,
+            "d": &self.said,
+            "f": &self.fner.numh()?,
+            "dt": &self.dt,
+            "et": &self.et,
+            "kt": self.tholder.sith()?,
+            "k": values(&self.verfers.iter().map(|verfer| verfer.qb64()).collect::<Result<Vec<_>>>()?),
+            "nt": self.ntholder.sith()?,
+            "n": values(&self.digers.iter().map(|diger| diger.qb64()).collect::<Result<Vec<_>>>()?),
+            "bt": &self.toader.numh()?,
+            "b": values(&self.wits),
+            "c": values(&self.cnfg),
+            "ee": {
+                "s": &Number::new_with_num(self.est.s)?.numh()?,
+                "d": &self.est.d,
+                "br": values(&self.est.br),
+                "ba": values(&self.est.ba)
+            },
+            "di": self.delpre.as_deref().unwrap_or("")
+        }))
+    }
+
+    /// The key state notice serialized as `kind`, JSON by default.
+    pub fn raw(&self, kind: Option<&str>) -> Result<Vec<u8>> {
+        dumps(&self.ked()?, Some(kind.unwrap_or(Serialage::JSON)))
+    }
+
+    /// Verifies the signatures `groups` attached to the `/ksn` reply `rpy`, which must carry
+    /// this key state, returning the prefixes of the parties whose signatures were accepted.
+    ///
+    /// Controller signatures must be sealed to the latest establishment event of the key state
+    /// and satisfy its signing threshold. Witness signatures must come from its witnesses.
+    /// Signatures from any other party are rejected, as is a reply with no accepted signers.
+    /// Whether the accepted signers are enough to trust the key state is left to the caller.
+    pub fn verify(&self, rpy: &Serder, groups: &[Group]) -> Result<Vec<String>> {
+        if Self::new_with_reply(rpy)? != *self {
+            return err!(Error::Validation(format!(
+                "mismatched key state in reply said = {said}",
+                said = rpy.said()?
+            )));
+        }
+
+        let pre = self.pre()?;
+        let ser = rpy.raw();
+        let mut signers = vec![];
+        for group in groups {
+            match group {
+                Group::TransIdxSigGroups(groups) => {
+                    for (prefixer, seqner, saider, sigers) in groups {
+                        if prefixer.qb64()? != pre
+                            || seqner.sn()? != self.est.s
+                            || saider.qb64()? != self.est.d
+                        {
+                            return err!(Error::Validation(format!(
+                                "unexpected reply signer = {signer}, sn = {sn}",
+                                signer = prefixer.qb64()?,
+                                sn = seqner.sn()?
+                            )));
+                        }
+
+                        let indices = verify_sigs(&ser, sigers, &self.verfers)?;
+                        if indices.len() != sigers.len() || !self.tholder.satisfy(&indices)? {
+                            return err!(Error::Validation(format!(
+                                "invalid controller signatures on reply said = {said}",
+                                said = rpy.said()?
+                            )));
+                        }
+                        if !signers.contains(&pre) {
+                            signers.push(pre.clone());
+                        }
+                    }
+                }
+                Group::NonTransReceiptCouples(couples) => {
+                    for (prefixer, cigar) in couples {
+                        let wit = prefixer.qb64()?;
+                        if !self.wits.contains(&wit) {
+                            return err!(Error::Validation(format!(
+                                "unexpected reply signer = {wit}, not a witness"
+                            )));
+                        }
+
+                        let verfer = Verfer::new_with_qb64(&wit)?;
+                        if !verfer.verify(&cigar.raw(), &ser)? {
+                            return err!(Error::Validation(format!(
+                                "invalid witness signature on reply said = {said}: wit = {wit}",
+                                said = rpy.said()?
+                            )));
+                        }
+                        if !signers.contains(&wit) {
+                            signers.push(wit);
+                        }
+                    }
+                }
+                _ => {
+                    return err!(Error::Validation(format!(
+                        "unexpected reply attachments = {group:?}"
+                    )))
+                }
+            }
+        }
+
+        if signers.is_empty() {
+            return err!(Error::Validation(format!(
+                "unsigned reply said = {said}",
+                said = rpy.said()?
+            )));
+        }
+
+        Ok(signers)
+    }
+
+    pub fn version(&self) -> Version {
+        self.version.clone()
+    }
+
+    pub fn prefixer(&self) -> Prefixer {
+        self.prefixer.clone()
+    }
+
+    pub fn pre(&self) -> Result<String> {
+        self.prefixer.qb64()
+    }
+
+    pub fn sner(&self) -> Number {
+        self.sner.clone()
+    }
+
+    pub fn sn(&self) -> Result<u128> {
+        self.sner.num()
+    }
+
+    /// The said of the event prior to the latest event, empty at inception.
+    pub fn prior(&self) -> String {
+        self.prior.clone()
+    }
+
+    /// The said of the latest event.
+    pub fn said(&self) -> String {
+        self.said.clone()
+    }
+
+    /// The first seen ordinal of the latest event.
+    pub fn fner(&self) -> Number {
+        self.fner.clone()
+    }
+
+    pub fn dt(&self) -> String {
+        self.dt.clone()
+    }
+
+    /// The ilk of the latest establishment event.
+    pub fn et(&self) -> String {
+        self.et.clone()
+    }
+
+    pub fn tholder(&self) -> Tholder {
+        self.tholder.clone()
+    }
+
+    pub fn verfers(&self) -> Vec<Verfer> {
+        self.verfers.clone()
+    }
+
+    pub fn ntholder(&self) -> Tholder {
+        self.ntholder.clone()
+    }
+
+    pub fn digers(&self) -> Vec<Diger> {
+        self.digers.clone()
+    }
+
+    pub fn toader(&self) -> Number {
+        self.toader.clone()
+    }
+
+    pub fn wits(&self) -> Vec<String> {
+        self.wits.clone()
+    }
+
+    pub fn cnfg(&self) -> Vec<String> {
+        self.cnfg.clone()
+    }
+
+    pub fn est(&self) -> StateEst {
+        self.est.clone()
+    }
+
+    pub fn delpre(&self) -> Option<String> {
+        self.delpre.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{KeyState, StateEst};
+    use crate::core::{
+        attachments::Group,
+        common::{versify, Traitage},
+        eventing::{incept, interact, rotate},
+        fixtures::{digs, keys, sign, signers, witnesses},
+        kever::Kever,
+        matter::tables as matter,
+        prefixer::Prefixer,
+        sadder::Sadder,
+        saider::Saider,
+        seqner::Seqner,
+        serder::Serder,
+        signer::Signer,
+        util::strs,
+    };
+    use crate::data::Value;
+
+    const DT: &str = "2023-04-30T00:34:11.853572+00:00";
+
+    // a kel of inception, rotation cutting the first witness, and interaction
+    fn kel(s0: &[Signer], s1: &[Signer], s2: &[Signer], wits: &[String]) -> Kever {
+        let icp = incept(
+            &strs(&keys(s0)),
+            Some(&dat!(2)),
+            Some(&strs(&digs(s1))),
+            Some(&dat!(2)),
+            Some(2),
+            Some(&strs(wits)),
+            Some(&[Traitage::DND]),
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .unwrap();
+        let mut kever = Kever::new(&icp, &sign(&icp.raw(), s0, &[0, 1])).unwrap();
+
+        let rot = rotate(
+            &kever.pre().unwrap(),
+            &strs(&keys(s1)),
+            &icp.said().unwrap(),
+            None,
+            None,
+            Some(&dat!(2)),
+            Some(&strs(&digs(s2))),
+            Some(&dat!(2)),
+            Some(1),
+            Some(&strs(wits)),
+            Some(&strs(&wits[..1])),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        kever.update(&rot, &sign(&rot.raw(), s1, &[0, 1])).unwrap();
+
+        let ixn = interact(&kever.pre().unwrap(), &rot.said().unwrap(), Some(2), None, None, None)
+            .unwrap();
+        kever.update(&ixn, &sign(&ixn.raw(), s1, &[1, 2])).unwrap();
+        kever
+    }
+
+    fn reply(route: &str, a: &Value) -> Serder {
+        let ked = dat!({
+            "v": &versify(None, None, None, Some(0)).unwrap(),
+            "t": "rpy",
+            "d": "",
+            "dt": DT,
+            "r": route,
+            "a": a.clone()
+        });
+        let (_, ked) = Saider::saidify(&ked, None, None, None, None).unwrap();
+        Serder::new_with_ked(&ked, None, None).unwrap()
+    }
+
+    #[test]
+    fn key_state() {
+        let (s0, s1, s2) = (signers(3, 0), signers(3, 3), signers(3, 6));
+        let wits = keys(&witnesses(3, 20))[..2].to_vec();
+        let kever = kel(&s0, &s1, &s2, &wits);
+
+        let state = KeyState::new_with_kever(&kever, Some(DT)).unwrap();
+        assert_eq!(state.pre().unwrap(), kever.pre().unwrap());
+        assert_eq!(state.sn().unwrap(), 2);
+        assert_eq!(state.fner().num().unwrap(), 2);
+        assert_eq!(state.said(), kever.serder().said().unwrap());
+        assert_eq!(state.prior(), kever.last_est().d);
+        assert_eq!(state.dt(), DT);
+        assert_eq!(state.et(), "rot");
+        assert_eq!(state.verfers(), kever.verfers());
+        assert_eq!(state.digers(), kever.digers());
+        assert_eq!(state.wits(), wits[1..]);
+        assert_eq!(state.toader().num().unwrap(), 1);
+        assert_eq!(state.cnfg(), vec![Traitage::DND]);
+        assert_eq!(
+            state.est(),
+            StateEst { s: 1, d: kever.last_est().d, br: wits[..1].to_vec(), ba: vec![] }
+        );
+        assert!(state.delpre().is_none());
+
+        let ked = state.ked().unwrap();
+        assert_eq!(
+            ked.to_map().unwrap().keys().cloned().collect::<Vec<_>>(),
+            vec![
+                "vn", "i", "s", "p", "d", "f", "dt", "et", "kt", "k", "nt", "n", "bt", "b", "c",
+                "ee", "di"
+            ]
+        );
+        assert_eq!(ked["vn"], dat!([1, 0]));
+        assert_eq!(ked["s"], dat!("2"));
+        assert_eq!(ked["kt"], dat!("2"));
+        assert_eq!(ked["ee"]["s"], dat!("1"));
+        assert_eq!(ked["di"], dat!(""));
+
+        let parsed = KeyState::new_with_ked(&ked).unwrap();
+        assert_eq!(parsed, state);
+        assert_eq!(parsed.raw(None).unwrap(), state.raw(None).unwrap());
+        assert!(state.raw(Some("CBOR")).unwrap() != state.raw(None).unwrap());
+
+        // at inception
+        let icp = incept(
+            &strs(&keys(&s0)),
+            None,
+            Some(&strs(&digs(&s1))),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(matter::Codex::Blake3_256),
+            None,
+            None,
+        )
+        .unwrap();
+        let kever = Kever::new(&icp, &sign(&icp.raw(), &s0, &[0, 1])).unwrap();
+        let state = KeyState::new_with_kever(&kever, None).unwrap();
+        assert_eq!(state.et(), "icp");
+        assert_eq!(state.prior(), "");
+        assert_eq!(state.est().d, icp.said().unwrap());
+        assert_eq!(KeyState::new_with_ked(&state.ked().unwrap()).unwrap(), state);
+    }
+
+    #[test]
+    fn reply_signatures() {
+        let (s0, s1, s2) = (signers(3, 0), signers(3, 3), signers(3, 6));
+        let witnesses = witnesses(3, 20);
+        let wits = keys(&witnesses)[..2].to_vec();
+        let kever = kel(&s0, &s1, &s2, &wits);
+        let state = KeyState::new_with_kever(&kever, Some(DT)).unwrap();
+        let pre = state.pre().unwrap();
+
+        let rpy = reply(&format!("/ksn/{wit}", wit = wits[1]), &state.ked().unwrap());
+        assert_eq!(KeyState::new_with_reply(&rpy).unwrap(), state);
+
+        // by the witness, without the key event log
+        let cigar = witnesses[1].sign_unindexed(&rpy.raw()).unwrap();
+        let couple = Group::NonTransReceiptCouples(vec![(
+            Prefixer::new_with_qb64(&wits[1]).unwrap(),
+            cigar.clone(),
+        )]);
+        assert_eq!(
+            state.verify(&rpy, std::slice::from_ref(&couple)).unwrap(),
+            vec![wits[1].clone()]
+        );
+
+        // by the controller, sealed to its latest establishment event
+        let est = state.est();
+        let controller = |sigers| {
+            Group::TransIdxSigGroups(vec![(
+                Prefixer::new_with_qb64(&pre).unwrap(),
+                Seqner::new_with_sn(est.s).unwrap(),
+                Saider::new_with_qb64(&est.d).unwrap(),
+                sigers,
+            )])
+        };
+        let group = controller(sign(&rpy.raw(), &s1, &[0, 2]));
+        assert_eq!(state.verify(&rpy, std::slice::from_ref(&group)).unwrap(), vec![pre.clone()]);
+        assert_eq!(
+            state.verify(&rpy, &[group, couple]).unwrap(),
+            vec![pre.clone(), wits[1].clone()]
+        );
+
+        // unsigned
+        assert!(state.verify(&rpy, &[]).is_err());
+        // below threshold, or signed with the rotated out keys
+        assert!(state.verify(&rpy, &[controller(sign(&rpy.raw(), &s1, &[0]))]).is_err());
+        assert!(state.verify(&rpy, &[controller(sign(&rpy.raw(), &s0, &[0, 1]))]).is_err());
+        // sealed to an earlier establishment event
+        let stale = Group::TransIdxSigGroups(vec![(
+            Prefixer::new_with_qb64(&pre).unwrap(),
+            Seqner::new_with_sn(0).unwrap(),
+            Saider::new_with_qb64(&est.d).unwrap(),
+            sign(&rpy.raw(), &s1, &[0, 1]),
+        )]);
+        assert!(state.verify(&rpy, &[stale]).is_err());
+        // by the cut witness
+        let cut = Group::NonTransReceiptCouples(vec![(
+            Prefixer::new_with_qb64(&wits[0]).unwrap(),
+            witnesses[0].sign_unindexed(&rpy.raw()).unwrap(),
+        )]);
+        assert!(state.verify(&rpy, &[cut]).is_err());
+        // a signature over another reply
+        let other = reply("/ksn", &state.ked().unwrap());
+        let forged = Group::NonTransReceiptCouples(vec![(
+            Prefixer::new_with_qb64(&wits[1]).unwrap(),
+            witnesses[1].sign_unindexed(&other.raw()).unwrap(),
+        )]);
+        assert!(state.verify(&rpy, &[forged]).is_err());
+        // a reply carrying another key state
+        let older =
+            KeyState::new_with_kever(&kever, Some("2022-04-30T00:34:11.853572+00:00")).unwrap();
+        let rpy = reply("/ksn", &older.ked().unwrap());
+        let couple = Group::NonTransReceiptCouples(vec![(
+            Prefixer::new_with_qb64(&wits[1]).unwrap(),
+            witnesses[1].sign_unindexed(&rpy.raw()).unwrap(),
+        )]);
+        assert!(state.verify(&rpy, &[couple]).is_err());
+    }
+
+    #[test]
+    fn key_state_unhappy_paths() {
+        let (s0, s1, s2) = (signers(3, 0), signers(3, 3), signers(3, 6));
+        let wits = keys(&witnesses(3, 20))[..2].to_vec();
+        let kever = kel(&s0, &s1, &s2, &wits);
+        let ked = KeyState::new_with_kever(&kever, Some(DT)).unwrap().ked().unwrap();
+
+        assert!(KeyState::new(None, None, None).is_err());
+
+        // out of order or missing fields
+        let mut map = ked.to_map().unwrap();
+        map.swap_indices(0, 1);
+        assert!(KeyState::new_with_ked(&Value::from(&map)).is_err());
+        map.swap_indices(0, 1);
+        map.shift_remove("di");
+        assert!(KeyState::new_with_ked(&Value::from(&map)).is_err());
+
+        let invalid = |label: &str, value: Value| {
+            let mut ked = ked.clone();
+            ked[label] = value;
+            KeyState::new_with_ked(&ked).is_err()
+        };
+        assert!(invalid("vn", dat!([1])));
+        assert!(invalid("i", dat!("not a prefix")));
+        assert!(invalid("s", dat!("not hex")));
+        assert!(invalid("p", dat!("")));
+        assert!(invalid("dt", dat!("yesterday")));
+        assert!(invalid("et", dat!("ixn")));
+        assert!(invalid("k", dat!(["not a key"])));
+        assert!(invalid("bt", dat!("3")));
+        assert!(invalid("b", dat!([&wits[1], &wits[1]])));
+        // a delegator without a delegated establishment event
+        assert!(invalid("di", dat!(&kever.pre().unwrap())));
+        assert!(invalid("et", dat!("drt")));
+
+        let mut ee = ked["ee"].clone();
+        ee["s"] = dat!("3");
+        assert!(invalid("ee", ee));
+        assert!(invalid("ee", dat!({ "s": "1" })));
+
+        // not a ksn reply
+        assert!(KeyState::new_with_reply(&reply("/end/role/add", &ked)).is_err());
+        assert!(KeyState::new_with_reply(&reply("/ksnx", &ked)).is_err());
+        assert!(KeyState::new_with_reply(&kever.serder()).is_err());
+    }
+}
@@ -0,0 +1,459 @@
+use crate::{
+    core::{
+        common::{versify, Ids, Ilkage, Serialage, Traitage, Version, CURRENT_VERSION},
+        dater::Dater,
+        diger::Diger,
+        eventing::{rotate_wits, toader, validate_pres, validate_unique},
+        matter::{tables as matter, Matter},
+        number::Number,
+        prefixer::Prefixer,
+        saider::Saider,
+        salter::Salter,
+        seal::SealEvent,
+        serder::Serder,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+fn values(items: &[&str]) -> Vec<Value> {
+    items.iter().map(|item| dat!(*item)).collect()
+}
+
+fn now() -> Result<String> {
+    Dater::new(None, None, None, None, None, None)?.dts()
+}
+
+/// Builds a credential registry inception event for the registry of issuer `pre`. With the
+/// `NB` trait in `cnfg` the registry has no backers and credentials are issued with `iss`,
+/// otherwise with `bis`. A random `nonce` is used when none is given, so that one issuer can
+/// hold many registries.
+#[allow(clippy::too_many_arguments)]
+pub fn incept(
+    pre: &str,             // issuer identifier prefix qb64
+    toad: Option<u128>,    // backer threshold number
+    baks: Option<&[&str]>, // backer identifier prefixes qb64
+    nonce: Option<&str>,   // registry nonce qb64
+    cnfg: Option<&[&str]>, // configuration traits from common::Traitage
+    version: Option<&Version>,
+    kind: Option<&str>,
+    code: Option<&str>, // registry prefix derivation code, digestive
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+    let code = code.unwrap_or(matter::Codex::Blake3_256);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    Prefixer::new_with_qb64(pre)?;
+
+    let cnfg = cnfg.unwrap_or(&[]);
+    let baks = baks.unwrap_or(&[]);
+    if cnfg.contains(&Traitage::NB) && !baks.is_empty() {
+        return err!(Error::Value(format!("invalid baks = {baks:?} for registry without backers")));
+    }
+    validate_pres(baks)?;
+    validate_unique("baks", baks)?;
+    let toader = toader(toad, baks)?;
+
+    let nonce = match nonce {
+        Some(nonce) => nonce.to_string(),
+        None => Salter::new_with_defaults(None)?.qb64()?,
+    };
+
+    let mut ked = dat!({
+        "v": vs,
+        "t": Ilkage::vcp,
+        "d": "",
+        "i": "",
+        "ii": pre,
+        "s": "0",
+        "c": values(cnfg).as_slice(),
+        "bt": &toader.numh()?,
+        "b": values(baks).as_slice(),
+        "n": &nonce
+    });
+
+    let prefixer = Prefixer::new_with_ked(&ked, None, Some(code))?;
+    if !prefixer.digestive() {
+        return err!(Error::Value(format!(
+            "invalid derivation code = {c} for registry, must be digestive",
+            c = prefixer.code()
+        )));
+    }
+
+    let label = Ids::i;
+    ked[label] = dat!(&prefixer.qb64()?);
+    let label = Ids::d;
+    ked[label] = dat!(&prefixer.qb64()?);
+
+    Serder::new(Some(code), None, None, Some(&ked), None)
+}
+
+/// Builds a registry rotation event, changing the backers of registry `regk`. `baks` are the
+/// backers in effect before this event; `cuts` are removed from them and `adds` appended. The
+/// default `toad` is computed from the resulting backer list.
+#[allow(clippy::too_many_arguments)]
+pub fn rotate(
+    regk: &str,            // registry identifier prefix qb64
+    dig: &str,             // said of the prior registry event qb64
+    sn: Option<u128>,      // sequence number
+    toad: Option<u128>,    // backer threshold number after cuts and adds
+    baks: Option<&[&str]>, // prior backer identifier prefixes qb64
+    cuts: Option<&[&str]>, // backer prefixes to remove
+    adds: Option<&[&str]>, // backer prefixes to add
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    let sner = Number::new_with_num(sn.unwrap_or(1))?;
+    if sner.num()? < 1 {
+        return err!(Error::Value(format!(
+            "invalid sn = {sn} for registry rotation",
+            sn = sner.num()?
+        )));
+    }
+
+    Prefixer::new_with_qb64(regk)?;
+    Diger::new_with_qb64(dig)?;
+
+    let cuts = cuts.unwrap_or(&[]);
+    let adds = adds.unwrap_or(&[]);
+    let baks = rotate_wits(baks.unwrap_or(&[]), cuts, adds)?;
+    let toader = toader(toad, &baks.iter().map(|bak| bak.as_str()).collect::<Vec<_>>())?;
+
+    let ked = dat!({
+        "v": vs,
+        "t": Ilkage::vrt,
+        "d": "",
+        "i": regk,
+        "p": dig,
+        "s": &sner.numh()?,
+        "bt": &toader.numh()?,
+        "br": values(cuts).as_slice(),
+        "ba": values(adds).as_slice()
+    });
+
+    let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
+    Serder::new(None, None, None, Some(&ked), None)
+}
+
+/// Builds the issuance event of credential `vcdig` in registry `regk`, which has no backers.
+/// `dt` defaults to now.
+pub fn issue(
+    vcdig: &str,      // credential said qb64
+    regk: &str,       // registry identifier prefix qb64
+    dt: Option<&str>, // issuance datetime, ISO 8601
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    Saider::new_with_qb64(vcdig)?;
+    Prefixer::new_with_qb64(regk)?;
+    let dt = match dt {
+        Some(dt) => dt.to_string(),
+        None => now()?,
+    };
+
+    let ked = dat!({
+        "v": vs,
+        "t": Ilkage::iss,
+        "d": "",
+        "i": vcdig,
+        "s": "0",
+        "ri": regk,
+        "dt": &dt
+    });
+
+    let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
+    Serder::new(None, None, None, Some(&ked), None)
+}
+
+/// Builds the revocation event of credential `vcdig` in registry `regk`, which has no backers.
+/// `dig` is the said of the issuance event. `dt` defaults to now.
+pub fn revoke(
+    vcdig: &str,      // credential said qb64
+    regk: &str,       // registry identifier prefix qb64
+    dig: &str,        // said of the issuance event qb64
+    dt: Option<&str>, // revocation datetime, ISO 8601
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    Saider::new_with_qb64(vcdig)?;
+    Prefixer::new_with_qb64(regk)?;
+    Diger::new_with_qb64(dig)?;
+    let dt = match dt {
+        Some(dt) => dt.to_string(),
+        None => now()?,
+    };
+
+    let ked = dat!({
+        "v": vs,
+        "t": Ilkage::rev,
+        "d": "",
+        "i": vcdig,
+        "s": "1",
+        "ri": regk,
+        "p": dig,
+        "dt": &dt
+    });
+
+    let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
+    Serder::new(None, None, None, Some(&ked), None)
+}
+
+/// Builds the issuance event of credential `vcdig` in registry `regk`, which has backers. The
+/// event is sealed to the registry event `regd` at `regsn`, fixing the backers in effect. `dt`
+/// defaults to now.
+#[allow(clippy::too_many_arguments)]
+pub fn backer_issue(
+    vcdig: &str,      // credential said qb64
+    regk: &str,       // registry identifier prefix qb64
+    regsn: u128,      // sequence number of the registry event in effect
+    regd: &str,       // said of the registry event in effect
+    dt: Option<&str>, // issuance datetime, ISO 8601
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    Saider::new_with_qb64(vcdig)?;
+    let seal = SealEvent::new(regk, regsn, regd)?;
+    let dt = match dt {
+        Some(dt) => dt.to_string(),
+        None => now()?,
+    };
+
+    let ked = dat!({
+        "v": vs,
+        "t": Ilkage::bis,
+        "d": "",
+        "i": vcdig,
+        "ii": regk,
+        "s": "0",
+        "ra": seal.value(),
+        "dt": &dt
+    });
+
+    let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
+    Serder::new(None, None, None, Some(&ked), None)
+}
+
+/// Builds the revocation event of credential `vcdig` in registry `regk`, which has backers.
+/// `dig` is the said of the issuance event and the event is sealed to the registry event
+/// `regd` at `regsn`. `dt` defaults to now.
+#[allow(clippy::too_many_arguments)]
+pub fn backer_revoke(
+    vcdig: &str,      // credential said qb64
+    regk: &str,       // registry identifier prefix qb64
+    regsn: u128,      // sequence number of the registry event in effect
+    regd: &str,       // said of the registry event in effect
+    dig: &str,        // said of the issuance event qb64
+    dt: Option<&str>, // revocation datetime, ISO 8601
+    version: Option<&Version>,
+    kind: Option<&str>,
+) -> Result<Serder> {
+    let version = version.unwrap_or(CURRENT_VERSION);
+    let kind = kind.unwrap_or(Serialage::JSON);
+
+    let vs = &versify(None, Some(version), Some(kind), Some(0))?;
+    Saider::new_with_qb64(vcdig)?;
+    Diger::new_with_qb64(dig)?;
+    let seal = SealEvent::new(regk, regsn, regd)?;
+    let dt = match dt {
+        Some(dt) => dt.to_string(),
+        None => now()?,
+    };
+
+    let ked = dat!({
+        "v": vs,
+        "t": Ilkage::brv,
+        "d": "",
+        "i": vcdig,
+        "s": "1",
+        "p": dig,
+        "ra": seal.value(),
+        "dt": &dt
+    });
+
+    let (_, ked) = Saider::saidify(&ked, None, None, None, None)?;
+    Serder::new(None, None, None, Some(&ked), None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{backer_issue, backer_revoke, incept, issue, revoke, rotate};
+    use crate::core::{
+        common::{Ids, Ilkage, Traitage},
+        matter::{tables as matter, Matter},
+        prefixer::Prefixer,
+        sadder::Sadder,
+        saider::Saider,
+    };
+
+    const PRE: &str = "EBfdlu8R27Fbx-ehrqwImnK-8Cm79sqbAQ4MmvEAYqao";
+    const NONCE: &str = "0AAB2P4dlcMqwU6Y9zWwu6Rj";
+    const VCDIG: &str = "EDS6Z2lZ7zWBLBqBsvV_qPWFG0jR6ifdhjtt_9lMp0zE";
+    const DT: &str = "2020-08-22T17:50:09.988921+00:00";
+    const BAKS: &[&str] = &[
+        "BEy_EvE8OUMqj0AgCJ3wOCOrIVHVtwubYAysPyaAv9VI",
+        "BC9Df6ssUZQFQZJYVUyfudw4WTQsugGcvVD_Z4ChFGE4",
+        "BEejlxZytU7gjUwtgkmNKmBWiFPKSsXjk_uxzoun8dtK",
+    ];
+
+    #[test]
+    fn registry_inception() {
+        let vcp =
+            incept(PRE, None, None, Some(NONCE), Some(&[Traitage::NB]), None, None, None).unwrap();
+        let ked = vcp.ked();
+        assert_eq!(ked[Ids::t].to_string().unwrap(), Ilkage::vcp);
+        assert_eq!(ked[Ids::ii].to_string().unwrap(), PRE);
+        assert_eq!(ked[Ids::s].to_string().unwrap(), "0");
+        assert_eq!(ked[Ids::bt].to_string().unwrap(), "0");
+        assert_eq!(ked[Ids::c], dat!(["NB"]));
+        assert_eq!(ked[Ids::b], dat!([]));
+        assert_eq!(ked[Ids::n].to_string().unwrap(), NONCE);
+        assert_eq!(vcp.pre().unwrap(), vcp.said().unwrap());
+        assert!(Prefixer::new_with_qb64(&vcp.pre().unwrap())
+            .unwrap()
+            .verify(&ked, Some(true))
+            .unwrap());
+
+        // deterministic for a given nonce, random otherwise
+        let again =
+            incept(PRE, None, None, Some(NONCE), Some(&[Traitage::NB]), None, None, None).unwrap();
+        assert_eq!(again, vcp);
+        let random =
+            incept(PRE, None, None, None, Some(&[Traitage::NB]), None, None, None).unwrap();
+        assert_ne!(random.pre().unwrap(), vcp.pre().unwrap());
+
+        let vcp = incept(PRE, None, Some(BAKS), Some(NONCE), None, None, None, None).unwrap();
+        assert_eq!(vcp.ked()[Ids::bt].to_string().unwrap(), "3");
+        assert_eq!(vcp.ked()[Ids::c], dat!([]));
+        assert_eq!(vcp.ked()[Ids::b], dat!([BAKS[0], BAKS[1], BAKS[2]]));
+
+        let code = Some(matter::Codex::Blake2b_256);
+        let vcp = incept(PRE, Some(2), Some(BAKS), Some(NONCE), None, None, None, code).unwrap();
+        assert_eq!(vcp.ked()[Ids::bt].to_string().unwrap(), "2");
+        assert_eq!(
+            Prefixer::new_with_qb64(&vcp.pre().unwrap()).unwrap().code(),
+            matter::Codex::Blake2b_256
+        );
+    }
+
+    #[test]
+    fn registry_inception_unhappy_paths() {
+        let nb = Some(&[Traitage::NB][..]);
+        assert!(incept(PRE, None, Some(BAKS), None, nb, None, None, None).is_err());
+        assert!(incept(PRE, Some(1), None, None, nb, None, None, None).is_err());
+        assert!(incept(PRE, Some(4), Some(BAKS), None, None, None, None, None).is_err());
+        assert!(incept(PRE, Some(0), Some(BAKS), None, None, None, None, None).is_err());
+        assert!(incept(PRE, None, Some(&[BAKS[0], BAKS[0]]), None, None, None, None, None).is_err());
+        assert!(incept("", None, None, None, None, None, None, None).is_err());
+        let code = Some(matter::Codex::Ed25519);
+        assert!(incept(PRE, None, None, None, nb, None, None, code).is_err());
+    }
+
+    #[test]
+    fn registry_rotation() {
+        let vcp = incept(PRE, None, Some(BAKS), Some(NONCE), None, None, None, None).unwrap();
+        let regk = vcp.pre().unwrap();
+
+        let vrt = rotate(
+            &regk,
+            &vcp.said().unwrap(),
+            None,
+            None,
+            Some(BAKS),
+            Some(&[BAKS[0]]),
+            Some(&[PRE]),
+            None,
+            None,
+        )
+        .unwrap();
+        let ked = vrt.ked();
+        assert_eq!(ked[Ids::t].to_string().unwrap(), Ilkage::vrt);
+        assert_eq!(ked[Ids::i].to_string().unwrap(), regk);
+        assert_eq!(ked[Ids::p].to_string().unwrap(), vcp.said().unwrap());
+        assert_eq!(ked[Ids::s].to_string().unwrap(), "1");
+        assert_eq!(ked[Ids::bt].to_string().unwrap(), "3");
+        assert_eq!(ked[Ids::br], dat!([BAKS[0]]));
+        assert_eq!(ked[Ids::ba], dat!([PRE]));
+        assert!(Saider::new_with_qb64(&vrt.said().unwrap())
+            .unwrap()
+            .verify(&ked, Some(true), None, None, None, None)
+            .unwrap());
+
+        let dig = vcp.said().unwrap();
+        let cuts = Some(&[PRE][..]);
+        assert!(rotate(&regk, &dig, Some(0), None, Some(BAKS), None, None, None, None).is_err());
+        assert!(rotate(&regk, &dig, None, None, Some(BAKS), cuts, None, None, None).is_err());
+        assert!(rotate(&regk, &dig, None, Some(4), Some(BAKS), None, None, None, None).is_err());
+        assert!(rotate(&regk, "", None, None, Some(BAKS), None, None, None, None).is_err());
+    }
+
+    #[test]
+    fn credential_events() {
+        let vcp =
+            incept(PRE, None, None, Some(NONCE), Some(&[Traitage::NB]), None, None, None).unwrap();
+        let regk = vcp.pre().unwrap();
+
+        let iss = issue(VCDIG, &regk, Some(DT), None, None).unwrap();
+        assert_eq!(
+            iss.ked().to_map().unwrap().keys().collect::<Vec<_>>(),
+            vec!["v", "t", "d", "i", "s", "ri", "dt"]
+        );
+        assert_eq!(iss.pre().unwrap(), VCDIG);
+        assert_eq!(iss.sn().unwrap(), 0);
+        assert_eq!(iss.ked()[Ids::ri].to_string().unwrap(), regk);
+        assert_eq!(iss.ked()[Ids::dt].to_string().unwrap(), DT);
+
+        let rev = revoke(VCDIG, &regk, &iss.said().unwrap(), Some(DT), None, None).unwrap();
+        assert_eq!(
+            rev.ked().to_map().unwrap().keys().collect::<Vec<_>>(),
+            vec!["v", "t", "d", "i", "s", "ri", "p", "dt"]
+        );
+        assert_eq!(rev.sn().unwrap(), 1);
+        assert_eq!(rev.ked()[Ids::p].to_string().unwrap(), iss.said().unwrap());
+
+        // dt defaults to now
+        let iss = issue(VCDIG, &regk, None, None, None).unwrap();
+        assert_ne!(iss.ked()[Ids::dt].to_string().unwrap(), DT);
+
+        let vcp = incept(PRE, None, Some(BAKS), Some(NONCE), None, None, None, None).unwrap();
+        let regk = vcp.pre().unwrap();
+        let regd = vcp.said().unwrap();
+
+        let bis = backer_issue(VCDIG, &regk, 0, &regd, Some(DT), None, None).unwrap();
+        assert_eq!(
+            bis.ked().to_map().unwrap().keys().collect::<Vec<_>>(),
+            vec!["v", "t", "d", "i", "ii", "s", "ra", "dt"]
+        );
+        assert_eq!(bis.ked()[Ids::ii].to_string().unwrap(), regk);
+        assert_eq!(bis.ked()[Ids::ra], dat!({ "i": &regk, "s": "0", "d": &regd }));
+
+        let brv = backer_revoke(VCDIG, &regk, 0, &regd, &bis.said().unwrap(), Some(DT), None, None)
+            .unwrap();
+        assert_eq!(
+            brv.ked().to_map().unwrap().keys().collect::<Vec<_>>(),
+            vec!["v", "t", "d", "i", "s", "p", "ra", "dt"]
+        );
+        assert_eq!(brv.ked()[Ids::p].to_string().unwrap(), bis.said().unwrap());
+
+        assert!(issue("", &regk, None, None, None).is_err());
+        assert!(revoke(VCDIG, &regk, "", None, None, None).is_err());
+        assert!(backer_issue(VCDIG, &regk, 0, "", None, None, None).is_err());
+        assert!(backer_revoke(VCDIG, "", 0, &regd, &regd, None, None, None).is_err());
+    }
+}
@@ -0,0 +1,550 @@
+use indexmap::IndexMap;
+
+use crate::{
+    core::{
+        common::{Ids, Ilkage, Traitage},
+        eventing::{anchored, rotate_wits, validate_toad},
+        kever::verify_said,
+        matter::Matter,
+        number::Number,
+        prefixer::Prefixer,
+        sadder::Sadder,
+        seal::SealEvent,
+        serder::Serder,
+        util::strs,
+    },
+    data::Value,
+    error::{err, Error, Result},
+};
+
+fn ilk(serder: &Serder) -> Result<String> {
+    let label = Ids::t;
+    serder.ked()[label].to_string()
+}
+
+fn value(serder: &Serder, label: &str) -> Result<Value> {
+    match serder.ked().to_map()?.get(label) {
+        Some(value) => Ok(value.clone()),
+        None => err!(Error::Validation(format!(
+            "missing field = {label} in event said = {said}",
+            said = serder.said()?
+        ))),
+    }
+}
+
+fn field(serder: &Serder, label: &str) -> Result<String> {
+    value(serder, label)?.to_string()
+}
+
+fn strings(value: &Value) -> Result<Vec<String>> {
+    value.to_vec()?.iter().map(|item| item.to_string()).collect()
+}
+
+fn toader(value: &Value) -> Result<Number> {
+    Number::new_with_numh(&value.to_string()?)
+}
+
+/// Registry state for a credential registry, built by validating its transaction event log one
+/// event at a time.
+///
+/// Every event must be anchored by an event seal in the issuer's key event passed with it. The
+/// anchoring events are expected to come from the issuer's validated key event log; their
+/// signatures and backer receipts are not checked here.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tever {
+    prefixer: Prefixer,
+    issuer: String,
+    sner: Number,
+    serder: Serder,
+    toader: Number,
+    baks: Vec<String>,
+    no_backers: bool,
+    regds: Vec<String>, // saids of the registry events, by sequence number
+    credentials: IndexMap<String, Serder>, // latest event of each credential, by said
+}
+
+impl Tever {
+    /// Validates a registry inception event anchored by the issuer's key event `anchor`.
+    pub fn new(serder: &Serder, anchor: &Serder) -> Result<Self> {
+        verify_said(serder)?;
+
+        let ilk = ilk(serder)?;
+        if ilk != Ilkage::vcp {
+            return err!(Error::Validation(format!(
+                "expected registry inception event, got ilk = {ilk}"
+            )));
+        }
+
+        let sner = serder.sner()?;
+        if sner.num()? != 0 {
+            return err!(Error::Validation(format!(
+                "invalid sn = {sn} for registry inception event",
+                sn = sner.num()?
+            )));
+        }
+
+        let ked = serder.ked();
+        let prefixer = Prefixer::new_with_qb64(&serder.pre()?)?;
+        if !prefixer.verify(&ked, Some(true))? {
+            return err!(Error::Validation(format!(
+                "invalid prefix = {pre} for registry inception event",
+                pre = prefixer.qb64()?
+            )));
+        }
+
+        let issuer = field(serder, Ids::ii)?;
+        let cnfg = strings(&value(serder, Ids::c)?)?;
+        let no_backers = cnfg.iter().any(|trait_| trait_ == Traitage::NB);
+
+        let baks = strings(&value(serder, Ids::b)?)?;
+        if no_backers && !baks.is_empty() {
+            return err!(Error::Validation(format!(
+                "backers = {baks:?} for registry without backers"
+            )));
+        }
+        let toader = toader(&value(serder, Ids::bt)?)?;
+        validate_toad(toader.num()?, &strs(&baks))?;
+
+        let tever = Tever {
+            prefixer,
+            issuer,
+            sner,
+            serder: serder.clone(),
+            toader,
+            baks,
+            no_backers,
+            regds: vec![serder.said()?],
+            credentials: IndexMap::new(),
+        };
+        tever.validate_anchor(serder, anchor)?;
+
+        Ok(tever)
+    }
+
+    /// Validates the next registry event, or an event of a credential in the registry, and
+    /// applies it. The state is unchanged when validation fails.
+    pub fn update(&mut self, serder: &Serder, anchor: &Serder) -> Result<()> {
+        verify_said(serder)?;
+
+        let ilk = ilk(serder)?;
+        match ilk.as_str() {
+            Ilkage::vrt => {
+                let (baks, toader) = self.rotate(serder)?;
+                self.validate_anchor(serder, anchor)?;
+
+                self.baks = baks;
+                self.toader = toader;
+                self.sner = serder.sner()?;
+                self.serder = serder.clone();
+                self.regds.push(serder.said()?);
+            }
+            Ilkage::iss | Ilkage::rev | Ilkage::bis | Ilkage::brv => {
+                self.validate_credential(serder, &ilk)?;
+                self.validate_anchor(serder, anchor)?;
+
+                self.credentials.insert(serder.pre()?, serder.clone());
+            }
+            _ => {
+                return err!(Error::Validation(format!(
+                    "unexpected ilk = {ilk} for registry = {regk}",
+                    regk = self.pre()?
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_anchor(&self, serder: &Serder, anchor: &Serder) -> Result<()> {
+        if anchor.pre()? != self.issuer {
+            return err!(Error::Validation(format!(
+                "anchoring event of another identifier: pre = {pre}, expected {issuer}",
+                pre = anchor.pre()?,
+                issuer = self.issuer
+            )));
+        }
+
+        if !anchored(anchor, &SealEvent::new_with_serder(serder)?)? {
+            return err!(Error::Validation(format!(
+                "event said = {said} not anchored by issuer = {issuer}",
+                said = serder.said()?,
+                issuer = self.issuer
+            )));
+        }
+
+        Ok(())
+    }
+
+    // the backers and backer threshold after a valid rotation
+    fn rotate(&self, serder: &Serder) -> Result<(Vec<String>, Number)> {
+        if self.no_backers {
+            return err!(Error::Validation(format!(
+                "rotation of registry without backers = {regk}",
+                regk = self.pre()?
+            )));
+        }
+
+        if serder.pre()? != self.pre()? {
+            return err!(Error::Validation(format!(
+                "mismatched registry = {pre}, expected {regk}",
+                pre = serder.pre()?,
+                regk = self.pre()?
+            )));
+        }
+
+        let sn = serder.sn()?;
+        let expected = self.sn()? + 1;
+        if sn != expected {
+            return err!(Error::Validation(format!(
+                "invalid sn = {sn}, expected {expected} for registry rotation"
+            )));
+        }
+
+        let prior = field(serder, Ids::p)?;
+        if prior != self.serder.said()? {
+            return err!(Error::Validation(format!(
+                "invalid prior event digest = {prior}, expected {expected}",
+                expected = self.serder.said()?
+            )));
+        }
+
+        let cuts = strings(&value(serder, Ids::br)?)?;
+        let adds = strings(&value(serder, Ids::ba)?)?;
+        let baks = rotate_wits(&strs(&self.baks), &strs(&cuts), &strs(&adds))?;
+        let toader = toader(&value(serder, Ids::bt)?)?;
+        validate_toad(toader.num()?, &strs(&baks))?;
+
+        Ok((baks, toader))
+    }
+
+    fn validate_credential(&self, serder: &Serder, ilk: &str) -> Result<()> {
+        let vcdig = serder.pre()?;
+        let backed = ilk == Ilkage::bis || ilk == Ilkage::brv;
+        if backed == self.no_backers {
+            return err!(Error::Validation(format!(
+                "unexpected ilk = {ilk} for registry = {regk} with no_backers = {nb}",
+                regk = self.pre()?,
+                nb = self.no_backers
+            )));
+        }
+
+        // the registry, named directly or by the seal of its event in effect
+        if backed {
+            let seal = SealEvent::new_with_value(&value(serder, Ids::ra)?)?;
+            let known = self.regds.get(seal.s() as usize).is_some_and(|d| *d == seal.d());
+            if seal.i() != self.pre()? || !known {
+                return err!(Error::Validation(format!(
+                    "invalid registry seal = {seal:?} for registry = {regk}",
+                    regk = self.pre()?
+                )));
+            }
+        }
+        let regk = match ilk {
+            Ilkage::iss | Ilkage::rev => Some(field(serder, Ids::ri)?),
+            Ilkage::bis => Some(field(serder, Ids::ii)?),
+            _ => None,
+        };
+        if let Some(regk) = regk {
+            if regk != self.pre()? {
+                return err!(Error::Validation(format!(
+                    "credential event of another registry = {regk}"
+                )));
+            }
+        }
+
+        let issuance = ilk == Ilkage::iss || ilk == Ilkage::bis;
+        let sn = serder.sn()?;
+        let expected = if issuance { 0 } else { 1 };
+        if sn != expected {
+            return err!(Error::Validation(format!(
+                "invalid sn = {sn}, expected {expected} for ilk = {ilk}"
+            )));
+        }
+
+        match (issuance, self.credentials.get(&vcdig)) {
+            (true, None) => {}
+            (true, Some(_)) => {
+                return err!(Error::Validation(format!("credential = {vcdig} already issued")))
+            }
+            (false, Some(prior)) if prior.sn()? == 0 => {
+                let dig = field(serder, Ids::p)?;
+                if dig != prior.said()? {
+                    return err!(Error::Validation(format!(
+                        "invalid prior event digest = {dig}, expected {expected}",
+                        expected = prior.said()?
+                    )));
+                }
+            }
+            (false, _) => {
+                return err!(Error::Validation(format!(
+                    "revocation of credential = {vcdig} that is not issued"
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn prefixer(&self) -> Prefixer {
+        self.prefixer.clone()
+    }
+
+    pub fn pre(&self) -> Result<String> {
+        self.prefixer.qb64()
+    }
+
+    /// The identifier prefix of the issuer controlling the registry.
+    pub fn issuer(&self) -> String {
+        self.issuer.clone()
+    }
+
+    pub fn sner(&self) -> Number {
+        self.sner.clone()
+    }
+
+    pub fn sn(&self) -> Result<u128> {
+        self.sner.num()
+    }
+
+    /// The latest registry event.
+    pub fn serder(&self) -> Serder {
+        self.serder.clone()
+    }
+
+    pub fn toader(&self) -> Number {
+        self.toader.clone()
+    }
+
+    pub fn baks(&self) -> Vec<String> {
+        self.baks.clone()
+    }
+
+    pub fn no_backers(&self) -> bool {
+        self.no_backers
+    }
+
+    /// The latest event of credential `vcdig`, if it was issued in this registry.
+    pub fn credential(&self, vcdig: &str) -> Option<Serder> {
+        self.credentials.get(vcdig).cloned()
+    }
+
+    /// Whether credential `vcdig` is issued and not revoked.
+    pub fn issued(&self, vcdig: &str) -> Result<bool> {
+        match self.credentials.get(vcdig) {
+            Some(serder) => Ok(serder.sn()? == 0),
+            None => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Tever;
+    use crate::core::{
+        common::Traitage,
+        eventing::{anchors, incept as kel_incept, interact},
+        matter::{tables as matter, Matter},
+        sadder::Sadder,
+        serder::Serder,
+        signer::Signer,
+        tel::{backer_issue, backer_revoke, incept, issue, revoke, rotate},
+    };
+
+    const VCDIG: &str = "EDS6Z2lZ7zWBLBqBsvV_qPWFG0jR6ifdhjtt_9lMp0zE";
+    const OTHER: &str = "ELC5L3iBVD77d_MYbYGGCUQgqQBju1o4x1Ud-z2sL-ux";
+    const DT: &str = "2020-08-22T17:50:09.988921+00:00";
+    const BAKS: &[&str] = &[
+        "BEy_EvE8OUMqj0AgCJ3wOCOrIVHVtwubYAysPyaAv9VI",
+        "BC9Df6ssUZQFQZJYVUyfudw4WTQsugGcvVD_Z4ChFGE4",
+    ];
+
+    // an issuer key event log that anchors each event it is given in a new interaction event
+    struct Issuer {
+        last: Serder,
+    }
+
+    impl Issuer {
+        fn new() -> Self {
+            let signer = Signer::new_with_raw(&[0u8; 32], Some(true), None).unwrap();
+            let icp = kel_incept(
+                &[&signer.verfer().qb64().unwrap()],
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(matter::Codex::Blake3_256),
+                None,
+                None,
+            )
+            .unwrap();
+            Issuer { last: icp }
+        }
+
+        fn pre(&self) -> String {
+            self.last.pre().unwrap()
+        }
+
+        fn anchor(&mut self, serder: &Serder) -> Serder {
+            self.last = interact(
+                &self.pre(),
+                &self.last.said().unwrap(),
+                Some(self.last.sn().unwrap() + 1),
+                Some(&anchors(std::slice::from_ref(serder)).unwrap()),
+                None,
+                None,
+            )
+            .unwrap();
+            self.last.clone()
+        }
+    }
+
+    #[test]
+    fn validate_tel() {
+        let mut issuer = Issuer::new();
+        let vcp = incept(&issuer.pre(), None, None, None, Some(&[Traitage::NB]), None, None, None)
+            .unwrap();
+        let mut tever = Tever::new(&vcp, &issuer.anchor(&vcp)).unwrap();
+        assert_eq!(tever.pre().unwrap(), vcp.pre().unwrap());
+        assert_eq!(tever.issuer(), issuer.pre());
+        assert_eq!(tever.sn().unwrap(), 0);
+        assert!(tever.no_backers());
+        assert!(tever.baks().is_empty());
+        assert!(!tever.issued(VCDIG).unwrap());
+
+        let regk = tever.pre().unwrap();
+        let iss = issue(VCDIG, &regk, Some(DT), None, None).unwrap();
+        tever.update(&iss, &issuer.anchor(&iss)).unwrap();
+        assert!(tever.issued(VCDIG).unwrap());
+        assert_eq!(tever.credential(VCDIG), Some(iss.clone()));
+
+        let rev = revoke(VCDIG, &regk, &iss.said().unwrap(), Some(DT), None, None).unwrap();
+        tever.update(&rev, &issuer.anchor(&rev)).unwrap();
+        assert!(!tever.issued(VCDIG).unwrap());
+        assert_eq!(tever.credential(VCDIG), Some(rev.clone()));
+        assert_eq!(tever.serder(), vcp);
+
+        // a revoked credential stays revoked
+        let iss = issue(VCDIG, &regk, None, None, None).unwrap();
+        assert!(tever.update(&iss, &issuer.anchor(&iss)).is_err());
+        assert!(tever.update(&rev, &issuer.anchor(&rev)).is_err());
+
+        // registries without backers do not rotate or use backer events
+        let vrt =
+            rotate(&regk, &vcp.said().unwrap(), None, None, None, None, None, None, None).unwrap();
+        assert!(tever.update(&vrt, &issuer.anchor(&vrt)).is_err());
+        let bis = backer_issue(OTHER, &regk, 0, &vcp.said().unwrap(), None, None, None).unwrap();
+        assert!(tever.update(&bis, &issuer.anchor(&bis)).is_err());
+    }
+
+    #[test]
+    fn validate_backed_tel() {
+        let mut issuer = Issuer::new();
+        let vcp = incept(&issuer.pre(), None, Some(BAKS), None, None, None, None, None).unwrap();
+        let mut tever = Tever::new(&vcp, &issuer.anchor(&vcp)).unwrap();
+        assert!(!tever.no_backers());
+        assert_eq!(tever.toader().num().unwrap(), 2);
+
+        let regk = tever.pre().unwrap();
+        let vrt = rotate(
+            &regk,
+            &vcp.said().unwrap(),
+            None,
+            Some(1),
+            Some(BAKS),
+            Some(&[BAKS[0]]),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        tever.update(&vrt, &issuer.anchor(&vrt)).unwrap();
+        assert_eq!(tever.sn().unwrap(), 1);
+        assert_eq!(tever.baks(), vec![BAKS[1].to_string()]);
+        assert_eq!(tever.toader().num().unwrap(), 1);
+        assert_eq!(tever.serder(), vrt);
+
+        // sealed to the inception, still a registry event of this registry
+        let bis = backer_issue(VCDIG, &regk, 0, &vcp.said().unwrap(), None, None, None).unwrap();
+        tever.update(&bis, &issuer.anchor(&bis)).unwrap();
+        assert!(tever.issued(VCDIG).unwrap());
+
+        let brv = backer_revoke(
+            VCDIG,
+            &regk,
+            1,
+            &vrt.said().unwrap(),
+            &bis.said().unwrap(),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        tever.update(&brv, &issuer.anchor(&brv)).unwrap();
+        assert!(!tever.issued(VCDIG).unwrap());
+
+        // unknown registry events and plain issuances
+        let bis = backer_issue(OTHER, &regk, 2, &vrt.said().unwrap(), None, None, None).unwrap();
+        assert!(tever.update(&bis, &issuer.anchor(&bis)).is_err());
+        let bis = backer_issue(OTHER, &regk, 1, &vcp.said().unwrap(), None, None, None).unwrap();
+        assert!(tever.update(&bis, &issuer.anchor(&bis)).is_err());
+        let iss = issue(OTHER, &regk, None, None, None).unwrap();
+        assert!(tever.update(&iss, &issuer.anchor(&iss)).is_err());
+    }
+
+    #[test]
+    fn validate_tel_unhappy_paths() {
+        let mut issuer = Issuer::new();
+        let nb = Some(&[Traitage::NB][..]);
+        let vcp = incept(&issuer.pre(), None, None, None, nb, None, None, None).unwrap();
+
+        // not anchored, or anchored by someone else
+        assert!(Tever::new(&vcp, &issuer.last.clone()).is_err());
+        let mut other = Issuer::new();
+        other.last = interact(
+            OTHER,
+            &issuer.last.said().unwrap(),
+            None,
+            Some(&anchors(std::slice::from_ref(&vcp)).unwrap()),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(Tever::new(&vcp, &other.last).is_err());
+
+        let anchor = issuer.anchor(&vcp);
+        let mut tever = Tever::new(&vcp, &anchor).unwrap();
+        let regk = tever.pre().unwrap();
+        assert!(Tever::new(&issue(VCDIG, &regk, None, None, None).unwrap(), &anchor).is_err());
+
+        // a body swapped under an anchored said
+        let iss = issue(VCDIG, &regk, Some(DT), None, None).unwrap();
+        let anchored = issuer.anchor(&iss);
+        let mut ked = iss.ked();
+        ked["dt"] = dat!("2021-01-01T00:00:00.000000+00:00");
+        let forged = Serder::new_with_ked(&ked, None, None).unwrap();
+        assert_eq!(forged.said().unwrap(), iss.said().unwrap());
+        assert!(tever.update(&forged, &anchored).is_err());
+
+        // unanchored updates leave the state unchanged
+        let iss = issue(VCDIG, &regk, None, None, None).unwrap();
+        assert!(tever.update(&iss, &anchor).is_err());
+        assert!(!tever.issued(VCDIG).unwrap());
+
+        // another registry, or a revocation of a credential never issued
+        let iss = issue(VCDIG, OTHER, None, None, None).unwrap();
+        assert!(tever.update(&iss, &issuer.anchor(&iss)).is_err());
+        let rev = revoke(VCDIG, &regk, OTHER, None, None, None).unwrap();
+        assert!(tever.update(&rev, &issuer.anchor(&rev)).is_err());
+
+        let iss = issue(VCDIG, &regk, None, None, None).unwrap();
+        tever.update(&iss, &issuer.anchor(&iss)).unwrap();
+        let rev = revoke(VCDIG, &regk, OTHER, None, None, None).unwrap();
+        assert!(tever.update(&rev, &issuer.anchor(&rev)).is_err());
+        assert!(tever.update(&anchor, &anchor).is_err());
+    }
+}
@@ -4,13 +4,31 @@ use crate::{
         matter::{tables as matter, Matter},
         number::{tables as number, Number},
     },
-    data::{dat, Array, Value},
+    data::{Array, Object, Value},
     error::{err, Error, Result},
 };
 
-use lazy_static::lazy_static;
 use num_rational::Rational32;
 
+/// The result of evaluating signature indices against a threshold.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Evaluation {
+    /// Whether the threshold is satisfied.
+    pub satisfied: bool,
+    /// The weight accumulated in each clause. A numeric threshold has a single clause whose
+    /// weight is the number of signatures counted.
+    pub weights: Vec<Rational32>,
+    /// The indices that counted toward the threshold, in ascending order.
+    pub counted: Vec<u32>,
+    /// Indices beyond the keys the threshold covers.
+    pub out_of_range: Vec<u32>,
+    /// Indices given more than once.
+    pub duplicates: Vec<u32>,
+    /// The fewest additional indices that would satisfy the threshold, in ascending order. Empty
+    /// when the threshold is satisfied or can never be.
+    pub needed: Vec<u32>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Tholder {
     thold: Value,
@@ -26,49 +44,143 @@ impl Default for Tholder {
     }
 }
 
-fn values_to_rationals(value: &Value) -> Result<Vec<Vec<Rational32>>> {
-    let threshold = value.to_vec()?;
-    let separator = "/";
-    let mut clauses: Vec<Vec<Rational32>> = Vec::new();
+// a weight in a clause, either a fraction or a fraction that counts only once its own nested
+// clause of sub-weights is satisfied
+#[derive(Debug, Clone, PartialEq)]
+enum Weight {
+    Simple(Rational32),
+    Nested(Rational32, Vec<Rational32>),
+}
 
-    for _clause in threshold {
-        let mut clause: Vec<Rational32> = Vec::new();
-        let _clause = _clause.to_vec()?;
-        for weight in _clause {
-            let weight = weight.to_string()?;
-            let parts: Vec<&str> = weight.split(separator).collect();
-            if parts.len() != 2 {
-                // must be 0 or 1
-                if parts[0] == "0" {
-                    clause.push(Rational32::new(0, 1));
-                } else if parts[0] == "1" {
-                    clause.push(Rational32::new(1, 1));
-                } else {
-                    return err!(Error::Value("integral weight must be 0 or 1".to_string()));
-                }
-            } else {
-                let numer = parts[0].parse::<i32>()?;
-                let denom = parts[1].parse::<i32>()?;
-                if numer < 0 || denom < 0 {
-                    return err!(Error::Value("negative weights do not make sense".to_string()));
-                }
-                if numer > denom {
-                    return err!(Error::Value(format!("weight {numer}/{denom} > 1")));
-                }
-                clause.push(Rational32::new(numer, denom));
+impl Weight {
+    // the number of keys the weight applies to
+    fn size(&self) -> usize {
+        match self {
+            Weight::Simple(_) => 1,
+            Weight::Nested(_, weights) => weights.len(),
+        }
+    }
+
+    fn value(&self) -> Value {
+        match self {
+            Weight::Simple(weight) => dat!(&rational_to_string(weight)),
+            Weight::Nested(weight, weights) => {
+                let weights: Array = weights.iter().map(|w| dat!(&rational_to_string(w))).collect();
+                let mut nested = Object::new();
+                nested.insert(rational_to_string(weight), dat!(weights.as_slice()));
+                Value::Object(nested)
             }
         }
-        clauses.push(clause);
     }
 
-    for clause in &*clauses {
-        let mut sum = Rational32::new(0, 1);
+    fn bext(&self) -> String {
+        match self {
+            Weight::Simple(weight) => rational_to_bext(weight),
+            Weight::Nested(weight, weights) => format!(
+                "{k}k{v}",
+                k = rational_to_bext(weight),
+                v = weights.iter().map(rational_to_bext).collect::<Vec<_>>().join("v")
+            ),
+        }
+    }
+}
 
-        for weight in clause {
-            sum += weight;
+fn string_to_rational(weight: &str) -> Result<Rational32> {
+    let separator = "/";
+    let parts: Vec<&str> = weight.split(separator).collect();
+    if parts.len() != 2 {
+        // must be 0 or 1
+        if parts[0] == "0" {
+            Ok(Rational32::new(0, 1))
+        } else if parts[0] == "1" {
+            Ok(Rational32::new(1, 1))
+        } else {
+            err!(Error::Value("integral weight must be 0 or 1".to_string()))
+        }
+    } else {
+        let numer = parts[0].parse::<i32>()?;
+        let denom = parts[1].parse::<i32>()?;
+        if numer < 0 || denom < 0 {
+            return err!(Error::Value("negative weights do not make sense".to_string()));
         }
+        if numer > denom {
+            return err!(Error::Value(format!("weight {numer}/{denom} > 1")));
+        }
+        Ok(Rational32::new(numer, denom))
+    }
+}
 
-        if sum < Rational32::new(1, 1) {
+fn rational_to_string(weight: &Rational32) -> String {
+    if *weight.denom() == 1 {
+        format!("{n}", n = weight.numer())
+    } else {
+        weight.to_string()
+    }
+}
+
+fn rational_to_bext(weight: &Rational32) -> String {
+    if *weight.denom() == 1 {
+        format!("{n}", n = weight.numer())
+    } else {
+        format!("{n}s{d}", n = weight.numer(), d = weight.denom())
+    }
+}
+
+fn satisfied(weights: &[Rational32]) -> bool {
+    weights.iter().sum::<Rational32>() >= Rational32::new(1, 1)
+}
+
+fn value_to_weight(value: &Value, thold: &Value) -> Result<Weight> {
+    if let Ok(weight) = value.to_string() {
+        return Ok(Weight::Simple(string_to_rational(&weight)?));
+    }
+
+    let nested = value.to_map()?;
+    if nested.len() != 1 {
+        return err!(Error::Value(format!(
+            "invalid sith = {s}, nested weights must have a single key",
+            s = thold.to_json()?
+        )));
+    }
+
+    // bounds checked above
+    let (weight, weights) = nested.iter().next().unwrap();
+    let weights = weights
+        .to_vec()?
+        .iter()
+        .map(|w| string_to_rational(&w.to_string()?))
+        .collect::<Result<Vec<_>>>()?;
+    if !satisfied(&weights) {
+        return err!(Error::Value(format!(
+            "invalid sith = {s}, nested clause weight sums must be >= 1",
+            s = thold.to_json()?
+        )));
+    }
+
+    Ok(Weight::Nested(string_to_rational(weight)?, weights))
+}
+
+fn values_to_weights(value: &Value) -> Result<Vec<Vec<Weight>>> {
+    let threshold = value.to_vec()?;
+    let mut clauses: Vec<Vec<Weight>> = Vec::new();
+
+    for _clause in threshold {
+        let mut clause: Vec<Weight> = Vec::new();
+        for weight in _clause.to_vec()? {
+            clause.push(value_to_weight(&weight, value)?);
+        }
+        clauses.push(clause);
+    }
+
+    for clause in &*clauses {
+        let weights: Vec<Rational32> = clause
+            .iter()
+            .map(|weight| match weight {
+                Weight::Simple(weight) | Weight::Nested(weight, _) => *weight,
+            })
+            .collect();
+
+        if !satisfied(&weights) {
             return err!(Error::Value(format!(
                 "invalid sith clause = {}, clause weight sums must be >= 1",
                 value.to_json()?
@@ -79,17 +191,66 @@ fn values_to_rationals(value: &Value) -> Result<Vec<Vec<Rational32>>> {
     Ok(clauses)
 }
 
-fn rationals_to_bext(clauses: &Vec<Vec<Rational32>>) -> String {
-    let mut envelope: Vec<String> = Vec::new();
-    for clause in clauses {
-        let mut text_clause: Vec<String> = Vec::new();
-        for weight in clause {
-            if *weight.denom() == 1 {
-                text_clause.push(format!("{n}", n = weight.numer()));
-            } else {
-                text_clause.push(format!("{n}s{d}", n = weight.numer(), d = weight.denom()));
+// the fewest of `candidates` whose weights reach `deficit`, heaviest first
+fn fill(deficit: Rational32, candidates: &[(Rational32, u32)]) -> Option<Vec<u32>> {
+    let mut candidates = candidates.to_vec();
+    candidates.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    let mut sum = Rational32::new(0, 1);
+    let mut indices = vec![];
+    for (weight, index) in candidates {
+        if sum >= deficit {
+            break;
+        }
+        sum += weight;
+        indices.push(index);
+    }
+
+    if sum >= deficit {
+        Some(indices)
+    } else {
+        None
+    }
+}
+
+// the fewest indices that reach `deficit` in a clause, trying every combination of the nested
+// weights that could still be satisfied and filling the remainder from the simple weights
+fn cheapest(
+    deficit: Rational32,
+    simples: &[(Rational32, u32)],
+    nesteds: &[(Rational32, Vec<u32>)],
+) -> Vec<u32> {
+    let mut best: Option<Vec<u32>> = None;
+    for mask in 0..(1_usize << nesteds.len()) {
+        let mut weight = Rational32::new(0, 1);
+        let mut indices = vec![];
+        for (i, (w, needed)) in nesteds.iter().enumerate() {
+            if mask & (1 << i) != 0 {
+                weight += w;
+                indices.extend_from_slice(needed);
             }
         }
+
+        if weight < deficit {
+            match fill(deficit - weight, simples) {
+                Some(mut rest) => indices.append(&mut rest),
+                None => continue,
+            }
+        }
+
+        match &best {
+            Some(best) if best.len() <= indices.len() => {}
+            _ => best = Some(indices),
+        }
+    }
+
+    best.unwrap_or_default()
+}
+
+fn weights_to_bext(clauses: &[Vec<Weight>]) -> String {
+    let mut envelope: Vec<String> = Vec::new();
+    for clause in clauses {
+        let text_clause: Vec<String> = clause.iter().map(Weight::bext).collect();
         envelope.push(text_clause.join("c"));
     }
     envelope.join("a")
@@ -194,62 +355,119 @@ impl Tholder {
     }
 
     pub fn satisfy(&self, indices: &[u32]) -> Result<bool> {
-        if self.number().is_some() {
-            self.satisfy_numeric(indices)
-        } else if self.bexter().is_some() {
-            self.satisfy_weighted(indices)
-        } else {
-            Ok(false)
-        }
+        Ok(self.evaluate(indices)?.satisfied)
     }
 
-    fn satisfy_numeric(&self, indices: &[u32]) -> Result<bool> {
-        let thold = self.thold().to_i64()?;
+    /// Evaluates the signatures at `indices` against the threshold, reporting what counted and,
+    /// when unsatisfied, what is still missing. Duplicate indices count once, and indices beyond
+    /// the keys a weighted threshold covers do not count at all.
+    pub fn evaluate(&self, indices: &[u32]) -> Result<Evaluation> {
+        let mut evaluation = Evaluation::default();
+        let mut seen = vec![];
+        for index in indices.iter().copied() {
+            if seen.contains(&index) {
+                if !evaluation.duplicates.contains(&index) {
+                    evaluation.duplicates.push(index);
+                }
+                continue;
+            }
+            seen.push(index);
 
-        if thold > 0 && indices.len() >= thold as usize {
-            return Ok(true);
+            if self.weighted() && index >= self.size() {
+                evaluation.out_of_range.push(index);
+            } else {
+                evaluation.counted.push(index);
+            }
+        }
+        evaluation.counted.sort();
+
+        if self.number().is_some() {
+            self.evaluate_numeric(&mut evaluation)?;
+        } else if self.bexter().is_some() {
+            self.evaluate_weighted(&mut evaluation)?;
         }
 
-        Ok(false)
+        Ok(evaluation)
     }
 
-    fn satisfy_weighted(&self, indices: &[u32]) -> Result<bool> {
-        lazy_static! {
-            static ref RATIONAL_ONE: Rational32 = Rational32::new(1, 1);
+    fn evaluate_numeric(&self, evaluation: &mut Evaluation) -> Result<()> {
+        let thold = self.thold().to_i64()? as usize;
+        let count = evaluation.counted.len();
+
+        evaluation.weights = vec![Rational32::from_integer(i32::try_from(count)?)];
+        evaluation.satisfied = thold > 0 && count >= thold;
+        if thold > 0 && !evaluation.satisfied {
+            // the keys number at least the threshold, so the lowest free indices are all valid
+            evaluation.needed =
+                (0..).filter(|i| !evaluation.counted.contains(i)).take(thold - count).collect();
         }
 
-        let mut indices = indices.to_vec();
-        indices.sort();
-        indices.dedup();
+        Ok(())
+    }
 
+    fn evaluate_weighted(&self, evaluation: &mut Evaluation) -> Result<()> {
         let mut sats = vec![false; self.size() as usize];
-        for index in indices {
-            sats[index as usize] = true
+        for index in &evaluation.counted {
+            sats[*index as usize] = true
         }
 
-        let clauses = values_to_rationals(&self.thold())?;
+        let clauses = values_to_weights(&self.thold())?;
 
-        let mut wio: usize = 0;
+        evaluation.satisfied = true;
+        let mut wio: u32 = 0;
         for clause in clauses {
             let mut cw = Rational32::new(0, 1);
+            // unsatisfied weights, and the indices that would satisfy the nested ones
+            let mut simples = vec![];
+            let mut nesteds = vec![];
             for weight in clause {
-                if sats[wio] {
-                    cw += weight;
+                match weight {
+                    Weight::Simple(weight) => {
+                        if sats[wio as usize] {
+                            cw += weight;
+                        } else {
+                            simples.push((weight, wio));
+                        }
+                        wio += 1;
+                    }
+                    Weight::Nested(weight, weights) => {
+                        let mut nw = Rational32::new(0, 1);
+                        let mut missing = vec![];
+                        for w in weights {
+                            if sats[wio as usize] {
+                                nw += w;
+                            } else {
+                                missing.push((w, wio));
+                            }
+                            wio += 1;
+                        }
+                        if satisfied(&[nw]) {
+                            cw += weight;
+                        } else if let Some(needed) = fill(Rational32::new(1, 1) - nw, &missing) {
+                            nesteds.push((weight, needed));
+                        }
+                    }
                 }
-                wio += 1;
             }
-            if cw < *RATIONAL_ONE {
-                return Ok(false);
+
+            evaluation.weights.push(cw);
+            if !satisfied(&[cw]) {
+                evaluation.satisfied = false;
+                evaluation.needed.append(&mut cheapest(
+                    Rational32::new(1, 1) - cw,
+                    &simples,
+                    &nesteds,
+                ));
             }
         }
+        evaluation.needed.sort();
 
-        Ok(true)
+        Ok(())
     }
 
     fn process_thold(&mut self, thold: &Value) -> Result<()> {
-        let result = thold.to_i64();
-        if result.is_ok() {
-            self.process_unweighted(result.unwrap())?;
+        if let Ok(thold) = thold.to_i64() {
+            self.process_unweighted(thold)?;
             return Ok(());
         }
 
@@ -281,7 +499,14 @@ impl Tholder {
                 let weights: Vec<&str> = clause.split('c').collect();
                 let mut oweights: Array = Vec::new();
                 for weight in weights {
-                    oweights.push(dat!(weight));
+                    if let Some((weight, nested)) = weight.split_once('k') {
+                        let nested: Array = nested.split('v').map(|w| dat!(w)).collect();
+                        let mut oweight = Object::new();
+                        oweight.insert(weight.to_string(), dat!(nested.as_slice()));
+                        oweights.push(Value::Object(oweight));
+                    } else {
+                        oweights.push(dat!(weight));
+                    }
                 }
                 oclauses.push(dat!(oweights.as_slice()));
             }
@@ -329,9 +554,9 @@ impl Tholder {
         for clause in sith.to_vec()? {
             let _clause = clause.to_vec()?;
             for weight in _clause {
-                if weight.to_string().is_err() {
+                if weight.to_string().is_err() && weight.to_map().is_err() {
                     return err!(Error::Value(format!(
-                        "invalid sith = {s}, some weights in clause {c} are not strings",
+                        "invalid sith = {s}, some weights in clause {c} are not strings or maps",
                         s = sith.to_json()?,
                         c = clause.to_json()?
                     )));
@@ -361,21 +586,14 @@ impl Tholder {
     }
 
     fn process_weighted(&mut self, thold: &Value) -> Result<()> {
-        let threshold = &values_to_rationals(thold)?;
+        let threshold = &values_to_weights(thold)?;
         let mut size = 0;
         for clause in threshold {
-            size += clause.len() as u32;
+            size += clause.iter().map(Weight::size).sum::<usize>() as u32;
         }
         let mut outer: Vec<Value> = Vec::new();
         for clause in threshold {
-            let mut inner: Vec<Value> = Vec::new();
-            for weight in clause {
-                if *weight.denom() == 1 {
-                    inner.push(dat!(&format!("{n}", n = weight.numer())));
-                } else {
-                    inner.push(dat!(&weight.to_string()));
-                }
-            }
+            let inner: Vec<Value> = clause.iter().map(Weight::value).collect();
             outer.push(dat!(inner.as_slice()));
         }
 
@@ -383,7 +601,7 @@ impl Tholder {
         self.weighted = true;
         self.size = size;
         self.number = None;
-        let bext = rationals_to_bext(threshold);
+        let bext = weights_to_bext(threshold);
         self.bexter = Some(Bexter::new(Some(&bext), None, None, None, None, None)?);
 
         Ok(())
@@ -393,6 +611,7 @@ impl Tholder {
 #[cfg(test)]
 mod test {
     use crate::core::tholder::Tholder;
+    use num_rational::Rational32;
     use rstest::rstest;
 
     #[test]
@@ -628,4 +847,155 @@ mod test {
         assert!(!tholder.satisfy(&[2, 3, 4]).unwrap());
         assert!(!tholder.satisfy(&[]).unwrap());
     }
+
+    #[rstest]
+    #[case(Tholder::new_with_sith(&dat!([{"1/3": ["1/2", "1/2", "1/2"]}, "1/3", "1/2", {"1/2": ["1", "1"]}])).unwrap())]
+    #[case(Tholder::new_with_sith(&dat!("[{\"1/3\":[\"1/2\",\"1/2\",\"1/2\"]},\"1/3\",\"1/2\",{\"1/2\":[\"1\",\"1\"]}]")).unwrap())]
+    #[case(Tholder::new_with_thold(&dat!([[{"1/3": ["1/2", "1/2", "1/2"]}, "1/3", "1/2", {"1/2": ["1/1", "1"]}]])).unwrap())]
+    #[case(Tholder::new_with_limen(b"4AAIA1s3k1s2v1s2v1s2c1s3c1s2c1s2k1v1").unwrap())]
+    fn nested_weighted(#[case] tholder: Tholder) {
+        assert!(tholder.weighted());
+        assert_eq!(tholder.size(), 7);
+        assert_eq!(
+            tholder.thold(),
+            dat!([[{"1/3": ["1/2", "1/2", "1/2"]}, "1/3", "1/2", {"1/2": ["1", "1"]}]])
+        );
+        assert_eq!(tholder.limen().unwrap(), b"4AAIA1s3k1s2v1s2v1s2c1s3c1s2c1s2k1v1");
+        assert_eq!(
+            tholder.sith().unwrap(),
+            dat!([{"1/3": ["1/2", "1/2", "1/2"]}, "1/3", "1/2", {"1/2": ["1", "1"]}])
+        );
+        assert_eq!(
+            tholder.to_json().unwrap(),
+            "[{\"1/3\":[\"1/2\",\"1/2\",\"1/2\"]},\"1/3\",\"1/2\",{\"1/2\":[\"1\",\"1\"]}]"
+        );
+        assert_eq!(tholder.num().unwrap(), None);
+
+        assert!(tholder.satisfy(&[0, 1, 2, 3, 4, 5, 6]).unwrap());
+        assert!(tholder.satisfy(&[0, 1, 3, 4]).unwrap());
+        assert!(tholder.satisfy(&[3, 4, 5, 6]).unwrap());
+        assert!(tholder.satisfy(&[1, 2, 3, 5, 6]).unwrap());
+        assert!(!tholder.satisfy(&[1, 2, 5, 6]).unwrap());
+        // one of three nested signatures contributes nothing
+        assert!(!tholder.satisfy(&[0, 3, 4]).unwrap());
+        assert!(!tholder.satisfy(&[0, 1, 2, 3]).unwrap());
+        assert!(tholder.satisfy(&[5, 6, 4]).unwrap());
+        assert!(!tholder.satisfy(&[3, 5]).unwrap());
+        assert!(!tholder.satisfy(&[]).unwrap());
+
+        let round_trip = Tholder::new_with_limen(&tholder.limen().unwrap()).unwrap();
+        assert_eq!(round_trip, tholder);
+        assert_eq!(Tholder::new_with_sith(&tholder.sith().unwrap()).unwrap(), tholder);
+    }
+
+    #[test]
+    fn nested_weighted_multi_clause() {
+        let sith = dat!([[{"1/2": ["1/2", "1/2"]}, "1/2"], ["1", {"1": ["1/3", "1/3", "1/3"]}]]);
+        let tholder = Tholder::new_with_sith(&sith).unwrap();
+        assert_eq!(tholder.size(), 7);
+        assert_eq!(tholder.sith().unwrap(), sith);
+        assert_eq!(tholder.limen().unwrap(), b"4AAIA1s2k1s2v1s2c1s2a1c1k1s3v1s3v1s3");
+        assert_eq!(Tholder::new_with_limen(&tholder.limen().unwrap()).unwrap(), tholder);
+
+        assert!(tholder.satisfy(&[0, 1, 2, 3]).unwrap());
+        assert!(tholder.satisfy(&[0, 1, 2, 4, 5, 6]).unwrap());
+        assert!(!tholder.satisfy(&[0, 2, 3]).unwrap());
+        assert!(!tholder.satisfy(&[0, 1, 2, 4, 5]).unwrap());
+    }
+
+    #[test]
+    fn nested_weighted_unhappy() {
+        // nested clauses must sum to at least one, like top level clauses
+        assert!(Tholder::new_with_sith(&dat!([{"1/2": ["1/2", "1/4"]}, "1/2"])).is_err());
+        assert!(Tholder::new_with_sith(&dat!([{"1/2": ["1/2", "1/2"]}, "1/4"])).is_err());
+        assert!(Tholder::new_with_sith(&dat!([{"1/2": ["1", "1"], "1/3": ["1"]}, "1/2"])).is_err());
+        assert!(Tholder::new_with_sith(&dat!([{"1/2": []}, "1/2"])).is_err());
+        assert!(Tholder::new_with_sith(&dat!([{"1/2": "1"}, "1/2"])).is_err());
+        assert!(Tholder::new_with_sith(&dat!([{"1/2": [1, 1]}, "1/2"])).is_err());
+        assert!(Tholder::new_with_sith(&dat!([{"3/2": ["1"]}])).is_err());
+        assert!(Tholder::new_with_sith(&dat!([{"1/2": [{"1": ["1"]}]}, "1/2"])).is_err());
+    }
+
+    fn rationals(weights: &[(i32, i32)]) -> Vec<Rational32> {
+        weights.iter().map(|(n, d)| Rational32::new(*n, *d)).collect()
+    }
+
+    #[test]
+    fn evaluate_numeric() {
+        let tholder = Tholder::new_with_sith(&dat!(3)).unwrap();
+
+        let evaluation = tholder.evaluate(&[4, 1, 1]).unwrap();
+        assert!(!evaluation.satisfied);
+        assert_eq!(evaluation.weights, rationals(&[(2, 1)]));
+        assert_eq!(evaluation.counted, vec![1, 4]);
+        assert_eq!(evaluation.duplicates, vec![1]);
+        assert!(evaluation.out_of_range.is_empty());
+        assert_eq!(evaluation.needed, vec![0]);
+
+        let evaluation = tholder.evaluate(&[2, 0, 1]).unwrap();
+        assert!(evaluation.satisfied);
+        assert!(evaluation.needed.is_empty());
+
+        // duplicates no longer satisfy a numeric threshold
+        assert!(!tholder.satisfy(&[0, 0, 0]).unwrap());
+        assert_eq!(tholder.evaluate(&[]).unwrap().needed, vec![0, 1, 2]);
+
+        let evaluation = Tholder::new_with_sith(&dat!(0)).unwrap().evaluate(&[0]).unwrap();
+        assert!(!evaluation.satisfied);
+        assert!(evaluation.needed.is_empty());
+    }
+
+    #[test]
+    fn evaluate_weighted() {
+        let tholder =
+            Tholder::new_with_sith(&dat!([["1/2", "1/2", "1/4", "1/4", "1/4"], ["1", "1"]]))
+                .unwrap();
+
+        // out of range indices used to panic
+        assert!(!tholder.satisfy(&[0, 7, 100]).unwrap());
+
+        let evaluation = tholder.evaluate(&[2, 7, 2, 100, 7]).unwrap();
+        assert!(!evaluation.satisfied);
+        assert_eq!(evaluation.weights, rationals(&[(1, 4), (0, 1)]));
+        assert_eq!(evaluation.counted, vec![2]);
+        assert_eq!(evaluation.out_of_range, vec![7, 100]);
+        assert_eq!(evaluation.duplicates, vec![2, 7]);
+        assert_eq!(evaluation.needed, vec![0, 1, 5]);
+
+        let evaluation = tholder.evaluate(&[2, 3, 6]).unwrap();
+        assert_eq!(evaluation.weights, rationals(&[(1, 2), (1, 1)]));
+        assert_eq!(evaluation.needed, vec![0]);
+        assert!(tholder.satisfy(&[0, 2, 3, 6]).unwrap());
+
+        let evaluation = tholder.evaluate(&[0, 2, 3, 6]).unwrap();
+        assert!(evaluation.satisfied);
+        assert_eq!(evaluation.weights, rationals(&[(1, 1), (1, 1)]));
+        assert!(evaluation.needed.is_empty());
+    }
+
+    #[test]
+    fn evaluate_nested_weighted() {
+        let tholder = Tholder::new_with_sith(&dat!([
+            {"1/3": ["1/2", "1/2", "1/2"]},
+            "1/3",
+            "1/2",
+            {"1/2": ["1", "1"]}
+        ]))
+        .unwrap();
+
+        let evaluation = tholder.evaluate(&[0]).unwrap();
+        assert_eq!(evaluation.weights, rationals(&[(0, 1)]));
+        // half the clause from one nested key, the rest from the heaviest simple weight
+        assert_eq!(evaluation.needed, vec![4, 5]);
+        assert!(tholder.satisfy(&[0, 4, 5]).unwrap());
+
+        let evaluation = tholder.evaluate(&[0, 1, 3]).unwrap();
+        assert_eq!(evaluation.weights, rationals(&[(2, 3)]));
+        assert_eq!(evaluation.needed, vec![4]);
+        assert!(tholder.satisfy(&[0, 1, 3, 4]).unwrap());
+
+        let evaluation = tholder.evaluate(&[3, 4, 6]).unwrap();
+        assert!(evaluation.satisfied);
+        assert_eq!(evaluation.weights, rationals(&[(4, 3)]));
+    }
 }
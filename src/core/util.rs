@@ -281,6 +281,10 @@ pub fn nab_sextets(binary: &[u8], count: usize) -> Result<Vec<u8>> {
     Ok(out[..count].to_vec())
 }
 
+pub(crate) fn strs(items: &[String]) -> Vec<&str> {
+    items.iter().map(|item| item.as_str()).collect()
+}
+
 #[cfg(test)]
 mod test {
     use crate::core::util;
@@ -1,4 +1,5 @@
 pub(crate) mod csprng;
 pub(crate) mod hash;
 pub(crate) mod salt;
+pub(crate) mod seal;
 pub(crate) mod sign;
@@ -0,0 +1,94 @@
+use crypto_box::{PublicKey, SecretKey};
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use rand_core::OsRng;
+use sha2::{Digest, Sha512};
+use zeroize::Zeroize;
+
+use crate::error::{err, Error, Result};
+
+// libsodium crypto_box_seal compatible sealed boxes, as implemented by crypto_box. the ed25519
+// to x25519 key conversions match libsodium's crypto_sign_ed25519_*_to_curve25519.
+
+const KEY_SIZE: usize = crypto_box::KEY_SIZE;
+
+pub(crate) const OVERHEAD: usize = crypto_box::SEALBYTES;
+
+fn key(bytes: &[u8]) -> Result<[u8; KEY_SIZE]> {
+    match bytes.try_into() {
+        Ok(key) => Ok(key),
+        Err(_) => err!(Error::Value(format!("invalid x25519 key size = {}", bytes.len()))),
+    }
+}
+
+/// Converts an Ed25519 public verification key into an X25519 public encryption key.
+pub(crate) fn public_key_from_ed25519(verkey: &[u8]) -> Result<Vec<u8>> {
+    let point = match CompressedEdwardsY(key(verkey)?).decompress() {
+        Some(point) => point,
+        None => return err!(Error::Value("invalid ed25519 public key".to_string())),
+    };
+
+    Ok(point.to_montgomery().to_bytes().to_vec())
+}
+
+/// Converts an Ed25519 seed into an X25519 private decryption key.
+pub(crate) fn private_key_from_ed25519(seed: &[u8]) -> Result<Vec<u8>> {
+    let mut hash = Sha512::digest(key(seed)?);
+    let mut private_key = hash[..KEY_SIZE].to_vec();
+    hash.zeroize();
+
+    private_key[0] &= 248;
+    private_key[31] &= 127;
+    private_key[31] |= 64;
+
+    Ok(private_key)
+}
+
+pub(crate) fn seal(public_key: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+    match PublicKey::from_bytes(key(public_key)?).seal(&mut OsRng, msg) {
+        Ok(sealed) => Ok(sealed),
+        Err(_) => err!(Error::Value("unable to seal message".to_string())),
+    }
+}
+
+pub(crate) fn open(private_key: &[u8], sealed: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() < OVERHEAD {
+        return err!(Error::Value(format!("invalid sealed box size = {}", sealed.len())));
+    }
+
+    match SecretKey::from_bytes(key(private_key)?).unseal(sealed) {
+        Ok(msg) => Ok(msg),
+        Err(_) => err!(Error::Validation("sealed box failed authentication".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::matter::tables as matter;
+    use crate::crypto::{seal, sign};
+    use hex_literal::hex;
+
+    #[test]
+    fn seal_and_open() {
+        let seed = hex!("9f7ba8a7a843399626fab199ebaa20c41b4711c4ae534152c9bd049d85297e93");
+        let private_key = seal::private_key_from_ed25519(&seed).unwrap();
+        let verkey = sign::public_key(matter::Codex::Ed25519_Seed, &seed).unwrap();
+        let public_key = seal::public_key_from_ed25519(&verkey).unwrap();
+
+        let msg = b"the quick brown fox jumps over the lazy dog, a few more times than one block";
+        let sealed = seal::seal(&public_key, msg).unwrap();
+        assert_eq!(sealed.len(), msg.len() + seal::OVERHEAD);
+        assert_eq!(seal::open(&private_key, &sealed).unwrap(), msg);
+
+        let mut tampered = sealed.clone();
+        tampered[40] ^= 1;
+        assert!(seal::open(&private_key, &tampered).is_err());
+        assert!(seal::open(&private_key, &sealed[..40]).is_err());
+    }
+
+    #[test]
+    fn unhappy_paths() {
+        assert!(seal::public_key_from_ed25519(&[0u8; 31]).is_err());
+        assert!(seal::private_key_from_ed25519(&[0u8; 33]).is_err());
+        assert!(seal::seal(&[0u8; 16], b"abc").is_err());
+    }
+}
@@ -15,6 +15,10 @@ pub(crate) fn generate(code: &str) -> Result<Vec<u8>> {
         | matter::Codex::ECDSA_256r1N
         | matter::Codex::ECDSA_256r1_Seed
         | matter::Codex::ECDSA_256r1_Sig => ecdsa_256r1::generate(),
+        matter::Codex::Ed448
+        | matter::Codex::Ed448N
+        | matter::Codex::Ed448_Seed
+        | matter::Codex::Ed448_Sig => ed448::generate(),
         _ => err!(Error::UnexpectedCode(code.to_string())),
     }
 }
@@ -33,6 +37,10 @@ pub(crate) fn public_key(code: &str, private_key: &[u8]) -> Result<Vec<u8>> {
         | matter::Codex::ECDSA_256r1N
         | matter::Codex::ECDSA_256r1_Seed
         | matter::Codex::ECDSA_256r1_Sig => ecdsa_256r1::public_key(private_key),
+        matter::Codex::Ed448
+        | matter::Codex::Ed448N
+        | matter::Codex::Ed448_Seed
+        | matter::Codex::Ed448_Sig => ed448::public_key(private_key),
         _ => err!(Error::UnexpectedCode(code.to_string())),
     }
 }
@@ -51,6 +59,10 @@ pub(crate) fn sign(code: &str, private_key: &[u8], ser: &[u8]) -> Result<Vec<u8>
         | matter::Codex::ECDSA_256r1N
         | matter::Codex::ECDSA_256r1_Seed
         | matter::Codex::ECDSA_256r1_Sig => ecdsa_256r1::sign(private_key, ser),
+        matter::Codex::Ed448
+        | matter::Codex::Ed448N
+        | matter::Codex::Ed448_Seed
+        | matter::Codex::Ed448_Sig => ed448::sign(private_key, ser),
         _ => err!(Error::UnexpectedCode(code.to_string())),
     }
 }
@@ -69,6 +81,10 @@ pub(crate) fn verify(code: &str, public_key: &[u8], sig: &[u8], ser: &[u8]) -> R
         | matter::Codex::ECDSA_256r1N
         | matter::Codex::ECDSA_256r1_Seed
         | matter::Codex::ECDSA_256r1_Sig => ecdsa_256r1::verify(public_key, sig, ser),
+        matter::Codex::Ed448
+        | matter::Codex::Ed448N
+        | matter::Codex::Ed448_Seed
+        | matter::Codex::Ed448_Sig => ed448::verify(public_key, sig, ser),
         _ => err!(Error::UnexpectedCode(code.to_string())),
     }
 }
@@ -116,6 +132,70 @@ mod ed25519 {
     }
 }
 
+mod ed448 {
+    use ed448_goldilocks_plus::{SecretKey, Signature, SigningKey, VerifyingKey};
+    use zeroize::Zeroize;
+
+    use crate::crypto::csprng;
+    use crate::error::{err, Error, Result};
+
+    const SEED_SIZE: usize = 56;
+    const KEY_SIZE: usize = 57;
+
+    // cesr seeds are 448 bits (56 bytes) while rfc 8032 secret keys are 57 bytes of random data,
+    // so a seed is the secret key with its last byte zero. 57 byte secret keys are used as is.
+    fn signing_key(private_key: &[u8]) -> Result<SigningKey> {
+        let mut secret = SecretKey::default();
+        match private_key.len() {
+            SEED_SIZE => secret[..SEED_SIZE].copy_from_slice(private_key),
+            KEY_SIZE => secret.copy_from_slice(private_key),
+            _ => {
+                return err!(Error::Value(format!(
+                    "invalid ed448 private key size = {}",
+                    private_key.len()
+                )))
+            }
+        }
+
+        let key = SigningKey::from(&secret);
+        secret[..].zeroize();
+        Ok(key)
+    }
+
+    pub(crate) fn generate() -> Result<Vec<u8>> {
+        let mut private_key = vec![0u8; SEED_SIZE];
+        csprng::fill_bytes(&mut private_key);
+        Ok(private_key)
+    }
+
+    pub(crate) fn public_key(private_key: &[u8]) -> Result<Vec<u8>> {
+        Ok(signing_key(private_key)?.verifying_key().to_bytes().to_vec())
+    }
+
+    pub(crate) fn sign(private_key: &[u8], ser: &[u8]) -> Result<Vec<u8>> {
+        match signing_key(private_key)?.sign_raw(ser) {
+            Ok(signature) => Ok(signature.to_bytes().to_vec()),
+            Err(_) => err!(Error::Value("unable to sign with ed448".to_string())),
+        }
+    }
+
+    pub(crate) fn verify(public_key: &[u8], sig: &[u8], ser: &[u8]) -> Result<bool> {
+        let public_key = match VerifyingKey::from_bytes(public_key.try_into()?) {
+            Ok(public_key) => public_key,
+            Err(_) => return err!(Error::Value("invalid ed448 public key".to_string())),
+        };
+        let signature = match Signature::try_from(sig) {
+            Ok(signature) => signature,
+            Err(_) => return err!(Error::Value("invalid ed448 signature".to_string())),
+        };
+
+        match public_key.verify_raw(&signature, ser) {
+            Ok(_) => Ok(true),
+            Err(_) => Ok(false),
+        }
+    }
+}
+
 mod ecdsa_256k1 {
     use k256::ecdsa::{
         signature::{RandomizedSigner, Verifier},
@@ -200,7 +280,12 @@ mod test {
 
     #[rstest]
     fn end_to_end(
-        #[values(matter::Codex::Ed25519, matter::Codex::ECDSA_256k1, matter::Codex::ECDSA_256r1)]
+        #[values(
+            matter::Codex::Ed25519,
+            matter::Codex::ECDSA_256k1,
+            matter::Codex::ECDSA_256r1,
+            matter::Codex::Ed448
+        )]
         code: &str,
     ) {
         let ser = b"abcdefghijklmnopqrstuvwxyz";
@@ -210,6 +295,58 @@ mod test {
         assert!(sign::verify(code, &public_key, &signature, ser).unwrap());
     }
 
+    #[rstest]
+    #[case(
+        "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b",
+        "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180",
+        "",
+        "533a37f6bbe457251f023c0d88f976ae2dfb504a843e34d2074fd823d41a591f2b233f034f628281f2fd7a22ddd47d7828c59bd0a21bfd3980ff0d2028d4b18a9df63e006c5d1c2d345b925d8dc00b4104852db99ac5c7cdda8530a113a0f4dbb61149f05a7363268c71d95808ff2e652600"
+    )]
+    #[case(
+        "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+        "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+        "03",
+        "26b8f91727bd62897af15e41eb43c377efb9c610d48f2335cb0bd0087810f4352541b143c4b981b7e18f62de8ccdf633fc1bf037ab7cd779805e0dbcc0aae1cbcee1afb2e027df36bc04dcecbf154336c19f0af7e0a6472905e799f1953d2a0ff3348ab21aa4adafd1d234441cf807c03a00"
+    )]
+    fn ed448_rfc8032(
+        #[case] private_key: &str,
+        #[case] public_key: &str,
+        #[case] ser: &str,
+        #[case] signature: &str,
+    ) {
+        let code = matter::Codex::Ed448;
+        let private_key = hex::decode(private_key).unwrap();
+        let public_key = hex::decode(public_key).unwrap();
+        let ser = hex::decode(ser).unwrap();
+        let signature = hex::decode(signature).unwrap();
+
+        assert_eq!(sign::public_key(code, &private_key).unwrap(), public_key);
+        assert_eq!(sign::sign(code, &private_key, &ser).unwrap(), signature);
+        assert!(sign::verify(code, &public_key, &signature, &ser).unwrap());
+        assert!(!sign::verify(code, &public_key, &signature, b"tampered").unwrap());
+    }
+
+    #[test]
+    fn ed448_seed() {
+        let code = matter::Codex::Ed448;
+        let seed = hex::decode("c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a1227").unwrap();
+        // the rfc 8032 secret key is the seed followed by a zero byte
+        let mut secret = seed.clone();
+        secret.push(0);
+        let ser = b"abcdefghijklmnopqrstuvwxyz";
+
+        // from openssl with the 57 byte secret key
+        let public_key = hex::decode("b84afc7934411696efa457c28f6359c228a349fd9b8caf8751fa5a11342b8898b4cbd8e57d48959f25caee9c8d999bc3cc8433bb999d087800").unwrap();
+        let signature = hex::decode("3fb2821cfe525326a26b7304c76c7bf051ed828089b43f0c758ce7a7e1d05320f0a0d4fc5eeb5349dec62625080eff2cddb5a90163f436f40064f9bcf438e25ec4e8152b5e697535002bf4210888dba0f7da021e0dd1533381c8b8c89095dffd1b3fb6b8cdd6c5735cb1f37f3872dfc22600").unwrap();
+
+        assert_eq!(sign::public_key(code, &seed).unwrap(), public_key);
+        assert_eq!(sign::public_key(code, &secret).unwrap(), public_key);
+        assert_eq!(sign::sign(code, &seed, ser).unwrap(), signature);
+        assert!(sign::verify(code, &public_key, &signature, ser).unwrap());
+        assert!(sign::public_key(code, &secret[..55]).is_err());
+        assert!(sign::verify(code, &[0; 57], &[0; 114], ser).is_err());
+    }
+
     #[test]
     fn unhappy_paths() {
         let code = matter::Codex::SHA3_256;
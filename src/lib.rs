@@ -8,26 +8,45 @@ mod error;
 
 pub use crate::{
     core::{
+        attachments::{Attachments, Group},
         bexter::{Bext, Bexter},
         cigar::Cigar,
+        cipher::Cipher,
         common,
         counter::{tables as counter, Counter}, // This seems like it shoudl be an abstract class
         creder::Creder,
         dater::Dater,
+        decrypter::Decrypter,
         diger::Diger,
+        encrypter::Encrypter,
+        eventing,
+        exchanging::{self, Exchange},
+        grouping,
         indexer::{tables as indexer, Indexer},
+        ipex,
+        kever::{Kever, LastEst},
         matter::{tables as matter, Matter},
         number::{tables as number, Number},
+        oobing::{self, EndRole, Fetcher, LocScheme, Oobi, Resolution},
+        parser::{Body, Message, Parser},
         pather::Pather,
         prefixer::Prefixer,
+        proving,
+        routing::{Handler, Revery, Router},
         sadder::Sadder,
         saider::Saider,
         salter::Salter,
+        schemer::{SchemaRegistry, Schemer, Violation},
+        seal::{SealDigest, SealEvent, SealLocation, SealRoot},
         seqner::Seqner,
         serder::Serder,
         siger::Siger,
         signer::Signer,
-        tholder::Tholder,
+        signing,
+        state::{KeyState, StateEst},
+        tel,
+        tever::Tever,
+        tholder::{Evaluation, Tholder},
         verfer::Verfer,
     },
     error::Error,